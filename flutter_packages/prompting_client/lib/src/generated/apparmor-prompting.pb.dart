@@ -11,14 +11,324 @@
 
 import 'dart:core' as $core;
 
+import 'package:fixnum/fixnum.dart' as $fixnum;
 import 'package:protobuf/protobuf.dart' as $pb;
 
 import 'apparmor-prompting.pbenum.dart';
 
 export 'apparmor-prompting.pbenum.dart';
 
+class WatchPromptsResponse extends $pb.GeneratedMessage {
+  factory WatchPromptsResponse({
+    WatchPromptsResponse_PromptStatus? status,
+    $core.String? promptId,
+    GetCurrentPromptResponse? currentPrompt,
+    PromptQueue? queue,
+  }) {
+    final $result = create();
+    if (status != null) {
+      $result.status = status;
+    }
+    if (promptId != null) {
+      $result.promptId = promptId;
+    }
+    if (currentPrompt != null) {
+      $result.currentPrompt = currentPrompt;
+    }
+    if (queue != null) {
+      $result.queue = queue;
+    }
+    return $result;
+  }
+  WatchPromptsResponse._() : super();
+  factory WatchPromptsResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory WatchPromptsResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'WatchPromptsResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..e<WatchPromptsResponse_PromptStatus>(1, _omitFieldNames ? '' : 'status', $pb.PbFieldType.OE, defaultOrMaker: WatchPromptsResponse_PromptStatus.NO_PROMPT, valueOf: WatchPromptsResponse_PromptStatus.valueOf, enumValues: WatchPromptsResponse_PromptStatus.values)
+    ..aOS(2, _omitFieldNames ? '' : 'promptId')
+    ..aOM<GetCurrentPromptResponse>(3, _omitFieldNames ? '' : 'currentPrompt', subBuilder: GetCurrentPromptResponse.create)
+    ..aOM<PromptQueue>(4, _omitFieldNames ? '' : 'queue', subBuilder: PromptQueue.create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  WatchPromptsResponse clone() => WatchPromptsResponse()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  WatchPromptsResponse copyWith(void Function(WatchPromptsResponse) updates) => super.copyWith((message) => updates(message as WatchPromptsResponse)) as WatchPromptsResponse;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static WatchPromptsResponse create() => WatchPromptsResponse._();
+  WatchPromptsResponse createEmptyInstance() => create();
+  static $pb.PbList<WatchPromptsResponse> createRepeated() => $pb.PbList<WatchPromptsResponse>();
+  @$core.pragma('dart2js:noInline')
+  static WatchPromptsResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<WatchPromptsResponse>(create);
+  static WatchPromptsResponse? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  WatchPromptsResponse_PromptStatus get status => $_getN(0);
+  @$pb.TagNumber(1)
+  set status(WatchPromptsResponse_PromptStatus v) { setField(1, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasStatus() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearStatus() => clearField(1);
+
+  /// Not set when the status is NO_PROMPT
+  @$pb.TagNumber(2)
+  $core.String get promptId => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set promptId($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasPromptId() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearPromptId() => clearField(2);
+
+  /// Only set when the status is ACTIVE
+  @$pb.TagNumber(3)
+  GetCurrentPromptResponse get currentPrompt => $_getN(2);
+  @$pb.TagNumber(3)
+  set currentPrompt(GetCurrentPromptResponse v) { setField(3, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasCurrentPrompt() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearCurrentPrompt() => clearField(3);
+  @$pb.TagNumber(3)
+  GetCurrentPromptResponse ensureCurrentPrompt() => $_ensure(2);
+
+  @$pb.TagNumber(4)
+  PromptQueue get queue => $_getN(3);
+  @$pb.TagNumber(4)
+  set queue(PromptQueue v) { setField(4, v); }
+  @$pb.TagNumber(4)
+  $core.bool hasQueue() => $_has(3);
+  @$pb.TagNumber(4)
+  void clearQueue() => clearField(4);
+  @$pb.TagNumber(4)
+  PromptQueue ensureQueue() => $_ensure(3);
+}
+
+/// The prompts waiting to be shown once the current prompt has been actioned
+class PromptQueue extends $pb.GeneratedMessage {
+  factory PromptQueue({
+    $core.Iterable<QueuedPrompt>? prompts,
+  }) {
+    final $result = create();
+    if (prompts != null) {
+      $result.prompts.addAll(prompts);
+    }
+    return $result;
+  }
+  PromptQueue._() : super();
+  factory PromptQueue.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory PromptQueue.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'PromptQueue', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..pc<QueuedPrompt>(1, _omitFieldNames ? '' : 'prompts', $pb.PbFieldType.PM, subBuilder: QueuedPrompt.create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  PromptQueue clone() => PromptQueue()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  PromptQueue copyWith(void Function(PromptQueue) updates) => super.copyWith((message) => updates(message as PromptQueue)) as PromptQueue;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static PromptQueue create() => PromptQueue._();
+  PromptQueue createEmptyInstance() => create();
+  static $pb.PbList<PromptQueue> createRepeated() => $pb.PbList<PromptQueue>();
+  @$core.pragma('dart2js:noInline')
+  static PromptQueue getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<PromptQueue>(create);
+  static PromptQueue? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.List<QueuedPrompt> get prompts => $_getList(0);
+}
+
+class QueuedPrompt extends $pb.GeneratedMessage {
+  factory QueuedPrompt({
+    $core.String? promptId,
+    $core.String? snapName,
+    $core.String? interface,
+    $core.String? requestedPath,
+    $core.Iterable<$core.String>? requestedPermissions,
+  }) {
+    final $result = create();
+    if (promptId != null) {
+      $result.promptId = promptId;
+    }
+    if (snapName != null) {
+      $result.snapName = snapName;
+    }
+    if (interface != null) {
+      $result.interface = interface;
+    }
+    if (requestedPath != null) {
+      $result.requestedPath = requestedPath;
+    }
+    if (requestedPermissions != null) {
+      $result.requestedPermissions.addAll(requestedPermissions);
+    }
+    return $result;
+  }
+  QueuedPrompt._() : super();
+  factory QueuedPrompt.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory QueuedPrompt.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'QueuedPrompt', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'promptId')
+    ..aOS(2, _omitFieldNames ? '' : 'snapName')
+    ..aOS(3, _omitFieldNames ? '' : 'interface')
+    ..aOS(4, _omitFieldNames ? '' : 'requestedPath')
+    ..pPS(5, _omitFieldNames ? '' : 'requestedPermissions')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  QueuedPrompt clone() => QueuedPrompt()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  QueuedPrompt copyWith(void Function(QueuedPrompt) updates) => super.copyWith((message) => updates(message as QueuedPrompt)) as QueuedPrompt;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static QueuedPrompt create() => QueuedPrompt._();
+  QueuedPrompt createEmptyInstance() => create();
+  static $pb.PbList<QueuedPrompt> createRepeated() => $pb.PbList<QueuedPrompt>();
+  @$core.pragma('dart2js:noInline')
+  static QueuedPrompt getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<QueuedPrompt>(create);
+  static QueuedPrompt? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get promptId => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set promptId($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasPromptId() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearPromptId() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get snapName => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set snapName($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasSnapName() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearSnapName() => clearField(2);
+
+  @$pb.TagNumber(3)
+  $core.String get interface => $_getSZ(2);
+  @$pb.TagNumber(3)
+  set interface($core.String v) { $_setString(2, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasInterface() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearInterface() => clearField(3);
+
+  /// Only set for interfaces that request access to a path
+  @$pb.TagNumber(4)
+  $core.String get requestedPath => $_getSZ(3);
+  @$pb.TagNumber(4)
+  set requestedPath($core.String v) { $_setString(3, v); }
+  @$pb.TagNumber(4)
+  $core.bool hasRequestedPath() => $_has(3);
+  @$pb.TagNumber(4)
+  void clearRequestedPath() => clearField(4);
+
+  @$pb.TagNumber(5)
+  $core.List<$core.String> get requestedPermissions => $_getList(4);
+}
+
+class ReplyToPromptsResponse extends $pb.GeneratedMessage {
+  factory ReplyToPromptsResponse({
+    PromptReplyResponse? reply,
+    $core.Iterable<$core.String>? actionedPromptIds,
+  }) {
+    final $result = create();
+    if (reply != null) {
+      $result.reply = reply;
+    }
+    if (actionedPromptIds != null) {
+      $result.actionedPromptIds.addAll(actionedPromptIds);
+    }
+    return $result;
+  }
+  ReplyToPromptsResponse._() : super();
+  factory ReplyToPromptsResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory ReplyToPromptsResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'ReplyToPromptsResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOM<PromptReplyResponse>(1, _omitFieldNames ? '' : 'reply', subBuilder: PromptReplyResponse.create)
+    ..pPS(2, _omitFieldNames ? '' : 'actionedPromptIds')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  ReplyToPromptsResponse clone() => ReplyToPromptsResponse()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  ReplyToPromptsResponse copyWith(void Function(ReplyToPromptsResponse) updates) => super.copyWith((message) => updates(message as ReplyToPromptsResponse)) as ReplyToPromptsResponse;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static ReplyToPromptsResponse create() => ReplyToPromptsResponse._();
+  ReplyToPromptsResponse createEmptyInstance() => create();
+  static $pb.PbList<ReplyToPromptsResponse> createRepeated() => $pb.PbList<ReplyToPromptsResponse>();
+  @$core.pragma('dart2js:noInline')
+  static ReplyToPromptsResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<ReplyToPromptsResponse>(create);
+  static ReplyToPromptsResponse? _defaultInstance;
+
+  /// The result of replying to the prompt identified in the request
+  @$pb.TagNumber(1)
+  PromptReplyResponse get reply => $_getN(0);
+  @$pb.TagNumber(1)
+  set reply(PromptReplyResponse v) { setField(1, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasReply() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearReply() => clearField(1);
+  @$pb.TagNumber(1)
+  PromptReplyResponse ensureReply() => $_ensure(0);
+
+  /// Queued prompts from the same snap that the same action was applied to
+  @$pb.TagNumber(2)
+  $core.List<$core.String> get actionedPromptIds => $_getList(1);
+}
+
 enum PromptReply_PromptReply {
   homePromptReply, 
+  cameraPromptReply, 
+  audioRecordPromptReply, 
+  genericPromptReply, 
   notSet
 }
 
@@ -28,6 +338,10 @@ class PromptReply extends $pb.GeneratedMessage {
     Action? action,
     Lifespan? lifespan,
     HomePromptReply? homePromptReply,
+    CameraPromptReply? cameraPromptReply,
+    AudioRecordPromptReply? audioRecordPromptReply,
+    GenericPromptReply? genericPromptReply,
+    $core.String? duration,
   }) {
     final $result = create();
     if (promptId != null) {
@@ -42,6 +356,18 @@ class PromptReply extends $pb.GeneratedMessage {
     if (homePromptReply != null) {
       $result.homePromptReply = homePromptReply;
     }
+    if (cameraPromptReply != null) {
+      $result.cameraPromptReply = cameraPromptReply;
+    }
+    if (audioRecordPromptReply != null) {
+      $result.audioRecordPromptReply = audioRecordPromptReply;
+    }
+    if (genericPromptReply != null) {
+      $result.genericPromptReply = genericPromptReply;
+    }
+    if (duration != null) {
+      $result.duration = duration;
+    }
     return $result;
   }
   PromptReply._() : super();
@@ -50,14 +376,21 @@ class PromptReply extends $pb.GeneratedMessage {
 
   static const $core.Map<$core.int, PromptReply_PromptReply> _PromptReply_PromptReplyByTag = {
     4 : PromptReply_PromptReply.homePromptReply,
+    5 : PromptReply_PromptReply.cameraPromptReply,
+    6 : PromptReply_PromptReply.audioRecordPromptReply,
+    7 : PromptReply_PromptReply.genericPromptReply,
     0 : PromptReply_PromptReply.notSet
   };
   static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'PromptReply', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
-    ..oo(0, [4])
+    ..oo(0, [4, 5, 6, 7])
     ..aOS(1, _omitFieldNames ? '' : 'promptId')
     ..e<Action>(2, _omitFieldNames ? '' : 'action', $pb.PbFieldType.OE, defaultOrMaker: Action.ALLOW, valueOf: Action.valueOf, enumValues: Action.values)
     ..e<Lifespan>(3, _omitFieldNames ? '' : 'lifespan', $pb.PbFieldType.OE, defaultOrMaker: Lifespan.SINGLE, valueOf: Lifespan.valueOf, enumValues: Lifespan.values)
     ..aOM<HomePromptReply>(4, _omitFieldNames ? '' : 'homePromptReply', subBuilder: HomePromptReply.create)
+    ..aOM<CameraPromptReply>(5, _omitFieldNames ? '' : 'cameraPromptReply', subBuilder: CameraPromptReply.create)
+    ..aOM<AudioRecordPromptReply>(6, _omitFieldNames ? '' : 'audioRecordPromptReply', subBuilder: AudioRecordPromptReply.create)
+    ..aOM<GenericPromptReply>(7, _omitFieldNames ? '' : 'genericPromptReply', subBuilder: GenericPromptReply.create)
+    ..aOS(8, _omitFieldNames ? '' : 'duration')
     ..hasRequiredFields = false
   ;
 
@@ -122,6 +455,49 @@ class PromptReply extends $pb.GeneratedMessage {
   void clearHomePromptReply() => clearField(4);
   @$pb.TagNumber(4)
   HomePromptReply ensureHomePromptReply() => $_ensure(3);
+
+  @$pb.TagNumber(5)
+  CameraPromptReply get cameraPromptReply => $_getN(4);
+  @$pb.TagNumber(5)
+  set cameraPromptReply(CameraPromptReply v) { setField(5, v); }
+  @$pb.TagNumber(5)
+  $core.bool hasCameraPromptReply() => $_has(4);
+  @$pb.TagNumber(5)
+  void clearCameraPromptReply() => clearField(5);
+  @$pb.TagNumber(5)
+  CameraPromptReply ensureCameraPromptReply() => $_ensure(4);
+
+  @$pb.TagNumber(6)
+  AudioRecordPromptReply get audioRecordPromptReply => $_getN(5);
+  @$pb.TagNumber(6)
+  set audioRecordPromptReply(AudioRecordPromptReply v) { setField(6, v); }
+  @$pb.TagNumber(6)
+  $core.bool hasAudioRecordPromptReply() => $_has(5);
+  @$pb.TagNumber(6)
+  void clearAudioRecordPromptReply() => clearField(6);
+  @$pb.TagNumber(6)
+  AudioRecordPromptReply ensureAudioRecordPromptReply() => $_ensure(5);
+
+  @$pb.TagNumber(7)
+  GenericPromptReply get genericPromptReply => $_getN(6);
+  @$pb.TagNumber(7)
+  set genericPromptReply(GenericPromptReply v) { setField(7, v); }
+  @$pb.TagNumber(7)
+  $core.bool hasGenericPromptReply() => $_has(6);
+  @$pb.TagNumber(7)
+  void clearGenericPromptReply() => clearField(7);
+  @$pb.TagNumber(7)
+  GenericPromptReply ensureGenericPromptReply() => $_ensure(6);
+
+  /// Required when the lifespan is TIMESPAN, in the format accepted by Go's time.ParseDuration
+  @$pb.TagNumber(8)
+  $core.String get duration => $_getSZ(7);
+  @$pb.TagNumber(8)
+  set duration($core.String v) { $_setString(7, v); }
+  @$pb.TagNumber(8)
+  $core.bool hasDuration() => $_has(7);
+  @$pb.TagNumber(8)
+  void clearDuration() => clearField(8);
 }
 
 class PromptReplyResponse extends $pb.GeneratedMessage {
@@ -190,17 +566,32 @@ class PromptReplyResponse extends $pb.GeneratedMessage {
 
 enum GetCurrentPromptResponse_Prompt {
   homePrompt, 
+  cameraPrompt, 
+  audioRecordPrompt, 
+  genericPrompt, 
   notSet
 }
 
 class GetCurrentPromptResponse extends $pb.GeneratedMessage {
   factory GetCurrentPromptResponse({
     HomePrompt? homePrompt,
+    CameraPrompt? cameraPrompt,
+    AudioRecordPrompt? audioRecordPrompt,
+    GenericPrompt? genericPrompt,
   }) {
     final $result = create();
     if (homePrompt != null) {
       $result.homePrompt = homePrompt;
     }
+    if (cameraPrompt != null) {
+      $result.cameraPrompt = cameraPrompt;
+    }
+    if (audioRecordPrompt != null) {
+      $result.audioRecordPrompt = audioRecordPrompt;
+    }
+    if (genericPrompt != null) {
+      $result.genericPrompt = genericPrompt;
+    }
     return $result;
   }
   GetCurrentPromptResponse._() : super();
@@ -209,11 +600,17 @@ class GetCurrentPromptResponse extends $pb.GeneratedMessage {
 
   static const $core.Map<$core.int, GetCurrentPromptResponse_Prompt> _GetCurrentPromptResponse_PromptByTag = {
     1 : GetCurrentPromptResponse_Prompt.homePrompt,
+    2 : GetCurrentPromptResponse_Prompt.cameraPrompt,
+    3 : GetCurrentPromptResponse_Prompt.audioRecordPrompt,
+    4 : GetCurrentPromptResponse_Prompt.genericPrompt,
     0 : GetCurrentPromptResponse_Prompt.notSet
   };
   static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'GetCurrentPromptResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
-    ..oo(0, [1])
+    ..oo(0, [1, 2, 3, 4])
     ..aOM<HomePrompt>(1, _omitFieldNames ? '' : 'homePrompt', subBuilder: HomePrompt.create)
+    ..aOM<CameraPrompt>(2, _omitFieldNames ? '' : 'cameraPrompt', subBuilder: CameraPrompt.create)
+    ..aOM<AudioRecordPrompt>(3, _omitFieldNames ? '' : 'audioRecordPrompt', subBuilder: AudioRecordPrompt.create)
+    ..aOM<GenericPrompt>(4, _omitFieldNames ? '' : 'genericPrompt', subBuilder: GenericPrompt.create)
     ..hasRequiredFields = false
   ;
 
@@ -251,6 +648,39 @@ class GetCurrentPromptResponse extends $pb.GeneratedMessage {
   void clearHomePrompt() => clearField(1);
   @$pb.TagNumber(1)
   HomePrompt ensureHomePrompt() => $_ensure(0);
+
+  @$pb.TagNumber(2)
+  CameraPrompt get cameraPrompt => $_getN(1);
+  @$pb.TagNumber(2)
+  set cameraPrompt(CameraPrompt v) { setField(2, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasCameraPrompt() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearCameraPrompt() => clearField(2);
+  @$pb.TagNumber(2)
+  CameraPrompt ensureCameraPrompt() => $_ensure(1);
+
+  @$pb.TagNumber(3)
+  AudioRecordPrompt get audioRecordPrompt => $_getN(2);
+  @$pb.TagNumber(3)
+  set audioRecordPrompt(AudioRecordPrompt v) { setField(3, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasAudioRecordPrompt() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearAudioRecordPrompt() => clearField(3);
+  @$pb.TagNumber(3)
+  AudioRecordPrompt ensureAudioRecordPrompt() => $_ensure(2);
+
+  @$pb.TagNumber(4)
+  GenericPrompt get genericPrompt => $_getN(3);
+  @$pb.TagNumber(4)
+  set genericPrompt(GenericPrompt v) { setField(4, v); }
+  @$pb.TagNumber(4)
+  $core.bool hasGenericPrompt() => $_has(3);
+  @$pb.TagNumber(4)
+  void clearGenericPrompt() => clearField(4);
+  @$pb.TagNumber(4)
+  GenericPrompt ensureGenericPrompt() => $_ensure(3);
 }
 
 class HomePromptReply extends $pb.GeneratedMessage {
@@ -515,42 +945,990 @@ class HomePrompt extends $pb.GeneratedMessage {
   void clearInitialPatternOption() => clearField(8);
 }
 
-class MetaData extends $pb.GeneratedMessage {
-  factory MetaData({
-    $core.String? promptId,
-    $core.String? snapName,
-    $core.String? storeUrl,
-    $core.String? publisher,
-    $core.String? updatedAt,
-  }) {
-    final $result = create();
-    if (promptId != null) {
-      $result.promptId = promptId;
-    }
-    if (snapName != null) {
-      $result.snapName = snapName;
-    }
-    if (storeUrl != null) {
-      $result.storeUrl = storeUrl;
-    }
-    if (publisher != null) {
-      $result.publisher = publisher;
-    }
-    if (updatedAt != null) {
-      $result.updatedAt = updatedAt;
-    }
-    return $result;
-  }
+class CameraPromptReply extends $pb.GeneratedMessage {
+  factory CameraPromptReply() => create();
+  CameraPromptReply._() : super();
+  factory CameraPromptReply.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory CameraPromptReply.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'CameraPromptReply', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  CameraPromptReply clone() => CameraPromptReply()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  CameraPromptReply copyWith(void Function(CameraPromptReply) updates) => super.copyWith((message) => updates(message as CameraPromptReply)) as CameraPromptReply;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static CameraPromptReply create() => CameraPromptReply._();
+  CameraPromptReply createEmptyInstance() => create();
+  static $pb.PbList<CameraPromptReply> createRepeated() => $pb.PbList<CameraPromptReply>();
+  @$core.pragma('dart2js:noInline')
+  static CameraPromptReply getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<CameraPromptReply>(create);
+  static CameraPromptReply? _defaultInstance;
+}
+
+class CameraPrompt extends $pb.GeneratedMessage {
+  factory CameraPrompt({
+    MetaData? metaData,
+  }) {
+    final $result = create();
+    if (metaData != null) {
+      $result.metaData = metaData;
+    }
+    return $result;
+  }
+  CameraPrompt._() : super();
+  factory CameraPrompt.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory CameraPrompt.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'CameraPrompt', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOM<MetaData>(1, _omitFieldNames ? '' : 'metaData', subBuilder: MetaData.create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  CameraPrompt clone() => CameraPrompt()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  CameraPrompt copyWith(void Function(CameraPrompt) updates) => super.copyWith((message) => updates(message as CameraPrompt)) as CameraPrompt;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static CameraPrompt create() => CameraPrompt._();
+  CameraPrompt createEmptyInstance() => create();
+  static $pb.PbList<CameraPrompt> createRepeated() => $pb.PbList<CameraPrompt>();
+  @$core.pragma('dart2js:noInline')
+  static CameraPrompt getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<CameraPrompt>(create);
+  static CameraPrompt? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  MetaData get metaData => $_getN(0);
+  @$pb.TagNumber(1)
+  set metaData(MetaData v) { setField(1, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasMetaData() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearMetaData() => clearField(1);
+  @$pb.TagNumber(1)
+  MetaData ensureMetaData() => $_ensure(0);
+}
+
+class AudioRecordPromptReply extends $pb.GeneratedMessage {
+  factory AudioRecordPromptReply() => create();
+  AudioRecordPromptReply._() : super();
+  factory AudioRecordPromptReply.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory AudioRecordPromptReply.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'AudioRecordPromptReply', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  AudioRecordPromptReply clone() => AudioRecordPromptReply()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  AudioRecordPromptReply copyWith(void Function(AudioRecordPromptReply) updates) => super.copyWith((message) => updates(message as AudioRecordPromptReply)) as AudioRecordPromptReply;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static AudioRecordPromptReply create() => AudioRecordPromptReply._();
+  AudioRecordPromptReply createEmptyInstance() => create();
+  static $pb.PbList<AudioRecordPromptReply> createRepeated() => $pb.PbList<AudioRecordPromptReply>();
+  @$core.pragma('dart2js:noInline')
+  static AudioRecordPromptReply getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<AudioRecordPromptReply>(create);
+  static AudioRecordPromptReply? _defaultInstance;
+}
+
+class AudioRecordPrompt extends $pb.GeneratedMessage {
+  factory AudioRecordPrompt({
+    MetaData? metaData,
+  }) {
+    final $result = create();
+    if (metaData != null) {
+      $result.metaData = metaData;
+    }
+    return $result;
+  }
+  AudioRecordPrompt._() : super();
+  factory AudioRecordPrompt.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory AudioRecordPrompt.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'AudioRecordPrompt', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOM<MetaData>(1, _omitFieldNames ? '' : 'metaData', subBuilder: MetaData.create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  AudioRecordPrompt clone() => AudioRecordPrompt()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  AudioRecordPrompt copyWith(void Function(AudioRecordPrompt) updates) => super.copyWith((message) => updates(message as AudioRecordPrompt)) as AudioRecordPrompt;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static AudioRecordPrompt create() => AudioRecordPrompt._();
+  AudioRecordPrompt createEmptyInstance() => create();
+  static $pb.PbList<AudioRecordPrompt> createRepeated() => $pb.PbList<AudioRecordPrompt>();
+  @$core.pragma('dart2js:noInline')
+  static AudioRecordPrompt getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<AudioRecordPrompt>(create);
+  static AudioRecordPrompt? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  MetaData get metaData => $_getN(0);
+  @$pb.TagNumber(1)
+  set metaData(MetaData v) { setField(1, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasMetaData() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearMetaData() => clearField(1);
+  @$pb.TagNumber(1)
+  MetaData ensureMetaData() => $_ensure(0);
+}
+
+///  Replies to prompts for interfaces without a dedicated message type are only supported with
+///  the SINGLE lifespan.
+class GenericPromptReply extends $pb.GeneratedMessage {
+  factory GenericPromptReply() => create();
+  GenericPromptReply._() : super();
+  factory GenericPromptReply.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory GenericPromptReply.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'GenericPromptReply', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  GenericPromptReply clone() => GenericPromptReply()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  GenericPromptReply copyWith(void Function(GenericPromptReply) updates) => super.copyWith((message) => updates(message as GenericPromptReply)) as GenericPromptReply;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static GenericPromptReply create() => GenericPromptReply._();
+  GenericPromptReply createEmptyInstance() => create();
+  static $pb.PbList<GenericPromptReply> createRepeated() => $pb.PbList<GenericPromptReply>();
+  @$core.pragma('dart2js:noInline')
+  static GenericPromptReply getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<GenericPromptReply>(create);
+  static GenericPromptReply? _defaultInstance;
+}
+
+class GenericPrompt_Constraint extends $pb.GeneratedMessage {
+  factory GenericPrompt_Constraint({
+    $core.String? key,
+    $core.String? value,
+  }) {
+    final $result = create();
+    if (key != null) {
+      $result.key = key;
+    }
+    if (value != null) {
+      $result.value = value;
+    }
+    return $result;
+  }
+  GenericPrompt_Constraint._() : super();
+  factory GenericPrompt_Constraint.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory GenericPrompt_Constraint.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'GenericPrompt.Constraint', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'key')
+    ..aOS(2, _omitFieldNames ? '' : 'value')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  GenericPrompt_Constraint clone() => GenericPrompt_Constraint()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  GenericPrompt_Constraint copyWith(void Function(GenericPrompt_Constraint) updates) => super.copyWith((message) => updates(message as GenericPrompt_Constraint)) as GenericPrompt_Constraint;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static GenericPrompt_Constraint create() => GenericPrompt_Constraint._();
+  GenericPrompt_Constraint createEmptyInstance() => create();
+  static $pb.PbList<GenericPrompt_Constraint> createRepeated() => $pb.PbList<GenericPrompt_Constraint>();
+  @$core.pragma('dart2js:noInline')
+  static GenericPrompt_Constraint getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<GenericPrompt_Constraint>(create);
+  static GenericPrompt_Constraint? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get key => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set key($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasKey() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearKey() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get value => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set value($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasValue() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearValue() => clearField(2);
+}
+
+class GenericPrompt extends $pb.GeneratedMessage {
+  factory GenericPrompt({
+    MetaData? metaData,
+    $core.String? interface,
+    $core.Iterable<GenericPrompt_Constraint>? constraints,
+  }) {
+    final $result = create();
+    if (metaData != null) {
+      $result.metaData = metaData;
+    }
+    if (interface != null) {
+      $result.interface = interface;
+    }
+    if (constraints != null) {
+      $result.constraints.addAll(constraints);
+    }
+    return $result;
+  }
+  GenericPrompt._() : super();
+  factory GenericPrompt.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory GenericPrompt.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'GenericPrompt', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOM<MetaData>(1, _omitFieldNames ? '' : 'metaData', subBuilder: MetaData.create)
+    ..aOS(2, _omitFieldNames ? '' : 'interface')
+    ..pc<GenericPrompt_Constraint>(3, _omitFieldNames ? '' : 'constraints', $pb.PbFieldType.PM, subBuilder: GenericPrompt_Constraint.create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  GenericPrompt clone() => GenericPrompt()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  GenericPrompt copyWith(void Function(GenericPrompt) updates) => super.copyWith((message) => updates(message as GenericPrompt)) as GenericPrompt;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static GenericPrompt create() => GenericPrompt._();
+  GenericPrompt createEmptyInstance() => create();
+  static $pb.PbList<GenericPrompt> createRepeated() => $pb.PbList<GenericPrompt>();
+  @$core.pragma('dart2js:noInline')
+  static GenericPrompt getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<GenericPrompt>(create);
+  static GenericPrompt? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  MetaData get metaData => $_getN(0);
+  @$pb.TagNumber(1)
+  set metaData(MetaData v) { setField(1, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasMetaData() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearMetaData() => clearField(1);
+  @$pb.TagNumber(1)
+  MetaData ensureMetaData() => $_ensure(0);
+
+  @$pb.TagNumber(2)
+  $core.String get interface => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set interface($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasInterface() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearInterface() => clearField(2);
+
+  @$pb.TagNumber(3)
+  $core.List<GenericPrompt_Constraint> get constraints => $_getList(2);
+}
+
+class MetaData extends $pb.GeneratedMessage {
+  factory MetaData({
+    $core.String? promptId,
+    $core.String? snapName,
+    $core.String? storeUrl,
+    $core.String? publisher,
+    $core.String? updatedAt,
+    $core.String? timestamp,
+    $fixnum.Int64? ageMs,
+    MetaData_PublisherValidation? publisherValidation,
+    $core.String? version,
+    $core.String? revision,
+    $core.String? channel,
+    $core.String? confinement,
+    $core.String? title,
+    $core.String? summary,
+    $core.String? iconApiPath,
+  }) {
+    final $result = create();
+    if (promptId != null) {
+      $result.promptId = promptId;
+    }
+    if (snapName != null) {
+      $result.snapName = snapName;
+    }
+    if (storeUrl != null) {
+      $result.storeUrl = storeUrl;
+    }
+    if (publisher != null) {
+      $result.publisher = publisher;
+    }
+    if (updatedAt != null) {
+      $result.updatedAt = updatedAt;
+    }
+    if (timestamp != null) {
+      $result.timestamp = timestamp;
+    }
+    if (ageMs != null) {
+      $result.ageMs = ageMs;
+    }
+    if (publisherValidation != null) {
+      $result.publisherValidation = publisherValidation;
+    }
+    if (version != null) {
+      $result.version = version;
+    }
+    if (revision != null) {
+      $result.revision = revision;
+    }
+    if (channel != null) {
+      $result.channel = channel;
+    }
+    if (confinement != null) {
+      $result.confinement = confinement;
+    }
+    if (title != null) {
+      $result.title = title;
+    }
+    if (summary != null) {
+      $result.summary = summary;
+    }
+    if (iconApiPath != null) {
+      $result.iconApiPath = iconApiPath;
+    }
+    return $result;
+  }
   MetaData._() : super();
   factory MetaData.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
   factory MetaData.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
 
-  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'MetaData', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
-    ..aOS(1, _omitFieldNames ? '' : 'promptId')
-    ..aOS(2, _omitFieldNames ? '' : 'snapName')
-    ..aOS(3, _omitFieldNames ? '' : 'storeUrl')
-    ..aOS(4, _omitFieldNames ? '' : 'publisher')
-    ..aOS(5, _omitFieldNames ? '' : 'updatedAt')
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'MetaData', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'promptId')
+    ..aOS(2, _omitFieldNames ? '' : 'snapName')
+    ..aOS(3, _omitFieldNames ? '' : 'storeUrl')
+    ..aOS(4, _omitFieldNames ? '' : 'publisher')
+    ..aOS(5, _omitFieldNames ? '' : 'updatedAt')
+    ..aOS(6, _omitFieldNames ? '' : 'timestamp')
+    ..a<$fixnum.Int64>(7, _omitFieldNames ? '' : 'ageMs', $pb.PbFieldType.OU6, defaultOrMaker: $fixnum.Int64.ZERO)
+    ..e<MetaData_PublisherValidation>(8, _omitFieldNames ? '' : 'publisherValidation', $pb.PbFieldType.OE, defaultOrMaker: MetaData_PublisherValidation.UNKNOWN, valueOf: MetaData_PublisherValidation.valueOf, enumValues: MetaData_PublisherValidation.values)
+    ..aOS(9, _omitFieldNames ? '' : 'version')
+    ..aOS(10, _omitFieldNames ? '' : 'revision')
+    ..aOS(11, _omitFieldNames ? '' : 'channel')
+    ..aOS(12, _omitFieldNames ? '' : 'confinement')
+    ..aOS(13, _omitFieldNames ? '' : 'title')
+    ..aOS(14, _omitFieldNames ? '' : 'summary')
+    ..aOS(15, _omitFieldNames ? '' : 'iconApiPath')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  MetaData clone() => MetaData()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  MetaData copyWith(void Function(MetaData) updates) => super.copyWith((message) => updates(message as MetaData)) as MetaData;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static MetaData create() => MetaData._();
+  MetaData createEmptyInstance() => create();
+  static $pb.PbList<MetaData> createRepeated() => $pb.PbList<MetaData>();
+  @$core.pragma('dart2js:noInline')
+  static MetaData getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<MetaData>(create);
+  static MetaData? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get promptId => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set promptId($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasPromptId() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearPromptId() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get snapName => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set snapName($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasSnapName() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearSnapName() => clearField(2);
+
+  /// Only set for snaps installed from the store
+  @$pb.TagNumber(3)
+  $core.String get storeUrl => $_getSZ(2);
+  @$pb.TagNumber(3)
+  set storeUrl($core.String v) { $_setString(2, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasStoreUrl() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearStoreUrl() => clearField(3);
+
+  @$pb.TagNumber(4)
+  $core.String get publisher => $_getSZ(3);
+  @$pb.TagNumber(4)
+  set publisher($core.String v) { $_setString(3, v); }
+  @$pb.TagNumber(4)
+  $core.bool hasPublisher() => $_has(3);
+  @$pb.TagNumber(4)
+  void clearPublisher() => clearField(4);
+
+  @$pb.TagNumber(5)
+  $core.String get updatedAt => $_getSZ(4);
+  @$pb.TagNumber(5)
+  set updatedAt($core.String v) { $_setString(4, v); }
+  @$pb.TagNumber(5)
+  $core.bool hasUpdatedAt() => $_has(4);
+  @$pb.TagNumber(5)
+  void clearUpdatedAt() => clearField(5);
+
+  /// When snapd created the prompt as an RFC 3339 timestamp
+  @$pb.TagNumber(6)
+  $core.String get timestamp => $_getSZ(5);
+  @$pb.TagNumber(6)
+  set timestamp($core.String v) { $_setString(5, v); }
+  @$pb.TagNumber(6)
+  $core.bool hasTimestamp() => $_has(5);
+  @$pb.TagNumber(6)
+  void clearTimestamp() => clearField(6);
+
+  /// How long ago the prompt was created at the time it was sent to the UI
+  @$pb.TagNumber(7)
+  $fixnum.Int64 get ageMs => $_getI64(6);
+  @$pb.TagNumber(7)
+  set ageMs($fixnum.Int64 v) { $_setInt64(6, v); }
+  @$pb.TagNumber(7)
+  $core.bool hasAgeMs() => $_has(6);
+  @$pb.TagNumber(7)
+  void clearAgeMs() => clearField(7);
+
+  @$pb.TagNumber(8)
+  MetaData_PublisherValidation get publisherValidation => $_getN(7);
+  @$pb.TagNumber(8)
+  set publisherValidation(MetaData_PublisherValidation v) { setField(8, v); }
+  @$pb.TagNumber(8)
+  $core.bool hasPublisherValidation() => $_has(7);
+  @$pb.TagNumber(8)
+  void clearPublisherValidation() => clearField(8);
+
+  @$pb.TagNumber(9)
+  $core.String get version => $_getSZ(8);
+  @$pb.TagNumber(9)
+  set version($core.String v) { $_setString(8, v); }
+  @$pb.TagNumber(9)
+  $core.bool hasVersion() => $_has(8);
+  @$pb.TagNumber(9)
+  void clearVersion() => clearField(9);
+
+  @$pb.TagNumber(10)
+  $core.String get revision => $_getSZ(9);
+  @$pb.TagNumber(10)
+  set revision($core.String v) { $_setString(9, v); }
+  @$pb.TagNumber(10)
+  $core.bool hasRevision() => $_has(9);
+  @$pb.TagNumber(10)
+  void clearRevision() => clearField(10);
+
+  @$pb.TagNumber(11)
+  $core.String get channel => $_getSZ(10);
+  @$pb.TagNumber(11)
+  set channel($core.String v) { $_setString(10, v); }
+  @$pb.TagNumber(11)
+  $core.bool hasChannel() => $_has(10);
+  @$pb.TagNumber(11)
+  void clearChannel() => clearField(11);
+
+  @$pb.TagNumber(12)
+  $core.String get confinement => $_getSZ(11);
+  @$pb.TagNumber(12)
+  set confinement($core.String v) { $_setString(11, v); }
+  @$pb.TagNumber(12)
+  $core.bool hasConfinement() => $_has(11);
+  @$pb.TagNumber(12)
+  void clearConfinement() => clearField(12);
+
+  @$pb.TagNumber(13)
+  $core.String get title => $_getSZ(12);
+  @$pb.TagNumber(13)
+  set title($core.String v) { $_setString(12, v); }
+  @$pb.TagNumber(13)
+  $core.bool hasTitle() => $_has(12);
+  @$pb.TagNumber(13)
+  void clearTitle() => clearField(13);
+
+  @$pb.TagNumber(14)
+  $core.String get summary => $_getSZ(13);
+  @$pb.TagNumber(14)
+  set summary($core.String v) { $_setString(13, v); }
+  @$pb.TagNumber(14)
+  $core.bool hasSummary() => $_has(13);
+  @$pb.TagNumber(14)
+  void clearSummary() => clearField(14);
+
+  ///  The snapd REST API path (/v2/icons/<name>/icon) for fetching the snap icon if it has one.
+  ///  This needs to be requested from snapd and is not a path on the filesystem.
+  @$pb.TagNumber(15)
+  $core.String get iconApiPath => $_getSZ(14);
+  @$pb.TagNumber(15)
+  set iconApiPath($core.String v) { $_setString(14, v); }
+  @$pb.TagNumber(15)
+  $core.bool hasIconApiPath() => $_has(14);
+  @$pb.TagNumber(15)
+  void clearIconApiPath() => clearField(15);
+}
+
+class ResolveHomePatternTypeResponse extends $pb.GeneratedMessage {
+  factory ResolveHomePatternTypeResponse({
+    HomePatternType? homePatternType,
+  }) {
+    final $result = create();
+    if (homePatternType != null) {
+      $result.homePatternType = homePatternType;
+    }
+    return $result;
+  }
+  ResolveHomePatternTypeResponse._() : super();
+  factory ResolveHomePatternTypeResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory ResolveHomePatternTypeResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'ResolveHomePatternTypeResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..e<HomePatternType>(1, _omitFieldNames ? '' : 'homePatternType', $pb.PbFieldType.OE, defaultOrMaker: HomePatternType.REQUESTED_DIRECTORY, valueOf: HomePatternType.valueOf, enumValues: HomePatternType.values)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  ResolveHomePatternTypeResponse clone() => ResolveHomePatternTypeResponse()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  ResolveHomePatternTypeResponse copyWith(void Function(ResolveHomePatternTypeResponse) updates) => super.copyWith((message) => updates(message as ResolveHomePatternTypeResponse)) as ResolveHomePatternTypeResponse;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static ResolveHomePatternTypeResponse create() => ResolveHomePatternTypeResponse._();
+  ResolveHomePatternTypeResponse createEmptyInstance() => create();
+  static $pb.PbList<ResolveHomePatternTypeResponse> createRepeated() => $pb.PbList<ResolveHomePatternTypeResponse>();
+  @$core.pragma('dart2js:noInline')
+  static ResolveHomePatternTypeResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<ResolveHomePatternTypeResponse>(create);
+  static ResolveHomePatternTypeResponse? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  HomePatternType get homePatternType => $_getN(0);
+  @$pb.TagNumber(1)
+  set homePatternType(HomePatternType v) { setField(1, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasHomePatternType() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearHomePatternType() => clearField(1);
+}
+
+class SetLoggingFilterResponse extends $pb.GeneratedMessage {
+  factory SetLoggingFilterResponse({
+    $core.String? current,
+  }) {
+    final $result = create();
+    if (current != null) {
+      $result.current = current;
+    }
+    return $result;
+  }
+  SetLoggingFilterResponse._() : super();
+  factory SetLoggingFilterResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory SetLoggingFilterResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'SetLoggingFilterResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'current')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  SetLoggingFilterResponse clone() => SetLoggingFilterResponse()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  SetLoggingFilterResponse copyWith(void Function(SetLoggingFilterResponse) updates) => super.copyWith((message) => updates(message as SetLoggingFilterResponse)) as SetLoggingFilterResponse;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static SetLoggingFilterResponse create() => SetLoggingFilterResponse._();
+  SetLoggingFilterResponse createEmptyInstance() => create();
+  static $pb.PbList<SetLoggingFilterResponse> createRepeated() => $pb.PbList<SetLoggingFilterResponse>();
+  @$core.pragma('dart2js:noInline')
+  static SetLoggingFilterResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<SetLoggingFilterResponse>(create);
+  static SetLoggingFilterResponse? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get current => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set current($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasCurrent() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearCurrent() => clearField(1);
+}
+
+/// Empty fields are not used for filtering
+class ListRulesRequest extends $pb.GeneratedMessage {
+  factory ListRulesRequest({
+    $core.String? snap,
+    $core.String? interface,
+  }) {
+    final $result = create();
+    if (snap != null) {
+      $result.snap = snap;
+    }
+    if (interface != null) {
+      $result.interface = interface;
+    }
+    return $result;
+  }
+  ListRulesRequest._() : super();
+  factory ListRulesRequest.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory ListRulesRequest.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'ListRulesRequest', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'snap')
+    ..aOS(2, _omitFieldNames ? '' : 'interface')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  ListRulesRequest clone() => ListRulesRequest()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  ListRulesRequest copyWith(void Function(ListRulesRequest) updates) => super.copyWith((message) => updates(message as ListRulesRequest)) as ListRulesRequest;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static ListRulesRequest create() => ListRulesRequest._();
+  ListRulesRequest createEmptyInstance() => create();
+  static $pb.PbList<ListRulesRequest> createRepeated() => $pb.PbList<ListRulesRequest>();
+  @$core.pragma('dart2js:noInline')
+  static ListRulesRequest getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<ListRulesRequest>(create);
+  static ListRulesRequest? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get snap => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set snap($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasSnap() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearSnap() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get interface => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set interface($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasInterface() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearInterface() => clearField(2);
+}
+
+class ListRulesResponse extends $pb.GeneratedMessage {
+  factory ListRulesResponse({
+    $core.Iterable<Rule>? rules,
+  }) {
+    final $result = create();
+    if (rules != null) {
+      $result.rules.addAll(rules);
+    }
+    return $result;
+  }
+  ListRulesResponse._() : super();
+  factory ListRulesResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory ListRulesResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'ListRulesResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..pc<Rule>(1, _omitFieldNames ? '' : 'rules', $pb.PbFieldType.PM, subBuilder: Rule.create)
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  ListRulesResponse clone() => ListRulesResponse()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  ListRulesResponse copyWith(void Function(ListRulesResponse) updates) => super.copyWith((message) => updates(message as ListRulesResponse)) as ListRulesResponse;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static ListRulesResponse create() => ListRulesResponse._();
+  ListRulesResponse createEmptyInstance() => create();
+  static $pb.PbList<ListRulesResponse> createRepeated() => $pb.PbList<ListRulesResponse>();
+  @$core.pragma('dart2js:noInline')
+  static ListRulesResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<ListRulesResponse>(create);
+  static ListRulesResponse? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.List<Rule> get rules => $_getList(0);
+}
+
+class Rule extends $pb.GeneratedMessage {
+  factory Rule({
+    $core.String? id,
+    $core.String? timestamp,
+    $core.String? snap,
+    $core.String? interface,
+    Action? outcome,
+    Lifespan? lifespan,
+    $core.String? expiration,
+    $core.Iterable<$core.String>? permissions,
+    $core.String? pathPattern,
+  }) {
+    final $result = create();
+    if (id != null) {
+      $result.id = id;
+    }
+    if (timestamp != null) {
+      $result.timestamp = timestamp;
+    }
+    if (snap != null) {
+      $result.snap = snap;
+    }
+    if (interface != null) {
+      $result.interface = interface;
+    }
+    if (outcome != null) {
+      $result.outcome = outcome;
+    }
+    if (lifespan != null) {
+      $result.lifespan = lifespan;
+    }
+    if (expiration != null) {
+      $result.expiration = expiration;
+    }
+    if (permissions != null) {
+      $result.permissions.addAll(permissions);
+    }
+    if (pathPattern != null) {
+      $result.pathPattern = pathPattern;
+    }
+    return $result;
+  }
+  Rule._() : super();
+  factory Rule.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory Rule.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'Rule', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'id')
+    ..aOS(2, _omitFieldNames ? '' : 'timestamp')
+    ..aOS(3, _omitFieldNames ? '' : 'snap')
+    ..aOS(4, _omitFieldNames ? '' : 'interface')
+    ..e<Action>(5, _omitFieldNames ? '' : 'outcome', $pb.PbFieldType.OE, defaultOrMaker: Action.ALLOW, valueOf: Action.valueOf, enumValues: Action.values)
+    ..e<Lifespan>(6, _omitFieldNames ? '' : 'lifespan', $pb.PbFieldType.OE, defaultOrMaker: Lifespan.SINGLE, valueOf: Lifespan.valueOf, enumValues: Lifespan.values)
+    ..aOS(7, _omitFieldNames ? '' : 'expiration')
+    ..pPS(8, _omitFieldNames ? '' : 'permissions')
+    ..aOS(9, _omitFieldNames ? '' : 'pathPattern')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  Rule clone() => Rule()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  Rule copyWith(void Function(Rule) updates) => super.copyWith((message) => updates(message as Rule)) as Rule;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static Rule create() => Rule._();
+  Rule createEmptyInstance() => create();
+  static $pb.PbList<Rule> createRepeated() => $pb.PbList<Rule>();
+  @$core.pragma('dart2js:noInline')
+  static Rule getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<Rule>(create);
+  static Rule? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get id => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set id($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasId() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearId() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get timestamp => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set timestamp($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasTimestamp() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearTimestamp() => clearField(2);
+
+  @$pb.TagNumber(3)
+  $core.String get snap => $_getSZ(2);
+  @$pb.TagNumber(3)
+  set snap($core.String v) { $_setString(2, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasSnap() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearSnap() => clearField(3);
+
+  @$pb.TagNumber(4)
+  $core.String get interface => $_getSZ(3);
+  @$pb.TagNumber(4)
+  set interface($core.String v) { $_setString(3, v); }
+  @$pb.TagNumber(4)
+  $core.bool hasInterface() => $_has(3);
+  @$pb.TagNumber(4)
+  void clearInterface() => clearField(4);
+
+  @$pb.TagNumber(5)
+  Action get outcome => $_getN(4);
+  @$pb.TagNumber(5)
+  set outcome(Action v) { setField(5, v); }
+  @$pb.TagNumber(5)
+  $core.bool hasOutcome() => $_has(4);
+  @$pb.TagNumber(5)
+  void clearOutcome() => clearField(5);
+
+  @$pb.TagNumber(6)
+  Lifespan get lifespan => $_getN(5);
+  @$pb.TagNumber(6)
+  set lifespan(Lifespan v) { setField(6, v); }
+  @$pb.TagNumber(6)
+  $core.bool hasLifespan() => $_has(5);
+  @$pb.TagNumber(6)
+  void clearLifespan() => clearField(6);
+
+  /// Only set for rules with a TIMESPAN lifespan
+  @$pb.TagNumber(7)
+  $core.String get expiration => $_getSZ(6);
+  @$pb.TagNumber(7)
+  set expiration($core.String v) { $_setString(6, v); }
+  @$pb.TagNumber(7)
+  $core.bool hasExpiration() => $_has(6);
+  @$pb.TagNumber(7)
+  void clearExpiration() => clearField(7);
+
+  @$pb.TagNumber(8)
+  $core.List<$core.String> get permissions => $_getList(7);
+
+  /// Only set for interfaces that have a path pattern
+  @$pb.TagNumber(9)
+  $core.String get pathPattern => $_getSZ(8);
+  @$pb.TagNumber(9)
+  set pathPattern($core.String v) { $_setString(8, v); }
+  @$pb.TagNumber(9)
+  $core.bool hasPathPattern() => $_has(8);
+  @$pb.TagNumber(9)
+  void clearPathPattern() => clearField(9);
+}
+
+class RuleUpdate extends $pb.GeneratedMessage {
+  factory RuleUpdate({
+    $core.String? id,
+    RuleUpdate_RuleUpdateType? updateType,
+    Rule? rule,
+  }) {
+    final $result = create();
+    if (id != null) {
+      $result.id = id;
+    }
+    if (updateType != null) {
+      $result.updateType = updateType;
+    }
+    if (rule != null) {
+      $result.rule = rule;
+    }
+    return $result;
+  }
+  RuleUpdate._() : super();
+  factory RuleUpdate.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory RuleUpdate.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'RuleUpdate', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'id')
+    ..e<RuleUpdate_RuleUpdateType>(2, _omitFieldNames ? '' : 'updateType', $pb.PbFieldType.OE, defaultOrMaker: RuleUpdate_RuleUpdateType.CHANGED, valueOf: RuleUpdate_RuleUpdateType.valueOf, enumValues: RuleUpdate_RuleUpdateType.values)
+    ..aOM<Rule>(3, _omitFieldNames ? '' : 'rule', subBuilder: Rule.create)
     ..hasRequiredFields = false
   ;
 
@@ -558,85 +1936,198 @@ class MetaData extends $pb.GeneratedMessage {
   'Using this can add significant overhead to your binary. '
   'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
   'Will be removed in next major version')
-  MetaData clone() => MetaData()..mergeFromMessage(this);
+  RuleUpdate clone() => RuleUpdate()..mergeFromMessage(this);
   @$core.Deprecated(
   'Using this can add significant overhead to your binary. '
   'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
   'Will be removed in next major version')
-  MetaData copyWith(void Function(MetaData) updates) => super.copyWith((message) => updates(message as MetaData)) as MetaData;
+  RuleUpdate copyWith(void Function(RuleUpdate) updates) => super.copyWith((message) => updates(message as RuleUpdate)) as RuleUpdate;
 
   $pb.BuilderInfo get info_ => _i;
 
   @$core.pragma('dart2js:noInline')
-  static MetaData create() => MetaData._();
-  MetaData createEmptyInstance() => create();
-  static $pb.PbList<MetaData> createRepeated() => $pb.PbList<MetaData>();
+  static RuleUpdate create() => RuleUpdate._();
+  RuleUpdate createEmptyInstance() => create();
+  static $pb.PbList<RuleUpdate> createRepeated() => $pb.PbList<RuleUpdate>();
   @$core.pragma('dart2js:noInline')
-  static MetaData getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<MetaData>(create);
-  static MetaData? _defaultInstance;
+  static RuleUpdate getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<RuleUpdate>(create);
+  static RuleUpdate? _defaultInstance;
 
   @$pb.TagNumber(1)
-  $core.String get promptId => $_getSZ(0);
+  $core.String get id => $_getSZ(0);
   @$pb.TagNumber(1)
-  set promptId($core.String v) { $_setString(0, v); }
+  set id($core.String v) { $_setString(0, v); }
   @$pb.TagNumber(1)
-  $core.bool hasPromptId() => $_has(0);
+  $core.bool hasId() => $_has(0);
   @$pb.TagNumber(1)
-  void clearPromptId() => clearField(1);
+  void clearId() => clearField(1);
 
   @$pb.TagNumber(2)
-  $core.String get snapName => $_getSZ(1);
+  RuleUpdate_RuleUpdateType get updateType => $_getN(1);
   @$pb.TagNumber(2)
-  set snapName($core.String v) { $_setString(1, v); }
+  set updateType(RuleUpdate_RuleUpdateType v) { setField(2, v); }
   @$pb.TagNumber(2)
-  $core.bool hasSnapName() => $_has(1);
+  $core.bool hasUpdateType() => $_has(1);
   @$pb.TagNumber(2)
-  void clearSnapName() => clearField(2);
+  void clearUpdateType() => clearField(2);
 
+  /// Only set for CHANGED updates
   @$pb.TagNumber(3)
-  $core.String get storeUrl => $_getSZ(2);
+  Rule get rule => $_getN(2);
   @$pb.TagNumber(3)
-  set storeUrl($core.String v) { $_setString(2, v); }
+  set rule(Rule v) { setField(3, v); }
   @$pb.TagNumber(3)
-  $core.bool hasStoreUrl() => $_has(2);
+  $core.bool hasRule() => $_has(2);
   @$pb.TagNumber(3)
-  void clearStoreUrl() => clearField(3);
+  void clearRule() => clearField(3);
+  @$pb.TagNumber(3)
+  Rule ensureRule() => $_ensure(2);
+}
+
+/// Only the fields that are set are updated
+class UpdateRuleRequest extends $pb.GeneratedMessage {
+  factory UpdateRuleRequest({
+    $core.String? id,
+    Action? outcome,
+    Lifespan? lifespan,
+    $core.String? duration,
+    $core.String? pathPattern,
+    $core.Iterable<$core.String>? permissions,
+  }) {
+    final $result = create();
+    if (id != null) {
+      $result.id = id;
+    }
+    if (outcome != null) {
+      $result.outcome = outcome;
+    }
+    if (lifespan != null) {
+      $result.lifespan = lifespan;
+    }
+    if (duration != null) {
+      $result.duration = duration;
+    }
+    if (pathPattern != null) {
+      $result.pathPattern = pathPattern;
+    }
+    if (permissions != null) {
+      $result.permissions.addAll(permissions);
+    }
+    return $result;
+  }
+  UpdateRuleRequest._() : super();
+  factory UpdateRuleRequest.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory UpdateRuleRequest.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'UpdateRuleRequest', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'id')
+    ..e<Action>(2, _omitFieldNames ? '' : 'outcome', $pb.PbFieldType.OE, defaultOrMaker: Action.ALLOW, valueOf: Action.valueOf, enumValues: Action.values)
+    ..e<Lifespan>(3, _omitFieldNames ? '' : 'lifespan', $pb.PbFieldType.OE, defaultOrMaker: Lifespan.SINGLE, valueOf: Lifespan.valueOf, enumValues: Lifespan.values)
+    ..aOS(4, _omitFieldNames ? '' : 'duration')
+    ..aOS(5, _omitFieldNames ? '' : 'pathPattern')
+    ..pPS(6, _omitFieldNames ? '' : 'permissions')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  UpdateRuleRequest clone() => UpdateRuleRequest()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  UpdateRuleRequest copyWith(void Function(UpdateRuleRequest) updates) => super.copyWith((message) => updates(message as UpdateRuleRequest)) as UpdateRuleRequest;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static UpdateRuleRequest create() => UpdateRuleRequest._();
+  UpdateRuleRequest createEmptyInstance() => create();
+  static $pb.PbList<UpdateRuleRequest> createRepeated() => $pb.PbList<UpdateRuleRequest>();
+  @$core.pragma('dart2js:noInline')
+  static UpdateRuleRequest getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<UpdateRuleRequest>(create);
+  static UpdateRuleRequest? _defaultInstance;
+
+  @$pb.TagNumber(1)
+  $core.String get id => $_getSZ(0);
+  @$pb.TagNumber(1)
+  set id($core.String v) { $_setString(0, v); }
+  @$pb.TagNumber(1)
+  $core.bool hasId() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearId() => clearField(1);
+
+  @$pb.TagNumber(2)
+  Action get outcome => $_getN(1);
+  @$pb.TagNumber(2)
+  set outcome(Action v) { setField(2, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasOutcome() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearOutcome() => clearField(2);
+
+  @$pb.TagNumber(3)
+  Lifespan get lifespan => $_getN(2);
+  @$pb.TagNumber(3)
+  set lifespan(Lifespan v) { setField(3, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasLifespan() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearLifespan() => clearField(3);
 
+  /// Required when setting the lifespan to TIMESPAN
   @$pb.TagNumber(4)
-  $core.String get publisher => $_getSZ(3);
+  $core.String get duration => $_getSZ(3);
   @$pb.TagNumber(4)
-  set publisher($core.String v) { $_setString(3, v); }
+  set duration($core.String v) { $_setString(3, v); }
   @$pb.TagNumber(4)
-  $core.bool hasPublisher() => $_has(3);
+  $core.bool hasDuration() => $_has(3);
   @$pb.TagNumber(4)
-  void clearPublisher() => clearField(4);
+  void clearDuration() => clearField(4);
 
   @$pb.TagNumber(5)
-  $core.String get updatedAt => $_getSZ(4);
+  $core.String get pathPattern => $_getSZ(4);
   @$pb.TagNumber(5)
-  set updatedAt($core.String v) { $_setString(4, v); }
+  set pathPattern($core.String v) { $_setString(4, v); }
   @$pb.TagNumber(5)
-  $core.bool hasUpdatedAt() => $_has(4);
+  $core.bool hasPathPattern() => $_has(4);
   @$pb.TagNumber(5)
-  void clearUpdatedAt() => clearField(5);
+  void clearPathPattern() => clearField(5);
+
+  /// Permissions are only updated if this is non-empty
+  @$pb.TagNumber(6)
+  $core.List<$core.String> get permissions => $_getList(5);
 }
 
-class ResolveHomePatternTypeResponse extends $pb.GeneratedMessage {
-  factory ResolveHomePatternTypeResponse({
-    HomePatternType? homePatternType,
+/// Empty fields are not used for filtering
+class GetHistoryRequest extends $pb.GeneratedMessage {
+  factory GetHistoryRequest({
+    $core.int? limit,
+    $core.String? snap,
+    $core.bool? repliesOnly,
   }) {
     final $result = create();
-    if (homePatternType != null) {
-      $result.homePatternType = homePatternType;
+    if (limit != null) {
+      $result.limit = limit;
+    }
+    if (snap != null) {
+      $result.snap = snap;
+    }
+    if (repliesOnly != null) {
+      $result.repliesOnly = repliesOnly;
     }
     return $result;
   }
-  ResolveHomePatternTypeResponse._() : super();
-  factory ResolveHomePatternTypeResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
-  factory ResolveHomePatternTypeResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
-
-  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'ResolveHomePatternTypeResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
-    ..e<HomePatternType>(1, _omitFieldNames ? '' : 'homePatternType', $pb.PbFieldType.OE, defaultOrMaker: HomePatternType.REQUESTED_DIRECTORY, valueOf: HomePatternType.valueOf, enumValues: HomePatternType.values)
+  GetHistoryRequest._() : super();
+  factory GetHistoryRequest.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory GetHistoryRequest.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'GetHistoryRequest', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..a<$core.int>(1, _omitFieldNames ? '' : 'limit', $pb.PbFieldType.OU3)
+    ..aOS(2, _omitFieldNames ? '' : 'snap')
+    ..aOB(3, _omitFieldNames ? '' : 'repliesOnly')
     ..hasRequiredFields = false
   ;
 
@@ -644,49 +2135,70 @@ class ResolveHomePatternTypeResponse extends $pb.GeneratedMessage {
   'Using this can add significant overhead to your binary. '
   'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
   'Will be removed in next major version')
-  ResolveHomePatternTypeResponse clone() => ResolveHomePatternTypeResponse()..mergeFromMessage(this);
+  GetHistoryRequest clone() => GetHistoryRequest()..mergeFromMessage(this);
   @$core.Deprecated(
   'Using this can add significant overhead to your binary. '
   'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
   'Will be removed in next major version')
-  ResolveHomePatternTypeResponse copyWith(void Function(ResolveHomePatternTypeResponse) updates) => super.copyWith((message) => updates(message as ResolveHomePatternTypeResponse)) as ResolveHomePatternTypeResponse;
+  GetHistoryRequest copyWith(void Function(GetHistoryRequest) updates) => super.copyWith((message) => updates(message as GetHistoryRequest)) as GetHistoryRequest;
 
   $pb.BuilderInfo get info_ => _i;
 
   @$core.pragma('dart2js:noInline')
-  static ResolveHomePatternTypeResponse create() => ResolveHomePatternTypeResponse._();
-  ResolveHomePatternTypeResponse createEmptyInstance() => create();
-  static $pb.PbList<ResolveHomePatternTypeResponse> createRepeated() => $pb.PbList<ResolveHomePatternTypeResponse>();
+  static GetHistoryRequest create() => GetHistoryRequest._();
+  GetHistoryRequest createEmptyInstance() => create();
+  static $pb.PbList<GetHistoryRequest> createRepeated() => $pb.PbList<GetHistoryRequest>();
   @$core.pragma('dart2js:noInline')
-  static ResolveHomePatternTypeResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<ResolveHomePatternTypeResponse>(create);
-  static ResolveHomePatternTypeResponse? _defaultInstance;
+  static GetHistoryRequest getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<GetHistoryRequest>(create);
+  static GetHistoryRequest? _defaultInstance;
 
+  /// The maximum number of entries to return, with 0 returning all entries
   @$pb.TagNumber(1)
-  HomePatternType get homePatternType => $_getN(0);
+  $core.int get limit => $_getIZ(0);
   @$pb.TagNumber(1)
-  set homePatternType(HomePatternType v) { setField(1, v); }
+  set limit($core.int v) { $_setUnsignedInt32(0, v); }
   @$pb.TagNumber(1)
-  $core.bool hasHomePatternType() => $_has(0);
+  $core.bool hasLimit() => $_has(0);
   @$pb.TagNumber(1)
-  void clearHomePatternType() => clearField(1);
+  void clearLimit() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get snap => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set snap($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasSnap() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearSnap() => clearField(2);
+
+  /// Only return REPLIED entries
+  @$pb.TagNumber(3)
+  $core.bool get repliesOnly => $_getBF(2);
+  @$pb.TagNumber(3)
+  set repliesOnly($core.bool v) { $_setBool(2, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasRepliesOnly() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearRepliesOnly() => clearField(3);
 }
 
-class SetLoggingFilterResponse extends $pb.GeneratedMessage {
-  factory SetLoggingFilterResponse({
-    $core.String? current,
+/// Entries are ordered from most to least recent
+class GetHistoryResponse extends $pb.GeneratedMessage {
+  factory GetHistoryResponse({
+    $core.Iterable<HistoryEntry>? entries,
   }) {
     final $result = create();
-    if (current != null) {
-      $result.current = current;
+    if (entries != null) {
+      $result.entries.addAll(entries);
     }
     return $result;
   }
-  SetLoggingFilterResponse._() : super();
-  factory SetLoggingFilterResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
-  factory SetLoggingFilterResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+  GetHistoryResponse._() : super();
+  factory GetHistoryResponse.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory GetHistoryResponse.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
 
-  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'SetLoggingFilterResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
-    ..aOS(1, _omitFieldNames ? '' : 'current')
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'GetHistoryResponse', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..pc<HistoryEntry>(1, _omitFieldNames ? '' : 'entries', $pb.PbFieldType.PM, subBuilder: HistoryEntry.create)
     ..hasRequiredFields = false
   ;
 
@@ -694,31 +2206,242 @@ class SetLoggingFilterResponse extends $pb.GeneratedMessage {
   'Using this can add significant overhead to your binary. '
   'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
   'Will be removed in next major version')
-  SetLoggingFilterResponse clone() => SetLoggingFilterResponse()..mergeFromMessage(this);
+  GetHistoryResponse clone() => GetHistoryResponse()..mergeFromMessage(this);
   @$core.Deprecated(
   'Using this can add significant overhead to your binary. '
   'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
   'Will be removed in next major version')
-  SetLoggingFilterResponse copyWith(void Function(SetLoggingFilterResponse) updates) => super.copyWith((message) => updates(message as SetLoggingFilterResponse)) as SetLoggingFilterResponse;
+  GetHistoryResponse copyWith(void Function(GetHistoryResponse) updates) => super.copyWith((message) => updates(message as GetHistoryResponse)) as GetHistoryResponse;
 
   $pb.BuilderInfo get info_ => _i;
 
   @$core.pragma('dart2js:noInline')
-  static SetLoggingFilterResponse create() => SetLoggingFilterResponse._();
-  SetLoggingFilterResponse createEmptyInstance() => create();
-  static $pb.PbList<SetLoggingFilterResponse> createRepeated() => $pb.PbList<SetLoggingFilterResponse>();
+  static GetHistoryResponse create() => GetHistoryResponse._();
+  GetHistoryResponse createEmptyInstance() => create();
+  static $pb.PbList<GetHistoryResponse> createRepeated() => $pb.PbList<GetHistoryResponse>();
   @$core.pragma('dart2js:noInline')
-  static SetLoggingFilterResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<SetLoggingFilterResponse>(create);
-  static SetLoggingFilterResponse? _defaultInstance;
+  static GetHistoryResponse getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<GetHistoryResponse>(create);
+  static GetHistoryResponse? _defaultInstance;
 
   @$pb.TagNumber(1)
-  $core.String get current => $_getSZ(0);
+  $core.List<HistoryEntry> get entries => $_getList(0);
+}
+
+class HistoryEntry extends $pb.GeneratedMessage {
+  factory HistoryEntry({
+    $core.String? timestamp,
+    $core.String? promptId,
+    $core.String? snap,
+    $core.String? interface,
+    HistoryEntry_HistoryEvent? event,
+    $core.String? constraints,
+    Action? action,
+    Lifespan? lifespan,
+    $core.String? pathPattern,
+    $core.Iterable<$core.String>? permissions,
+    $fixnum.Int64? latencyMs,
+    HistoryEntry_ReplyOutcome? outcome,
+    $core.String? resolvedBy,
+  }) {
+    final $result = create();
+    if (timestamp != null) {
+      $result.timestamp = timestamp;
+    }
+    if (promptId != null) {
+      $result.promptId = promptId;
+    }
+    if (snap != null) {
+      $result.snap = snap;
+    }
+    if (interface != null) {
+      $result.interface = interface;
+    }
+    if (event != null) {
+      $result.event = event;
+    }
+    if (constraints != null) {
+      $result.constraints = constraints;
+    }
+    if (action != null) {
+      $result.action = action;
+    }
+    if (lifespan != null) {
+      $result.lifespan = lifespan;
+    }
+    if (pathPattern != null) {
+      $result.pathPattern = pathPattern;
+    }
+    if (permissions != null) {
+      $result.permissions.addAll(permissions);
+    }
+    if (latencyMs != null) {
+      $result.latencyMs = latencyMs;
+    }
+    if (outcome != null) {
+      $result.outcome = outcome;
+    }
+    if (resolvedBy != null) {
+      $result.resolvedBy = resolvedBy;
+    }
+    return $result;
+  }
+  HistoryEntry._() : super();
+  factory HistoryEntry.fromBuffer($core.List<$core.int> i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromBuffer(i, r);
+  factory HistoryEntry.fromJson($core.String i, [$pb.ExtensionRegistry r = $pb.ExtensionRegistry.EMPTY]) => create()..mergeFromJson(i, r);
+
+  static final $pb.BuilderInfo _i = $pb.BuilderInfo(_omitMessageNames ? '' : 'HistoryEntry', package: const $pb.PackageName(_omitMessageNames ? '' : 'apparmor_prompting'), createEmptyInstance: create)
+    ..aOS(1, _omitFieldNames ? '' : 'timestamp')
+    ..aOS(2, _omitFieldNames ? '' : 'promptId')
+    ..aOS(3, _omitFieldNames ? '' : 'snap')
+    ..aOS(4, _omitFieldNames ? '' : 'interface')
+    ..e<HistoryEntry_HistoryEvent>(5, _omitFieldNames ? '' : 'event', $pb.PbFieldType.OE, defaultOrMaker: HistoryEntry_HistoryEvent.PROMPTED, valueOf: HistoryEntry_HistoryEvent.valueOf, enumValues: HistoryEntry_HistoryEvent.values)
+    ..aOS(6, _omitFieldNames ? '' : 'constraints')
+    ..e<Action>(7, _omitFieldNames ? '' : 'action', $pb.PbFieldType.OE, defaultOrMaker: Action.ALLOW, valueOf: Action.valueOf, enumValues: Action.values)
+    ..e<Lifespan>(8, _omitFieldNames ? '' : 'lifespan', $pb.PbFieldType.OE, defaultOrMaker: Lifespan.SINGLE, valueOf: Lifespan.valueOf, enumValues: Lifespan.values)
+    ..aOS(9, _omitFieldNames ? '' : 'pathPattern')
+    ..pPS(10, _omitFieldNames ? '' : 'permissions')
+    ..a<$fixnum.Int64>(11, _omitFieldNames ? '' : 'latencyMs', $pb.PbFieldType.OU6, defaultOrMaker: $fixnum.Int64.ZERO)
+    ..e<HistoryEntry_ReplyOutcome>(12, _omitFieldNames ? '' : 'outcome', $pb.PbFieldType.OE, defaultOrMaker: HistoryEntry_ReplyOutcome.SUCCESS, valueOf: HistoryEntry_ReplyOutcome.valueOf, enumValues: HistoryEntry_ReplyOutcome.values)
+    ..aOS(13, _omitFieldNames ? '' : 'resolvedBy')
+    ..hasRequiredFields = false
+  ;
+
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.deepCopy] instead. '
+  'Will be removed in next major version')
+  HistoryEntry clone() => HistoryEntry()..mergeFromMessage(this);
+  @$core.Deprecated(
+  'Using this can add significant overhead to your binary. '
+  'Use [GeneratedMessageGenericExtensions.rebuild] instead. '
+  'Will be removed in next major version')
+  HistoryEntry copyWith(void Function(HistoryEntry) updates) => super.copyWith((message) => updates(message as HistoryEntry)) as HistoryEntry;
+
+  $pb.BuilderInfo get info_ => _i;
+
+  @$core.pragma('dart2js:noInline')
+  static HistoryEntry create() => HistoryEntry._();
+  HistoryEntry createEmptyInstance() => create();
+  static $pb.PbList<HistoryEntry> createRepeated() => $pb.PbList<HistoryEntry>();
+  @$core.pragma('dart2js:noInline')
+  static HistoryEntry getDefault() => _defaultInstance ??= $pb.GeneratedMessage.$_defaultFor<HistoryEntry>(create);
+  static HistoryEntry? _defaultInstance;
+
   @$pb.TagNumber(1)
-  set current($core.String v) { $_setString(0, v); }
+  $core.String get timestamp => $_getSZ(0);
   @$pb.TagNumber(1)
-  $core.bool hasCurrent() => $_has(0);
+  set timestamp($core.String v) { $_setString(0, v); }
   @$pb.TagNumber(1)
-  void clearCurrent() => clearField(1);
+  $core.bool hasTimestamp() => $_has(0);
+  @$pb.TagNumber(1)
+  void clearTimestamp() => clearField(1);
+
+  @$pb.TagNumber(2)
+  $core.String get promptId => $_getSZ(1);
+  @$pb.TagNumber(2)
+  set promptId($core.String v) { $_setString(1, v); }
+  @$pb.TagNumber(2)
+  $core.bool hasPromptId() => $_has(1);
+  @$pb.TagNumber(2)
+  void clearPromptId() => clearField(2);
+
+  @$pb.TagNumber(3)
+  $core.String get snap => $_getSZ(2);
+  @$pb.TagNumber(3)
+  set snap($core.String v) { $_setString(2, v); }
+  @$pb.TagNumber(3)
+  $core.bool hasSnap() => $_has(2);
+  @$pb.TagNumber(3)
+  void clearSnap() => clearField(3);
+
+  @$pb.TagNumber(4)
+  $core.String get interface => $_getSZ(3);
+  @$pb.TagNumber(4)
+  set interface($core.String v) { $_setString(3, v); }
+  @$pb.TagNumber(4)
+  $core.bool hasInterface() => $_has(3);
+  @$pb.TagNumber(4)
+  void clearInterface() => clearField(4);
+
+  @$pb.TagNumber(5)
+  HistoryEntry_HistoryEvent get event => $_getN(4);
+  @$pb.TagNumber(5)
+  set event(HistoryEntry_HistoryEvent v) { setField(5, v); }
+  @$pb.TagNumber(5)
+  $core.bool hasEvent() => $_has(4);
+  @$pb.TagNumber(5)
+  void clearEvent() => clearField(5);
+
+  /// Only set for PROMPTED entries: the JSON encoded constraints of the prompt
+  @$pb.TagNumber(6)
+  $core.String get constraints => $_getSZ(5);
+  @$pb.TagNumber(6)
+  set constraints($core.String v) { $_setString(5, v); }
+  @$pb.TagNumber(6)
+  $core.bool hasConstraints() => $_has(5);
+  @$pb.TagNumber(6)
+  void clearConstraints() => clearField(6);
+
+  /// The remaining fields are only set for REPLIED entries
+  @$pb.TagNumber(7)
+  Action get action => $_getN(6);
+  @$pb.TagNumber(7)
+  set action(Action v) { setField(7, v); }
+  @$pb.TagNumber(7)
+  $core.bool hasAction() => $_has(6);
+  @$pb.TagNumber(7)
+  void clearAction() => clearField(7);
+
+  @$pb.TagNumber(8)
+  Lifespan get lifespan => $_getN(7);
+  @$pb.TagNumber(8)
+  set lifespan(Lifespan v) { setField(8, v); }
+  @$pb.TagNumber(8)
+  $core.bool hasLifespan() => $_has(7);
+  @$pb.TagNumber(8)
+  void clearLifespan() => clearField(8);
+
+  /// Only set for interfaces that have a path pattern
+  @$pb.TagNumber(9)
+  $core.String get pathPattern => $_getSZ(8);
+  @$pb.TagNumber(9)
+  set pathPattern($core.String v) { $_setString(8, v); }
+  @$pb.TagNumber(9)
+  $core.bool hasPathPattern() => $_has(8);
+  @$pb.TagNumber(9)
+  void clearPathPattern() => clearField(9);
+
+  @$pb.TagNumber(10)
+  $core.List<$core.String> get permissions => $_getList(9);
+
+  /// The time between the prompt being shown to the user and the reply being sent
+  @$pb.TagNumber(11)
+  $fixnum.Int64 get latencyMs => $_getI64(10);
+  @$pb.TagNumber(11)
+  set latencyMs($fixnum.Int64 v) { $_setInt64(10, v); }
+  @$pb.TagNumber(11)
+  $core.bool hasLatencyMs() => $_has(10);
+  @$pb.TagNumber(11)
+  void clearLatencyMs() => clearField(11);
+
+  @$pb.TagNumber(12)
+  HistoryEntry_ReplyOutcome get outcome => $_getN(11);
+  @$pb.TagNumber(12)
+  set outcome(HistoryEntry_ReplyOutcome v) { setField(12, v); }
+  @$pb.TagNumber(12)
+  $core.bool hasOutcome() => $_has(11);
+  @$pb.TagNumber(12)
+  void clearOutcome() => clearField(12);
+
+  /// Only set for RESOLVED_BY_RULE entries: the prompt whose reply created the rule
+  @$pb.TagNumber(13)
+  $core.String get resolvedBy => $_getSZ(12);
+  @$pb.TagNumber(13)
+  set resolvedBy($core.String v) { $_setString(12, v); }
+  @$pb.TagNumber(13)
+  $core.bool hasResolvedBy() => $_has(12);
+  @$pb.TagNumber(13)
+  void clearResolvedBy() => clearField(13);
 }
 
 
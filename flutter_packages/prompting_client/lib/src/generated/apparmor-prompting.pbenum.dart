@@ -32,11 +32,13 @@ class Lifespan extends $pb.ProtobufEnum {
   static const Lifespan SINGLE = Lifespan._(0, _omitEnumNames ? '' : 'SINGLE');
   static const Lifespan SESSION = Lifespan._(1, _omitEnumNames ? '' : 'SESSION');
   static const Lifespan FOREVER = Lifespan._(2, _omitEnumNames ? '' : 'FOREVER');
+  static const Lifespan TIMESPAN = Lifespan._(3, _omitEnumNames ? '' : 'TIMESPAN');
 
   static const $core.List<Lifespan> values = <Lifespan> [
     SINGLE,
     SESSION,
     FOREVER,
+    TIMESPAN,
   ];
 
   static final $core.Map<$core.int, Lifespan> _byValue = $pb.ProtobufEnum.initByValue(values);
@@ -70,6 +72,29 @@ class HomePatternType extends $pb.ProtobufEnum {
   const HomePatternType._($core.int v, $core.String n) : super(v, n);
 }
 
+class WatchPromptsResponse_PromptStatus extends $pb.ProtobufEnum {
+  static const WatchPromptsResponse_PromptStatus NO_PROMPT = WatchPromptsResponse_PromptStatus._(0, _omitEnumNames ? '' : 'NO_PROMPT');
+  static const WatchPromptsResponse_PromptStatus ACTIVE = WatchPromptsResponse_PromptStatus._(1, _omitEnumNames ? '' : 'ACTIVE');
+  static const WatchPromptsResponse_PromptStatus ACTIONED = WatchPromptsResponse_PromptStatus._(2, _omitEnumNames ? '' : 'ACTIONED');
+  static const WatchPromptsResponse_PromptStatus DROPPED = WatchPromptsResponse_PromptStatus._(3, _omitEnumNames ? '' : 'DROPPED');
+  ///  The user did not reply before the prompt timeout and the configured timeout policy
+  ///  has been applied
+  static const WatchPromptsResponse_PromptStatus TIMED_OUT = WatchPromptsResponse_PromptStatus._(4, _omitEnumNames ? '' : 'TIMED_OUT');
+
+  static const $core.List<WatchPromptsResponse_PromptStatus> values = <WatchPromptsResponse_PromptStatus> [
+    NO_PROMPT,
+    ACTIVE,
+    ACTIONED,
+    DROPPED,
+    TIMED_OUT,
+  ];
+
+  static final $core.Map<$core.int, WatchPromptsResponse_PromptStatus> _byValue = $pb.ProtobufEnum.initByValue(values);
+  static WatchPromptsResponse_PromptStatus? valueOf($core.int value) => _byValue[value];
+
+  const WatchPromptsResponse_PromptStatus._($core.int v, $core.String n) : super(v, n);
+}
+
 class PromptReplyResponse_PromptReplyType extends $pb.ProtobufEnum {
   static const PromptReplyResponse_PromptReplyType SUCCESS = PromptReplyResponse_PromptReplyType._(0, _omitEnumNames ? '' : 'SUCCESS');
   static const PromptReplyResponse_PromptReplyType UNKNOWN = PromptReplyResponse_PromptReplyType._(1, _omitEnumNames ? '' : 'UNKNOWN');
@@ -87,5 +112,84 @@ class PromptReplyResponse_PromptReplyType extends $pb.ProtobufEnum {
   const PromptReplyResponse_PromptReplyType._($core.int v, $core.String n) : super(v, n);
 }
 
+class MetaData_PublisherValidation extends $pb.ProtobufEnum {
+  /// Locally installed snaps have no publisher validation
+  static const MetaData_PublisherValidation UNKNOWN = MetaData_PublisherValidation._(0, _omitEnumNames ? '' : 'UNKNOWN');
+  static const MetaData_PublisherValidation UNPROVEN = MetaData_PublisherValidation._(1, _omitEnumNames ? '' : 'UNPROVEN');
+  static const MetaData_PublisherValidation VERIFIED = MetaData_PublisherValidation._(2, _omitEnumNames ? '' : 'VERIFIED');
+  static const MetaData_PublisherValidation STARRED = MetaData_PublisherValidation._(3, _omitEnumNames ? '' : 'STARRED');
+
+  static const $core.List<MetaData_PublisherValidation> values = <MetaData_PublisherValidation> [
+    UNKNOWN,
+    UNPROVEN,
+    VERIFIED,
+    STARRED,
+  ];
+
+  static final $core.Map<$core.int, MetaData_PublisherValidation> _byValue = $pb.ProtobufEnum.initByValue(values);
+  static MetaData_PublisherValidation? valueOf($core.int value) => _byValue[value];
+
+  const MetaData_PublisherValidation._($core.int v, $core.String n) : super(v, n);
+}
+
+class RuleUpdate_RuleUpdateType extends $pb.ProtobufEnum {
+  static const RuleUpdate_RuleUpdateType CHANGED = RuleUpdate_RuleUpdateType._(0, _omitEnumNames ? '' : 'CHANGED');
+  static const RuleUpdate_RuleUpdateType REMOVED = RuleUpdate_RuleUpdateType._(1, _omitEnumNames ? '' : 'REMOVED');
+  static const RuleUpdate_RuleUpdateType EXPIRED = RuleUpdate_RuleUpdateType._(2, _omitEnumNames ? '' : 'EXPIRED');
+
+  static const $core.List<RuleUpdate_RuleUpdateType> values = <RuleUpdate_RuleUpdateType> [
+    CHANGED,
+    REMOVED,
+    EXPIRED,
+  ];
+
+  static final $core.Map<$core.int, RuleUpdate_RuleUpdateType> _byValue = $pb.ProtobufEnum.initByValue(values);
+  static RuleUpdate_RuleUpdateType? valueOf($core.int value) => _byValue[value];
+
+  const RuleUpdate_RuleUpdateType._($core.int v, $core.String n) : super(v, n);
+}
+
+class HistoryEntry_HistoryEvent extends $pb.ProtobufEnum {
+  static const HistoryEntry_HistoryEvent PROMPTED = HistoryEntry_HistoryEvent._(0, _omitEnumNames ? '' : 'PROMPTED');
+  static const HistoryEntry_HistoryEvent DISPLAYED = HistoryEntry_HistoryEvent._(1, _omitEnumNames ? '' : 'DISPLAYED');
+  static const HistoryEntry_HistoryEvent REPLIED = HistoryEntry_HistoryEvent._(2, _omitEnumNames ? '' : 'REPLIED');
+  static const HistoryEntry_HistoryEvent DROPPED = HistoryEntry_HistoryEvent._(3, _omitEnumNames ? '' : 'DROPPED');
+  static const HistoryEntry_HistoryEvent RESOLVED_BY_RULE = HistoryEntry_HistoryEvent._(4, _omitEnumNames ? '' : 'RESOLVED_BY_RULE');
+
+  static const $core.List<HistoryEntry_HistoryEvent> values = <HistoryEntry_HistoryEvent> [
+    PROMPTED,
+    DISPLAYED,
+    REPLIED,
+    DROPPED,
+    RESOLVED_BY_RULE,
+  ];
+
+  static final $core.Map<$core.int, HistoryEntry_HistoryEvent> _byValue = $pb.ProtobufEnum.initByValue(values);
+  static HistoryEntry_HistoryEvent? valueOf($core.int value) => _byValue[value];
+
+  const HistoryEntry_HistoryEvent._($core.int v, $core.String n) : super(v, n);
+}
+
+class HistoryEntry_ReplyOutcome extends $pb.ProtobufEnum {
+  static const HistoryEntry_ReplyOutcome SUCCESS = HistoryEntry_ReplyOutcome._(0, _omitEnumNames ? '' : 'SUCCESS');
+  static const HistoryEntry_ReplyOutcome NOT_FOUND = HistoryEntry_ReplyOutcome._(1, _omitEnumNames ? '' : 'NOT_FOUND');
+  static const HistoryEntry_ReplyOutcome TIMEOUT_DENY = HistoryEntry_ReplyOutcome._(2, _omitEnumNames ? '' : 'TIMEOUT_DENY');
+  static const HistoryEntry_ReplyOutcome MAPPING_FAILURE_DENY = HistoryEntry_ReplyOutcome._(3, _omitEnumNames ? '' : 'MAPPING_FAILURE_DENY');
+  static const HistoryEntry_ReplyOutcome FAILED = HistoryEntry_ReplyOutcome._(4, _omitEnumNames ? '' : 'FAILED');
+
+  static const $core.List<HistoryEntry_ReplyOutcome> values = <HistoryEntry_ReplyOutcome> [
+    SUCCESS,
+    NOT_FOUND,
+    TIMEOUT_DENY,
+    MAPPING_FAILURE_DENY,
+    FAILED,
+  ];
+
+  static final $core.Map<$core.int, HistoryEntry_ReplyOutcome> _byValue = $pb.ProtobufEnum.initByValue(values);
+  static HistoryEntry_ReplyOutcome? valueOf($core.int value) => _byValue[value];
+
+  const HistoryEntry_ReplyOutcome._($core.int v, $core.String n) : super(v, n);
+}
+
 
 const _omitEnumNames = $core.bool.fromEnvironment('protobuf.omit_enum_names');
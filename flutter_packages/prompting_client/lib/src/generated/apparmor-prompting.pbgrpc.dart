@@ -27,10 +27,22 @@ class AppArmorPromptingClient extends $grpc.Client {
       '/apparmor_prompting.AppArmorPrompting/GetCurrentPrompt',
       ($0.Empty value) => value.writeToBuffer(),
       ($core.List<$core.int> value) => $1.GetCurrentPromptResponse.fromBuffer(value));
+  static final _$watchPrompts = $grpc.ClientMethod<$0.Empty, $1.WatchPromptsResponse>(
+      '/apparmor_prompting.AppArmorPrompting/WatchPrompts',
+      ($0.Empty value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.WatchPromptsResponse.fromBuffer(value));
+  static final _$getPromptQueue = $grpc.ClientMethod<$0.Empty, $1.PromptQueue>(
+      '/apparmor_prompting.AppArmorPrompting/GetPromptQueue',
+      ($0.Empty value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.PromptQueue.fromBuffer(value));
   static final _$replyToPrompt = $grpc.ClientMethod<$1.PromptReply, $1.PromptReplyResponse>(
       '/apparmor_prompting.AppArmorPrompting/ReplyToPrompt',
       ($1.PromptReply value) => value.writeToBuffer(),
       ($core.List<$core.int> value) => $1.PromptReplyResponse.fromBuffer(value));
+  static final _$replyToPrompts = $grpc.ClientMethod<$1.PromptReply, $1.ReplyToPromptsResponse>(
+      '/apparmor_prompting.AppArmorPrompting/ReplyToPrompts',
+      ($1.PromptReply value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.ReplyToPromptsResponse.fromBuffer(value));
   static final _$resolveHomePatternType = $grpc.ClientMethod<$2.StringValue, $1.ResolveHomePatternTypeResponse>(
       '/apparmor_prompting.AppArmorPrompting/ResolveHomePatternType',
       ($2.StringValue value) => value.writeToBuffer(),
@@ -39,6 +51,26 @@ class AppArmorPromptingClient extends $grpc.Client {
       '/apparmor_prompting.AppArmorPrompting/SetLoggingFilter',
       ($2.StringValue value) => value.writeToBuffer(),
       ($core.List<$core.int> value) => $1.SetLoggingFilterResponse.fromBuffer(value));
+  static final _$listRules = $grpc.ClientMethod<$1.ListRulesRequest, $1.ListRulesResponse>(
+      '/apparmor_prompting.AppArmorPrompting/ListRules',
+      ($1.ListRulesRequest value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.ListRulesResponse.fromBuffer(value));
+  static final _$removeRule = $grpc.ClientMethod<$2.StringValue, $1.Rule>(
+      '/apparmor_prompting.AppArmorPrompting/RemoveRule',
+      ($2.StringValue value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.Rule.fromBuffer(value));
+  static final _$updateRule = $grpc.ClientMethod<$1.UpdateRuleRequest, $1.Rule>(
+      '/apparmor_prompting.AppArmorPrompting/UpdateRule',
+      ($1.UpdateRuleRequest value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.Rule.fromBuffer(value));
+  static final _$watchRules = $grpc.ClientMethod<$0.Empty, $1.RuleUpdate>(
+      '/apparmor_prompting.AppArmorPrompting/WatchRules',
+      ($0.Empty value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.RuleUpdate.fromBuffer(value));
+  static final _$getHistory = $grpc.ClientMethod<$1.GetHistoryRequest, $1.GetHistoryResponse>(
+      '/apparmor_prompting.AppArmorPrompting/GetHistory',
+      ($1.GetHistoryRequest value) => value.writeToBuffer(),
+      ($core.List<$core.int> value) => $1.GetHistoryResponse.fromBuffer(value));
 
   AppArmorPromptingClient($grpc.ClientChannel channel,
       {$grpc.CallOptions? options,
@@ -50,10 +82,22 @@ class AppArmorPromptingClient extends $grpc.Client {
     return $createUnaryCall(_$getCurrentPrompt, request, options: options);
   }
 
+  $grpc.ResponseStream<$1.WatchPromptsResponse> watchPrompts($0.Empty request, {$grpc.CallOptions? options}) {
+    return $createStreamingCall(_$watchPrompts, $async.Stream.fromIterable([request]), options: options);
+  }
+
+  $grpc.ResponseFuture<$1.PromptQueue> getPromptQueue($0.Empty request, {$grpc.CallOptions? options}) {
+    return $createUnaryCall(_$getPromptQueue, request, options: options);
+  }
+
   $grpc.ResponseFuture<$1.PromptReplyResponse> replyToPrompt($1.PromptReply request, {$grpc.CallOptions? options}) {
     return $createUnaryCall(_$replyToPrompt, request, options: options);
   }
 
+  $grpc.ResponseFuture<$1.ReplyToPromptsResponse> replyToPrompts($1.PromptReply request, {$grpc.CallOptions? options}) {
+    return $createUnaryCall(_$replyToPrompts, request, options: options);
+  }
+
   $grpc.ResponseFuture<$1.ResolveHomePatternTypeResponse> resolveHomePatternType($2.StringValue request, {$grpc.CallOptions? options}) {
     return $createUnaryCall(_$resolveHomePatternType, request, options: options);
   }
@@ -61,6 +105,26 @@ class AppArmorPromptingClient extends $grpc.Client {
   $grpc.ResponseFuture<$1.SetLoggingFilterResponse> setLoggingFilter($2.StringValue request, {$grpc.CallOptions? options}) {
     return $createUnaryCall(_$setLoggingFilter, request, options: options);
   }
+
+  $grpc.ResponseFuture<$1.ListRulesResponse> listRules($1.ListRulesRequest request, {$grpc.CallOptions? options}) {
+    return $createUnaryCall(_$listRules, request, options: options);
+  }
+
+  $grpc.ResponseFuture<$1.Rule> removeRule($2.StringValue request, {$grpc.CallOptions? options}) {
+    return $createUnaryCall(_$removeRule, request, options: options);
+  }
+
+  $grpc.ResponseFuture<$1.Rule> updateRule($1.UpdateRuleRequest request, {$grpc.CallOptions? options}) {
+    return $createUnaryCall(_$updateRule, request, options: options);
+  }
+
+  $grpc.ResponseStream<$1.RuleUpdate> watchRules($0.Empty request, {$grpc.CallOptions? options}) {
+    return $createStreamingCall(_$watchRules, $async.Stream.fromIterable([request]), options: options);
+  }
+
+  $grpc.ResponseFuture<$1.GetHistoryResponse> getHistory($1.GetHistoryRequest request, {$grpc.CallOptions? options}) {
+    return $createUnaryCall(_$getHistory, request, options: options);
+  }
 }
 
 @$pb.GrpcServiceName('apparmor_prompting.AppArmorPrompting')
@@ -75,6 +139,20 @@ abstract class AppArmorPromptingServiceBase extends $grpc.Service {
         false,
         ($core.List<$core.int> value) => $0.Empty.fromBuffer(value),
         ($1.GetCurrentPromptResponse value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$0.Empty, $1.WatchPromptsResponse>(
+        'WatchPrompts',
+        watchPrompts_Pre,
+        false,
+        true,
+        ($core.List<$core.int> value) => $0.Empty.fromBuffer(value),
+        ($1.WatchPromptsResponse value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$0.Empty, $1.PromptQueue>(
+        'GetPromptQueue',
+        getPromptQueue_Pre,
+        false,
+        false,
+        ($core.List<$core.int> value) => $0.Empty.fromBuffer(value),
+        ($1.PromptQueue value) => value.writeToBuffer()));
     $addMethod($grpc.ServiceMethod<$1.PromptReply, $1.PromptReplyResponse>(
         'ReplyToPrompt',
         replyToPrompt_Pre,
@@ -82,6 +160,13 @@ abstract class AppArmorPromptingServiceBase extends $grpc.Service {
         false,
         ($core.List<$core.int> value) => $1.PromptReply.fromBuffer(value),
         ($1.PromptReplyResponse value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$1.PromptReply, $1.ReplyToPromptsResponse>(
+        'ReplyToPrompts',
+        replyToPrompts_Pre,
+        false,
+        false,
+        ($core.List<$core.int> value) => $1.PromptReply.fromBuffer(value),
+        ($1.ReplyToPromptsResponse value) => value.writeToBuffer()));
     $addMethod($grpc.ServiceMethod<$2.StringValue, $1.ResolveHomePatternTypeResponse>(
         'ResolveHomePatternType',
         resolveHomePatternType_Pre,
@@ -96,16 +181,63 @@ abstract class AppArmorPromptingServiceBase extends $grpc.Service {
         false,
         ($core.List<$core.int> value) => $2.StringValue.fromBuffer(value),
         ($1.SetLoggingFilterResponse value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$1.ListRulesRequest, $1.ListRulesResponse>(
+        'ListRules',
+        listRules_Pre,
+        false,
+        false,
+        ($core.List<$core.int> value) => $1.ListRulesRequest.fromBuffer(value),
+        ($1.ListRulesResponse value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$2.StringValue, $1.Rule>(
+        'RemoveRule',
+        removeRule_Pre,
+        false,
+        false,
+        ($core.List<$core.int> value) => $2.StringValue.fromBuffer(value),
+        ($1.Rule value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$1.UpdateRuleRequest, $1.Rule>(
+        'UpdateRule',
+        updateRule_Pre,
+        false,
+        false,
+        ($core.List<$core.int> value) => $1.UpdateRuleRequest.fromBuffer(value),
+        ($1.Rule value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$0.Empty, $1.RuleUpdate>(
+        'WatchRules',
+        watchRules_Pre,
+        false,
+        true,
+        ($core.List<$core.int> value) => $0.Empty.fromBuffer(value),
+        ($1.RuleUpdate value) => value.writeToBuffer()));
+    $addMethod($grpc.ServiceMethod<$1.GetHistoryRequest, $1.GetHistoryResponse>(
+        'GetHistory',
+        getHistory_Pre,
+        false,
+        false,
+        ($core.List<$core.int> value) => $1.GetHistoryRequest.fromBuffer(value),
+        ($1.GetHistoryResponse value) => value.writeToBuffer()));
   }
 
   $async.Future<$1.GetCurrentPromptResponse> getCurrentPrompt_Pre($grpc.ServiceCall call, $async.Future<$0.Empty> request) async {
     return getCurrentPrompt(call, await request);
   }
 
+  $async.Stream<$1.WatchPromptsResponse> watchPrompts_Pre($grpc.ServiceCall call, $async.Future<$0.Empty> request) async* {
+    yield* watchPrompts(call, await request);
+  }
+
+  $async.Future<$1.PromptQueue> getPromptQueue_Pre($grpc.ServiceCall call, $async.Future<$0.Empty> request) async {
+    return getPromptQueue(call, await request);
+  }
+
   $async.Future<$1.PromptReplyResponse> replyToPrompt_Pre($grpc.ServiceCall call, $async.Future<$1.PromptReply> request) async {
     return replyToPrompt(call, await request);
   }
 
+  $async.Future<$1.ReplyToPromptsResponse> replyToPrompts_Pre($grpc.ServiceCall call, $async.Future<$1.PromptReply> request) async {
+    return replyToPrompts(call, await request);
+  }
+
   $async.Future<$1.ResolveHomePatternTypeResponse> resolveHomePatternType_Pre($grpc.ServiceCall call, $async.Future<$2.StringValue> request) async {
     return resolveHomePatternType(call, await request);
   }
@@ -114,8 +246,36 @@ abstract class AppArmorPromptingServiceBase extends $grpc.Service {
     return setLoggingFilter(call, await request);
   }
 
+  $async.Future<$1.ListRulesResponse> listRules_Pre($grpc.ServiceCall call, $async.Future<$1.ListRulesRequest> request) async {
+    return listRules(call, await request);
+  }
+
+  $async.Future<$1.Rule> removeRule_Pre($grpc.ServiceCall call, $async.Future<$2.StringValue> request) async {
+    return removeRule(call, await request);
+  }
+
+  $async.Future<$1.Rule> updateRule_Pre($grpc.ServiceCall call, $async.Future<$1.UpdateRuleRequest> request) async {
+    return updateRule(call, await request);
+  }
+
+  $async.Stream<$1.RuleUpdate> watchRules_Pre($grpc.ServiceCall call, $async.Future<$0.Empty> request) async* {
+    yield* watchRules(call, await request);
+  }
+
+  $async.Future<$1.GetHistoryResponse> getHistory_Pre($grpc.ServiceCall call, $async.Future<$1.GetHistoryRequest> request) async {
+    return getHistory(call, await request);
+  }
+
   $async.Future<$1.GetCurrentPromptResponse> getCurrentPrompt($grpc.ServiceCall call, $0.Empty request);
+  $async.Stream<$1.WatchPromptsResponse> watchPrompts($grpc.ServiceCall call, $0.Empty request);
+  $async.Future<$1.PromptQueue> getPromptQueue($grpc.ServiceCall call, $0.Empty request);
   $async.Future<$1.PromptReplyResponse> replyToPrompt($grpc.ServiceCall call, $1.PromptReply request);
+  $async.Future<$1.ReplyToPromptsResponse> replyToPrompts($grpc.ServiceCall call, $1.PromptReply request);
   $async.Future<$1.ResolveHomePatternTypeResponse> resolveHomePatternType($grpc.ServiceCall call, $2.StringValue request);
   $async.Future<$1.SetLoggingFilterResponse> setLoggingFilter($grpc.ServiceCall call, $2.StringValue request);
+  $async.Future<$1.ListRulesResponse> listRules($grpc.ServiceCall call, $1.ListRulesRequest request);
+  $async.Future<$1.Rule> removeRule($grpc.ServiceCall call, $2.StringValue request);
+  $async.Future<$1.Rule> updateRule($grpc.ServiceCall call, $1.UpdateRuleRequest request);
+  $async.Stream<$1.RuleUpdate> watchRules($grpc.ServiceCall call, $0.Empty request);
+  $async.Future<$1.GetHistoryResponse> getHistory($grpc.ServiceCall call, $1.GetHistoryRequest request);
 }
@@ -33,12 +33,14 @@ const Lifespan$json = {
     {'1': 'SINGLE', '2': 0},
     {'1': 'SESSION', '2': 1},
     {'1': 'FOREVER', '2': 2},
+    {'1': 'TIMESPAN', '2': 3},
   ],
 };
 
 /// Descriptor for `Lifespan`. Decode as a `google.protobuf.EnumDescriptorProto`.
 final $typed_data.Uint8List lifespanDescriptor = $convert.base64Decode(
-    'CghMaWZlc3BhbhIKCgZTSU5HTEUQABILCgdTRVNTSU9OEAESCwoHRk9SRVZFUhAC');
+    'CghMaWZlc3BhbhIKCgZTSU5HTEUQABILCgdTRVNTSU9OEAESCwoHRk9SRVZFUhACEgwKCFRJTU'
+    'VTUEFOEAM=');
 
 @$core.Deprecated('Use homePatternTypeDescriptor instead')
 const HomePatternType$json = {
@@ -61,6 +63,87 @@ final $typed_data.Uint8List homePatternTypeDescriptor = $convert.base64Decode(
     'EAMSEgoOSE9NRV9ESVJFQ1RPUlkQBBIbChdNQVRDSElOR19GSUxFX0VYVEVOU0lPThAFEiAKHF'
     'JFUVVFU1RFRF9ESVJFQ1RPUllfQ09OVEVOVFMQBg==');
 
+@$core.Deprecated('Use watchPromptsResponseDescriptor instead')
+const WatchPromptsResponse$json = {
+  '1': 'WatchPromptsResponse',
+  '2': [
+    {'1': 'status', '3': 1, '4': 1, '5': 14, '6': '.apparmor_prompting.WatchPromptsResponse.PromptStatus', '10': 'status'},
+    {'1': 'prompt_id', '3': 2, '4': 1, '5': 9, '10': 'promptId'},
+    {'1': 'current_prompt', '3': 3, '4': 1, '5': 11, '6': '.apparmor_prompting.GetCurrentPromptResponse', '10': 'currentPrompt'},
+    {'1': 'queue', '3': 4, '4': 1, '5': 11, '6': '.apparmor_prompting.PromptQueue', '10': 'queue'},
+  ],
+  '4': [WatchPromptsResponse_PromptStatus$json],
+};
+
+@$core.Deprecated('Use watchPromptsResponseDescriptor instead')
+const WatchPromptsResponse_PromptStatus$json = {
+  '1': 'PromptStatus',
+  '2': [
+    {'1': 'NO_PROMPT', '2': 0},
+    {'1': 'ACTIVE', '2': 1},
+    {'1': 'ACTIONED', '2': 2},
+    {'1': 'DROPPED', '2': 3},
+    {'1': 'TIMED_OUT', '2': 4},
+  ],
+};
+
+/// Descriptor for `WatchPromptsResponse`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List watchPromptsResponseDescriptor = $convert.base64Decode(
+    'ChRXYXRjaFByb21wdHNSZXNwb25zZRJNCgZzdGF0dXMYASABKA4yNS5hcHBhcm1vcl9wcm9tcH'
+    'RpbmcuV2F0Y2hQcm9tcHRzUmVzcG9uc2UuUHJvbXB0U3RhdHVzUgZzdGF0dXMSGwoJcHJvbXB0'
+    'X2lkGAIgASgJUghwcm9tcHRJZBJTCg5jdXJyZW50X3Byb21wdBgDIAEoCzIsLmFwcGFybW9yX3'
+    'Byb21wdGluZy5HZXRDdXJyZW50UHJvbXB0UmVzcG9uc2VSDWN1cnJlbnRQcm9tcHQSNQoFcXVl'
+    'dWUYBCABKAsyHy5hcHBhcm1vcl9wcm9tcHRpbmcuUHJvbXB0UXVldWVSBXF1ZXVlIlMKDFByb2'
+    '1wdFN0YXR1cxINCglOT19QUk9NUFQQABIKCgZBQ1RJVkUQARIMCghBQ1RJT05FRBACEgsKB0RS'
+    'T1BQRUQQAxINCglUSU1FRF9PVVQQBA==');
+
+@$core.Deprecated('Use promptQueueDescriptor instead')
+const PromptQueue$json = {
+  '1': 'PromptQueue',
+  '2': [
+    {'1': 'prompts', '3': 1, '4': 3, '5': 11, '6': '.apparmor_prompting.QueuedPrompt', '10': 'prompts'},
+  ],
+};
+
+/// Descriptor for `PromptQueue`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List promptQueueDescriptor = $convert.base64Decode(
+    'CgtQcm9tcHRRdWV1ZRI6Cgdwcm9tcHRzGAEgAygLMiAuYXBwYXJtb3JfcHJvbXB0aW5nLlF1ZX'
+    'VlZFByb21wdFIHcHJvbXB0cw==');
+
+@$core.Deprecated('Use queuedPromptDescriptor instead')
+const QueuedPrompt$json = {
+  '1': 'QueuedPrompt',
+  '2': [
+    {'1': 'prompt_id', '3': 1, '4': 1, '5': 9, '10': 'promptId'},
+    {'1': 'snap_name', '3': 2, '4': 1, '5': 9, '10': 'snapName'},
+    {'1': 'interface', '3': 3, '4': 1, '5': 9, '10': 'interface'},
+    {'1': 'requested_path', '3': 4, '4': 1, '5': 9, '10': 'requestedPath'},
+    {'1': 'requested_permissions', '3': 5, '4': 3, '5': 9, '10': 'requestedPermissions'},
+  ],
+};
+
+/// Descriptor for `QueuedPrompt`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List queuedPromptDescriptor = $convert.base64Decode(
+    'CgxRdWV1ZWRQcm9tcHQSGwoJcHJvbXB0X2lkGAEgASgJUghwcm9tcHRJZBIbCglzbmFwX25hbW'
+    'UYAiABKAlSCHNuYXBOYW1lEhwKCWludGVyZmFjZRgDIAEoCVIJaW50ZXJmYWNlEiUKDnJlcXVl'
+    'c3RlZF9wYXRoGAQgASgJUg1yZXF1ZXN0ZWRQYXRoEjMKFXJlcXVlc3RlZF9wZXJtaXNzaW9ucx'
+    'gFIAMoCVIUcmVxdWVzdGVkUGVybWlzc2lvbnM=');
+
+@$core.Deprecated('Use replyToPromptsResponseDescriptor instead')
+const ReplyToPromptsResponse$json = {
+  '1': 'ReplyToPromptsResponse',
+  '2': [
+    {'1': 'reply', '3': 1, '4': 1, '5': 11, '6': '.apparmor_prompting.PromptReplyResponse', '10': 'reply'},
+    {'1': 'actioned_prompt_ids', '3': 2, '4': 3, '5': 9, '10': 'actionedPromptIds'},
+  ],
+};
+
+/// Descriptor for `ReplyToPromptsResponse`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List replyToPromptsResponseDescriptor = $convert.base64Decode(
+    'ChZSZXBseVRvUHJvbXB0c1Jlc3BvbnNlEj0KBXJlcGx5GAEgASgLMicuYXBwYXJtb3JfcHJvbX'
+    'B0aW5nLlByb21wdFJlcGx5UmVzcG9uc2VSBXJlcGx5Ei4KE2FjdGlvbmVkX3Byb21wdF9pZHMY'
+    'AiADKAlSEWFjdGlvbmVkUHJvbXB0SWRz');
+
 @$core.Deprecated('Use promptReplyDescriptor instead')
 const PromptReply$json = {
   '1': 'PromptReply',
@@ -69,9 +152,14 @@ const PromptReply$json = {
     {'1': 'action', '3': 2, '4': 1, '5': 14, '6': '.apparmor_prompting.Action', '10': 'action'},
     {'1': 'lifespan', '3': 3, '4': 1, '5': 14, '6': '.apparmor_prompting.Lifespan', '10': 'lifespan'},
     {'1': 'home_prompt_reply', '3': 4, '4': 1, '5': 11, '6': '.apparmor_prompting.HomePromptReply', '9': 0, '10': 'homePromptReply'},
+    {'1': 'camera_prompt_reply', '3': 5, '4': 1, '5': 11, '6': '.apparmor_prompting.CameraPromptReply', '9': 0, '10': 'cameraPromptReply'},
+    {'1': 'audio_record_prompt_reply', '3': 6, '4': 1, '5': 11, '6': '.apparmor_prompting.AudioRecordPromptReply', '9': 0, '10': 'audioRecordPromptReply'},
+    {'1': 'generic_prompt_reply', '3': 7, '4': 1, '5': 11, '6': '.apparmor_prompting.GenericPromptReply', '9': 0, '10': 'genericPromptReply'},
+    {'1': 'duration', '3': 8, '4': 1, '5': 9, '9': 1, '10': 'duration', '17': true},
   ],
   '8': [
     {'1': 'prompt_reply'},
+    {'1': '_duration'},
   ],
 };
 
@@ -81,7 +169,13 @@ final $typed_data.Uint8List promptReplyDescriptor = $convert.base64Decode(
     'EoDjIaLmFwcGFybW9yX3Byb21wdGluZy5BY3Rpb25SBmFjdGlvbhI4CghsaWZlc3BhbhgDIAEo'
     'DjIcLmFwcGFybW9yX3Byb21wdGluZy5MaWZlc3BhblIIbGlmZXNwYW4SUQoRaG9tZV9wcm9tcH'
     'RfcmVwbHkYBCABKAsyIy5hcHBhcm1vcl9wcm9tcHRpbmcuSG9tZVByb21wdFJlcGx5SABSD2hv'
-    'bWVQcm9tcHRSZXBseUIOCgxwcm9tcHRfcmVwbHk=');
+    'bWVQcm9tcHRSZXBseRJXChNjYW1lcmFfcHJvbXB0X3JlcGx5GAUgASgLMiUuYXBwYXJtb3JfcH'
+    'JvbXB0aW5nLkNhbWVyYVByb21wdFJlcGx5SABSEWNhbWVyYVByb21wdFJlcGx5EmcKGWF1ZGlv'
+    'X3JlY29yZF9wcm9tcHRfcmVwbHkYBiABKAsyKi5hcHBhcm1vcl9wcm9tcHRpbmcuQXVkaW9SZW'
+    'NvcmRQcm9tcHRSZXBseUgAUhZhdWRpb1JlY29yZFByb21wdFJlcGx5EloKFGdlbmVyaWNfcHJv'
+    'bXB0X3JlcGx5GAcgASgLMiYuYXBwYXJtb3JfcHJvbXB0aW5nLkdlbmVyaWNQcm9tcHRSZXBseU'
+    'gAUhJnZW5lcmljUHJvbXB0UmVwbHkSHwoIZHVyYXRpb24YCCABKAlIAVIIZHVyYXRpb26IAQFC'
+    'DgoMcHJvbXB0X3JlcGx5QgsKCV9kdXJhdGlvbg==');
 
 @$core.Deprecated('Use promptReplyResponseDescriptor instead')
 const PromptReplyResponse$json = {
@@ -115,6 +209,9 @@ const GetCurrentPromptResponse$json = {
   '1': 'GetCurrentPromptResponse',
   '2': [
     {'1': 'home_prompt', '3': 1, '4': 1, '5': 11, '6': '.apparmor_prompting.HomePrompt', '9': 0, '10': 'homePrompt'},
+    {'1': 'camera_prompt', '3': 2, '4': 1, '5': 11, '6': '.apparmor_prompting.CameraPrompt', '9': 0, '10': 'cameraPrompt'},
+    {'1': 'audio_record_prompt', '3': 3, '4': 1, '5': 11, '6': '.apparmor_prompting.AudioRecordPrompt', '9': 0, '10': 'audioRecordPrompt'},
+    {'1': 'generic_prompt', '3': 4, '4': 1, '5': 11, '6': '.apparmor_prompting.GenericPrompt', '9': 0, '10': 'genericPrompt'},
   ],
   '8': [
     {'1': 'prompt'},
@@ -124,7 +221,12 @@ const GetCurrentPromptResponse$json = {
 /// Descriptor for `GetCurrentPromptResponse`. Decode as a `google.protobuf.DescriptorProto`.
 final $typed_data.Uint8List getCurrentPromptResponseDescriptor = $convert.base64Decode(
     'ChhHZXRDdXJyZW50UHJvbXB0UmVzcG9uc2USQQoLaG9tZV9wcm9tcHQYASABKAsyHi5hcHBhcm'
-    '1vcl9wcm9tcHRpbmcuSG9tZVByb21wdEgAUgpob21lUHJvbXB0QggKBnByb21wdA==');
+    '1vcl9wcm9tcHRpbmcuSG9tZVByb21wdEgAUgpob21lUHJvbXB0EkcKDWNhbWVyYV9wcm9tcHQY'
+    'AiABKAsyIC5hcHBhcm1vcl9wcm9tcHRpbmcuQ2FtZXJhUHJvbXB0SABSDGNhbWVyYVByb21wdB'
+    'JXChNhdWRpb19yZWNvcmRfcHJvbXB0GAMgASgLMiUuYXBwYXJtb3JfcHJvbXB0aW5nLkF1ZGlv'
+    'UmVjb3JkUHJvbXB0SABSEWF1ZGlvUmVjb3JkUHJvbXB0EkoKDmdlbmVyaWNfcHJvbXB0GAQgAS'
+    'gLMiEuYXBwYXJtb3JfcHJvbXB0aW5nLkdlbmVyaWNQcm9tcHRIAFINZ2VuZXJpY1Byb21wdEII'
+    'CgZwcm9tcHQ=');
 
 @$core.Deprecated('Use homePromptReplyDescriptor instead')
 const HomePromptReply$json = {
@@ -181,6 +283,87 @@ final $typed_data.Uint8List homePromptDescriptor = $convert.base64Decode(
     'ASgJUgtwYXRoUGF0dGVybhIlCg5zaG93X2luaXRpYWxseRgDIAEoCFINc2hvd0luaXRpYWxseQ'
     '==');
 
+@$core.Deprecated('Use cameraPromptReplyDescriptor instead')
+const CameraPromptReply$json = {
+  '1': 'CameraPromptReply',
+};
+
+/// Descriptor for `CameraPromptReply`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List cameraPromptReplyDescriptor = $convert.base64Decode(
+    'ChFDYW1lcmFQcm9tcHRSZXBseQ==');
+
+@$core.Deprecated('Use cameraPromptDescriptor instead')
+const CameraPrompt$json = {
+  '1': 'CameraPrompt',
+  '2': [
+    {'1': 'meta_data', '3': 1, '4': 1, '5': 11, '6': '.apparmor_prompting.MetaData', '10': 'metaData'},
+  ],
+};
+
+/// Descriptor for `CameraPrompt`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List cameraPromptDescriptor = $convert.base64Decode(
+    'CgxDYW1lcmFQcm9tcHQSOQoJbWV0YV9kYXRhGAEgASgLMhwuYXBwYXJtb3JfcHJvbXB0aW5nLk'
+    '1ldGFEYXRhUghtZXRhRGF0YQ==');
+
+@$core.Deprecated('Use audioRecordPromptReplyDescriptor instead')
+const AudioRecordPromptReply$json = {
+  '1': 'AudioRecordPromptReply',
+};
+
+/// Descriptor for `AudioRecordPromptReply`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List audioRecordPromptReplyDescriptor = $convert.base64Decode(
+    'ChZBdWRpb1JlY29yZFByb21wdFJlcGx5');
+
+@$core.Deprecated('Use audioRecordPromptDescriptor instead')
+const AudioRecordPrompt$json = {
+  '1': 'AudioRecordPrompt',
+  '2': [
+    {'1': 'meta_data', '3': 1, '4': 1, '5': 11, '6': '.apparmor_prompting.MetaData', '10': 'metaData'},
+  ],
+};
+
+/// Descriptor for `AudioRecordPrompt`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List audioRecordPromptDescriptor = $convert.base64Decode(
+    'ChFBdWRpb1JlY29yZFByb21wdBI5CgltZXRhX2RhdGEYASABKAsyHC5hcHBhcm1vcl9wcm9tcH'
+    'RpbmcuTWV0YURhdGFSCG1ldGFEYXRh');
+
+@$core.Deprecated('Use genericPromptReplyDescriptor instead')
+const GenericPromptReply$json = {
+  '1': 'GenericPromptReply',
+};
+
+/// Descriptor for `GenericPromptReply`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List genericPromptReplyDescriptor = $convert.base64Decode(
+    'ChJHZW5lcmljUHJvbXB0UmVwbHk=');
+
+@$core.Deprecated('Use genericPromptDescriptor instead')
+const GenericPrompt$json = {
+  '1': 'GenericPrompt',
+  '2': [
+    {'1': 'meta_data', '3': 1, '4': 1, '5': 11, '6': '.apparmor_prompting.MetaData', '10': 'metaData'},
+    {'1': 'interface', '3': 2, '4': 1, '5': 9, '10': 'interface'},
+    {'1': 'constraints', '3': 3, '4': 3, '5': 11, '6': '.apparmor_prompting.GenericPrompt.Constraint', '10': 'constraints'},
+  ],
+  '3': [GenericPrompt_Constraint$json],
+};
+
+@$core.Deprecated('Use genericPromptDescriptor instead')
+const GenericPrompt_Constraint$json = {
+  '1': 'Constraint',
+  '2': [
+    {'1': 'key', '3': 1, '4': 1, '5': 9, '10': 'key'},
+    {'1': 'value', '3': 2, '4': 1, '5': 9, '10': 'value'},
+  ],
+};
+
+/// Descriptor for `GenericPrompt`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List genericPromptDescriptor = $convert.base64Decode(
+    'Cg1HZW5lcmljUHJvbXB0EjkKCW1ldGFfZGF0YRgBIAEoCzIcLmFwcGFybW9yX3Byb21wdGluZy'
+    '5NZXRhRGF0YVIIbWV0YURhdGESHAoJaW50ZXJmYWNlGAIgASgJUglpbnRlcmZhY2USTgoLY29u'
+    'c3RyYWludHMYAyADKAsyLC5hcHBhcm1vcl9wcm9tcHRpbmcuR2VuZXJpY1Byb21wdC5Db25zdH'
+    'JhaW50Ugtjb25zdHJhaW50cxo0CgpDb25zdHJhaW50EhAKA2tleRgBIAEoCVIDa2V5EhQKBXZh'
+    'bHVlGAIgASgJUgV2YWx1ZQ==');
+
 @$core.Deprecated('Use metaDataDescriptor instead')
 const MetaData$json = {
   '1': 'MetaData',
@@ -190,6 +373,28 @@ const MetaData$json = {
     {'1': 'store_url', '3': 3, '4': 1, '5': 9, '10': 'storeUrl'},
     {'1': 'publisher', '3': 4, '4': 1, '5': 9, '10': 'publisher'},
     {'1': 'updated_at', '3': 5, '4': 1, '5': 9, '10': 'updatedAt'},
+    {'1': 'timestamp', '3': 6, '4': 1, '5': 9, '10': 'timestamp'},
+    {'1': 'age_ms', '3': 7, '4': 1, '5': 4, '10': 'ageMs'},
+    {'1': 'publisher_validation', '3': 8, '4': 1, '5': 14, '6': '.apparmor_prompting.MetaData.PublisherValidation', '10': 'publisherValidation'},
+    {'1': 'version', '3': 9, '4': 1, '5': 9, '10': 'version'},
+    {'1': 'revision', '3': 10, '4': 1, '5': 9, '10': 'revision'},
+    {'1': 'channel', '3': 11, '4': 1, '5': 9, '10': 'channel'},
+    {'1': 'confinement', '3': 12, '4': 1, '5': 9, '10': 'confinement'},
+    {'1': 'title', '3': 13, '4': 1, '5': 9, '10': 'title'},
+    {'1': 'summary', '3': 14, '4': 1, '5': 9, '10': 'summary'},
+    {'1': 'icon_api_path', '3': 15, '4': 1, '5': 9, '10': 'iconApiPath'},
+  ],
+  '4': [MetaData_PublisherValidation$json],
+};
+
+@$core.Deprecated('Use metaDataDescriptor instead')
+const MetaData_PublisherValidation$json = {
+  '1': 'PublisherValidation',
+  '2': [
+    {'1': 'UNKNOWN', '2': 0},
+    {'1': 'UNPROVEN', '2': 1},
+    {'1': 'VERIFIED', '2': 2},
+    {'1': 'STARRED', '2': 3},
   ],
 };
 
@@ -197,7 +402,15 @@ const MetaData$json = {
 final $typed_data.Uint8List metaDataDescriptor = $convert.base64Decode(
     'CghNZXRhRGF0YRIbCglwcm9tcHRfaWQYASABKAlSCHByb21wdElkEhsKCXNuYXBfbmFtZRgCIA'
     'EoCVIIc25hcE5hbWUSGwoJc3RvcmVfdXJsGAMgASgJUghzdG9yZVVybBIcCglwdWJsaXNoZXIY'
-    'BCABKAlSCXB1Ymxpc2hlchIdCgp1cGRhdGVkX2F0GAUgASgJUgl1cGRhdGVkQXQ=');
+    'BCABKAlSCXB1Ymxpc2hlchIdCgp1cGRhdGVkX2F0GAUgASgJUgl1cGRhdGVkQXQSHAoJdGltZX'
+    'N0YW1wGAYgASgJUgl0aW1lc3RhbXASFQoGYWdlX21zGAcgASgEUgVhZ2VNcxJjChRwdWJsaXNo'
+    'ZXJfdmFsaWRhdGlvbhgIIAEoDjIwLmFwcGFybW9yX3Byb21wdGluZy5NZXRhRGF0YS5QdWJsaX'
+    'NoZXJWYWxpZGF0aW9uUhNwdWJsaXNoZXJWYWxpZGF0aW9uEhgKB3ZlcnNpb24YCSABKAlSB3Zl'
+    'cnNpb24SGgoIcmV2aXNpb24YCiABKAlSCHJldmlzaW9uEhgKB2NoYW5uZWwYCyABKAlSB2NoYW'
+    '5uZWwSIAoLY29uZmluZW1lbnQYDCABKAlSC2NvbmZpbmVtZW50EhQKBXRpdGxlGA0gASgJUgV0'
+    'aXRsZRIYCgdzdW1tYXJ5GA4gASgJUgdzdW1tYXJ5EiIKDWljb25fYXBpX3BhdGgYDyABKAlSC2'
+    'ljb25BcGlQYXRoIksKE1B1Ymxpc2hlclZhbGlkYXRpb24SCwoHVU5LTk9XThAAEgwKCFVOUFJP'
+    'VkVOEAESDAoIVkVSSUZJRUQQAhILCgdTVEFSUkVEEAM=');
 
 @$core.Deprecated('Use resolveHomePatternTypeResponseDescriptor instead')
 const ResolveHomePatternTypeResponse$json = {
@@ -225,3 +438,204 @@ const SetLoggingFilterResponse$json = {
 final $typed_data.Uint8List setLoggingFilterResponseDescriptor = $convert.base64Decode(
     'ChhTZXRMb2dnaW5nRmlsdGVyUmVzcG9uc2USGAoHY3VycmVudBgBIAEoCVIHY3VycmVudA==');
 
+@$core.Deprecated('Use listRulesRequestDescriptor instead')
+const ListRulesRequest$json = {
+  '1': 'ListRulesRequest',
+  '2': [
+    {'1': 'snap', '3': 1, '4': 1, '5': 9, '10': 'snap'},
+    {'1': 'interface', '3': 2, '4': 1, '5': 9, '10': 'interface'},
+  ],
+};
+
+/// Descriptor for `ListRulesRequest`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List listRulesRequestDescriptor = $convert.base64Decode(
+    'ChBMaXN0UnVsZXNSZXF1ZXN0EhIKBHNuYXAYASABKAlSBHNuYXASHAoJaW50ZXJmYWNlGAIgAS'
+    'gJUglpbnRlcmZhY2U=');
+
+@$core.Deprecated('Use listRulesResponseDescriptor instead')
+const ListRulesResponse$json = {
+  '1': 'ListRulesResponse',
+  '2': [
+    {'1': 'rules', '3': 1, '4': 3, '5': 11, '6': '.apparmor_prompting.Rule', '10': 'rules'},
+  ],
+};
+
+/// Descriptor for `ListRulesResponse`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List listRulesResponseDescriptor = $convert.base64Decode(
+    'ChFMaXN0UnVsZXNSZXNwb25zZRIuCgVydWxlcxgBIAMoCzIYLmFwcGFybW9yX3Byb21wdGluZy'
+    '5SdWxlUgVydWxlcw==');
+
+@$core.Deprecated('Use ruleDescriptor instead')
+const Rule$json = {
+  '1': 'Rule',
+  '2': [
+    {'1': 'id', '3': 1, '4': 1, '5': 9, '10': 'id'},
+    {'1': 'timestamp', '3': 2, '4': 1, '5': 9, '10': 'timestamp'},
+    {'1': 'snap', '3': 3, '4': 1, '5': 9, '10': 'snap'},
+    {'1': 'interface', '3': 4, '4': 1, '5': 9, '10': 'interface'},
+    {'1': 'outcome', '3': 5, '4': 1, '5': 14, '6': '.apparmor_prompting.Action', '10': 'outcome'},
+    {'1': 'lifespan', '3': 6, '4': 1, '5': 14, '6': '.apparmor_prompting.Lifespan', '10': 'lifespan'},
+    {'1': 'expiration', '3': 7, '4': 1, '5': 9, '10': 'expiration'},
+    {'1': 'permissions', '3': 8, '4': 3, '5': 9, '10': 'permissions'},
+    {'1': 'path_pattern', '3': 9, '4': 1, '5': 9, '10': 'pathPattern'},
+  ],
+};
+
+/// Descriptor for `Rule`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List ruleDescriptor = $convert.base64Decode(
+    'CgRSdWxlEg4KAmlkGAEgASgJUgJpZBIcCgl0aW1lc3RhbXAYAiABKAlSCXRpbWVzdGFtcBISCg'
+    'RzbmFwGAMgASgJUgRzbmFwEhwKCWludGVyZmFjZRgEIAEoCVIJaW50ZXJmYWNlEjQKB291dGNv'
+    'bWUYBSABKA4yGi5hcHBhcm1vcl9wcm9tcHRpbmcuQWN0aW9uUgdvdXRjb21lEjgKCGxpZmVzcG'
+    'FuGAYgASgOMhwuYXBwYXJtb3JfcHJvbXB0aW5nLkxpZmVzcGFuUghsaWZlc3BhbhIeCgpleHBp'
+    'cmF0aW9uGAcgASgJUgpleHBpcmF0aW9uEiAKC3Blcm1pc3Npb25zGAggAygJUgtwZXJtaXNzaW'
+    '9ucxIhCgxwYXRoX3BhdHRlcm4YCSABKAlSC3BhdGhQYXR0ZXJu');
+
+@$core.Deprecated('Use ruleUpdateDescriptor instead')
+const RuleUpdate$json = {
+  '1': 'RuleUpdate',
+  '2': [
+    {'1': 'id', '3': 1, '4': 1, '5': 9, '10': 'id'},
+    {'1': 'update_type', '3': 2, '4': 1, '5': 14, '6': '.apparmor_prompting.RuleUpdate.RuleUpdateType', '10': 'updateType'},
+    {'1': 'rule', '3': 3, '4': 1, '5': 11, '6': '.apparmor_prompting.Rule', '10': 'rule'},
+  ],
+  '4': [RuleUpdate_RuleUpdateType$json],
+};
+
+@$core.Deprecated('Use ruleUpdateDescriptor instead')
+const RuleUpdate_RuleUpdateType$json = {
+  '1': 'RuleUpdateType',
+  '2': [
+    {'1': 'CHANGED', '2': 0},
+    {'1': 'REMOVED', '2': 1},
+    {'1': 'EXPIRED', '2': 2},
+  ],
+};
+
+/// Descriptor for `RuleUpdate`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List ruleUpdateDescriptor = $convert.base64Decode(
+    'CgpSdWxlVXBkYXRlEg4KAmlkGAEgASgJUgJpZBJOCgt1cGRhdGVfdHlwZRgCIAEoDjItLmFwcG'
+    'FybW9yX3Byb21wdGluZy5SdWxlVXBkYXRlLlJ1bGVVcGRhdGVUeXBlUgp1cGRhdGVUeXBlEiwK'
+    'BHJ1bGUYAyABKAsyGC5hcHBhcm1vcl9wcm9tcHRpbmcuUnVsZVIEcnVsZSI3Cg5SdWxlVXBkYX'
+    'RlVHlwZRILCgdDSEFOR0VEEAASCwoHUkVNT1ZFRBABEgsKB0VYUElSRUQQAg==');
+
+@$core.Deprecated('Use updateRuleRequestDescriptor instead')
+const UpdateRuleRequest$json = {
+  '1': 'UpdateRuleRequest',
+  '2': [
+    {'1': 'id', '3': 1, '4': 1, '5': 9, '10': 'id'},
+    {'1': 'outcome', '3': 2, '4': 1, '5': 14, '6': '.apparmor_prompting.Action', '9': 0, '10': 'outcome', '17': true},
+    {'1': 'lifespan', '3': 3, '4': 1, '5': 14, '6': '.apparmor_prompting.Lifespan', '9': 1, '10': 'lifespan', '17': true},
+    {'1': 'duration', '3': 4, '4': 1, '5': 9, '9': 2, '10': 'duration', '17': true},
+    {'1': 'path_pattern', '3': 5, '4': 1, '5': 9, '9': 3, '10': 'pathPattern', '17': true},
+    {'1': 'permissions', '3': 6, '4': 3, '5': 9, '10': 'permissions'},
+  ],
+  '8': [
+    {'1': '_outcome'},
+    {'1': '_lifespan'},
+    {'1': '_duration'},
+    {'1': '_path_pattern'},
+  ],
+};
+
+/// Descriptor for `UpdateRuleRequest`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List updateRuleRequestDescriptor = $convert.base64Decode(
+    'ChFVcGRhdGVSdWxlUmVxdWVzdBIOCgJpZBgBIAEoCVICaWQSOQoHb3V0Y29tZRgCIAEoDjIaLm'
+    'FwcGFybW9yX3Byb21wdGluZy5BY3Rpb25IAFIHb3V0Y29tZYgBARI9CghsaWZlc3BhbhgDIAEo'
+    'DjIcLmFwcGFybW9yX3Byb21wdGluZy5MaWZlc3BhbkgBUghsaWZlc3BhbogBARIfCghkdXJhdG'
+    'lvbhgEIAEoCUgCUghkdXJhdGlvbogBARImCgxwYXRoX3BhdHRlcm4YBSABKAlIA1ILcGF0aFBh'
+    'dHRlcm6IAQESIAoLcGVybWlzc2lvbnMYBiADKAlSC3Blcm1pc3Npb25zQgoKCF9vdXRjb21lQg'
+    'sKCV9saWZlc3BhbkILCglfZHVyYXRpb25CDwoNX3BhdGhfcGF0dGVybg==');
+
+@$core.Deprecated('Use getHistoryRequestDescriptor instead')
+const GetHistoryRequest$json = {
+  '1': 'GetHistoryRequest',
+  '2': [
+    {'1': 'limit', '3': 1, '4': 1, '5': 13, '10': 'limit'},
+    {'1': 'snap', '3': 2, '4': 1, '5': 9, '10': 'snap'},
+    {'1': 'replies_only', '3': 3, '4': 1, '5': 8, '10': 'repliesOnly'},
+  ],
+};
+
+/// Descriptor for `GetHistoryRequest`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List getHistoryRequestDescriptor = $convert.base64Decode(
+    'ChFHZXRIaXN0b3J5UmVxdWVzdBIUCgVsaW1pdBgBIAEoDVIFbGltaXQSEgoEc25hcBgCIAEoCV'
+    'IEc25hcBIhCgxyZXBsaWVzX29ubHkYAyABKAhSC3JlcGxpZXNPbmx5');
+
+@$core.Deprecated('Use getHistoryResponseDescriptor instead')
+const GetHistoryResponse$json = {
+  '1': 'GetHistoryResponse',
+  '2': [
+    {'1': 'entries', '3': 1, '4': 3, '5': 11, '6': '.apparmor_prompting.HistoryEntry', '10': 'entries'},
+  ],
+};
+
+/// Descriptor for `GetHistoryResponse`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List getHistoryResponseDescriptor = $convert.base64Decode(
+    'ChJHZXRIaXN0b3J5UmVzcG9uc2USOgoHZW50cmllcxgBIAMoCzIgLmFwcGFybW9yX3Byb21wdG'
+    'luZy5IaXN0b3J5RW50cnlSB2VudHJpZXM=');
+
+@$core.Deprecated('Use historyEntryDescriptor instead')
+const HistoryEntry$json = {
+  '1': 'HistoryEntry',
+  '2': [
+    {'1': 'timestamp', '3': 1, '4': 1, '5': 9, '10': 'timestamp'},
+    {'1': 'prompt_id', '3': 2, '4': 1, '5': 9, '10': 'promptId'},
+    {'1': 'snap', '3': 3, '4': 1, '5': 9, '10': 'snap'},
+    {'1': 'interface', '3': 4, '4': 1, '5': 9, '10': 'interface'},
+    {'1': 'event', '3': 5, '4': 1, '5': 14, '6': '.apparmor_prompting.HistoryEntry.HistoryEvent', '10': 'event'},
+    {'1': 'constraints', '3': 6, '4': 1, '5': 9, '10': 'constraints'},
+    {'1': 'action', '3': 7, '4': 1, '5': 14, '6': '.apparmor_prompting.Action', '10': 'action'},
+    {'1': 'lifespan', '3': 8, '4': 1, '5': 14, '6': '.apparmor_prompting.Lifespan', '10': 'lifespan'},
+    {'1': 'path_pattern', '3': 9, '4': 1, '5': 9, '10': 'pathPattern'},
+    {'1': 'permissions', '3': 10, '4': 3, '5': 9, '10': 'permissions'},
+    {'1': 'latency_ms', '3': 11, '4': 1, '5': 4, '9': 0, '10': 'latencyMs', '17': true},
+    {'1': 'outcome', '3': 12, '4': 1, '5': 14, '6': '.apparmor_prompting.HistoryEntry.ReplyOutcome', '10': 'outcome'},
+    {'1': 'resolved_by', '3': 13, '4': 1, '5': 9, '10': 'resolvedBy'},
+  ],
+  '4': [HistoryEntry_HistoryEvent$json, HistoryEntry_ReplyOutcome$json],
+  '8': [
+    {'1': '_latency_ms'},
+  ],
+};
+
+@$core.Deprecated('Use historyEntryDescriptor instead')
+const HistoryEntry_HistoryEvent$json = {
+  '1': 'HistoryEvent',
+  '2': [
+    {'1': 'PROMPTED', '2': 0},
+    {'1': 'DISPLAYED', '2': 1},
+    {'1': 'REPLIED', '2': 2},
+    {'1': 'DROPPED', '2': 3},
+    {'1': 'RESOLVED_BY_RULE', '2': 4},
+  ],
+};
+
+@$core.Deprecated('Use historyEntryDescriptor instead')
+const HistoryEntry_ReplyOutcome$json = {
+  '1': 'ReplyOutcome',
+  '2': [
+    {'1': 'SUCCESS', '2': 0},
+    {'1': 'NOT_FOUND', '2': 1},
+    {'1': 'TIMEOUT_DENY', '2': 2},
+    {'1': 'MAPPING_FAILURE_DENY', '2': 3},
+    {'1': 'FAILED', '2': 4},
+  ],
+};
+
+/// Descriptor for `HistoryEntry`. Decode as a `google.protobuf.DescriptorProto`.
+final $typed_data.Uint8List historyEntryDescriptor = $convert.base64Decode(
+    'CgxIaXN0b3J5RW50cnkSHAoJdGltZXN0YW1wGAEgASgJUgl0aW1lc3RhbXASGwoJcHJvbXB0X2'
+    'lkGAIgASgJUghwcm9tcHRJZBISCgRzbmFwGAMgASgJUgRzbmFwEhwKCWludGVyZmFjZRgEIAEo'
+    'CVIJaW50ZXJmYWNlEkMKBWV2ZW50GAUgASgOMi0uYXBwYXJtb3JfcHJvbXB0aW5nLkhpc3Rvcn'
+    'lFbnRyeS5IaXN0b3J5RXZlbnRSBWV2ZW50EiAKC2NvbnN0cmFpbnRzGAYgASgJUgtjb25zdHJh'
+    'aW50cxIyCgZhY3Rpb24YByABKA4yGi5hcHBhcm1vcl9wcm9tcHRpbmcuQWN0aW9uUgZhY3Rpb2'
+    '4SOAoIbGlmZXNwYW4YCCABKA4yHC5hcHBhcm1vcl9wcm9tcHRpbmcuTGlmZXNwYW5SCGxpZmVz'
+    'cGFuEiEKDHBhdGhfcGF0dGVybhgJIAEoCVILcGF0aFBhdHRlcm4SIAoLcGVybWlzc2lvbnMYCi'
+    'ADKAlSC3Blcm1pc3Npb25zEiIKCmxhdGVuY3lfbXMYCyABKARIAFIJbGF0ZW5jeU1ziAEBEkcK'
+    'B291dGNvbWUYDCABKA4yLS5hcHBhcm1vcl9wcm9tcHRpbmcuSGlzdG9yeUVudHJ5LlJlcGx5T3'
+    'V0Y29tZVIHb3V0Y29tZRIfCgtyZXNvbHZlZF9ieRgNIAEoCVIKcmVzb2x2ZWRCeSJbCgxIaXN0'
+    'b3J5RXZlbnQSDAoIUFJPTVBURUQQABINCglESVNQTEFZRUQQARILCgdSRVBMSUVEEAISCwoHRF'
+    'JPUFBFRBADEhQKEFJFU09MVkVEX0JZX1JVTEUQBCJiCgxSZXBseU91dGNvbWUSCwoHU1VDQ0VT'
+    'UxAAEg0KCU5PVF9GT1VORBABEhAKDFRJTUVPVVRfREVOWRACEhgKFE1BUFBJTkdfRkFJTFVSRV'
+    '9ERU5ZEAMSCgoGRkFJTEVEEARCDQoLX2xhdGVuY3lfbXM=');
+
@@ -29,6 +29,12 @@ class PromptingClient {
       .getCurrentPrompt(Empty())
       .then(PrompteDetailsConversion.fromProto);
 
+  /// Emits the prompt that should be shown each time that it changes, or null
+  /// when there is no prompt waiting for a reply.
+  Stream<PromptDetails?> watchPrompts() => _client
+      .watchPrompts(Empty())
+      .map(PrompteDetailsConversion.fromWatchProto);
+
   Future<PromptReplyResponse> replyToPrompt(PromptReply reply) => _client
       .replyToPrompt(reply.toProto())
       .then(PromptReplyResponseConversion.fromProto);
@@ -124,9 +130,22 @@ extension PrompteDetailsConversion on PromptDetails {
                 .toSet(),
             initialPatternOption: response.homePrompt.initialPatternOption,
           ),
+        pb.GetCurrentPromptResponse_Prompt.cameraPrompt ||
+        pb.GetCurrentPromptResponse_Prompt.audioRecordPrompt ||
+        pb.GetCurrentPromptResponse_Prompt.genericPrompt =>
+          throw ArgumentError(
+            'Unsupported prompt type: ${response.whichPrompt()}',
+          ),
         pb.GetCurrentPromptResponse_Prompt.notSet =>
           throw ArgumentError('Prompt type not set'),
       };
+
+  static PromptDetails? fromWatchProto(pb.WatchPromptsResponse response) =>
+      switch (response.status) {
+        pb.WatchPromptsResponse_PromptStatus.ACTIVE =>
+          fromProto(response.currentPrompt),
+        _ => null,
+      };
 }
 
 extension PromptReplyConversion on PromptReply {
@@ -101,6 +101,59 @@ void main() {
     }
   });
 
+  group('watch prompts', () {
+    final currentPrompt = pb.GetCurrentPromptResponse(
+      homePrompt: pb.HomePrompt(
+        metaData: pb.MetaData(promptId: 'promptId', snapName: 'snapName'),
+        requestedPath: '/home/user/Downloads/example.txt',
+        homeDir: '/home/user',
+        requestedPermissions: ['read'],
+      ),
+    );
+    final testCases = [
+      (
+        name: 'active prompt',
+        status: pb.WatchPromptsResponse_PromptStatus.ACTIVE,
+        expectPrompt: true,
+      ),
+      (
+        name: 'no prompt',
+        status: pb.WatchPromptsResponse_PromptStatus.NO_PROMPT,
+        expectPrompt: false,
+      ),
+      (
+        name: 'actioned prompt',
+        status: pb.WatchPromptsResponse_PromptStatus.ACTIONED,
+        expectPrompt: false,
+      ),
+      (
+        name: 'timed out prompt',
+        status: pb.WatchPromptsResponse_PromptStatus.TIMED_OUT,
+        expectPrompt: false,
+      ),
+    ];
+
+    for (final testCase in testCases) {
+      test(testCase.name, () {
+        final response = pb.WatchPromptsResponse(
+          status: testCase.status,
+          promptId: 'promptId',
+          currentPrompt: testCase.expectPrompt ? currentPrompt : null,
+        );
+
+        final details = PrompteDetailsConversion.fromWatchProto(response);
+        if (testCase.expectPrompt) {
+          expect(
+            details,
+            equals(PrompteDetailsConversion.fromProto(currentPrompt)),
+          );
+        } else {
+          expect(details, isNull);
+        }
+      });
+    }
+  });
+
   group('reply to prompt', () {
     final testCases = [
       (
@@ -1,6 +1,9 @@
 //! The daemon prompting client for apparmor prompting
 use prompting_client::{
-    daemon::run_daemon, log_filter, snapd_client::SnapdSocketClient, Result, DEFAULT_LOG_LEVEL,
+    daemon::{run_daemon, DaemonConfig},
+    log_filter,
+    snapd_client::SnapdSocketClient,
+    Result,
 };
 use std::{env, io::stdout};
 use tracing::subscriber::set_global_default;
@@ -8,8 +11,11 @@ use tracing_subscriber::{layer::SubscriberExt, FmtSubscriber};
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    // An invalid config is reported before anything else so that it is not silently ignored
+    let config = DaemonConfig::load()?;
+
     let builder = FmtSubscriber::builder()
-        .with_env_filter(log_filter(DEFAULT_LOG_LEVEL))
+        .with_env_filter(log_filter(&config.log_level))
         .with_writer(stdout)
         .with_filter_reloading();
 
@@ -31,5 +37,5 @@ async fn main() -> Result<()> {
         return Ok(());
     }
 
-    run_daemon(c, reload_handle).await
+    run_daemon(c, reload_handle, config).await
 }
@@ -0,0 +1,96 @@
+//! A command line tool for querying the history of prompts recorded by the daemon.
+use chrono::{DateTime, Utc};
+use clap::Parser;
+use prompting_client::{
+    cli_actions::{
+        list_history, parse_end_time, parse_time, EventKind, HistoryFilter, HistoryFormat,
+    },
+    daemon::History,
+    snapd_client::{path_pattern::PathPattern, Action, Lifespan},
+    Error, Result,
+};
+use std::{path::PathBuf, process::exit};
+
+/// Query the history of prompts seen by the prompting client daemon.
+///
+/// The daemon records each prompt it receives from snapd, when it was shown to the user and how
+/// it was replied to. This command allows for filtering that history in order to answer
+/// questions such as what a given snap has been allowed to access over a period of time.
+///
+/// Times can be given either as RFC 3339 timestamps or as YYYY-MM-DD dates. Dates passed to
+/// --until cover the whole of that day.
+#[derive(Debug, Parser)]
+#[clap(about, long_about = None)]
+struct Args {
+    /// Only show entries for the given snap
+    #[clap(short, long)]
+    snap: Option<String>,
+
+    /// Only show entries recorded at or after the given time
+    #[clap(long, value_parser = parse_time_arg)]
+    since: Option<DateTime<Utc>>,
+
+    /// Only show entries recorded at or before the given time
+    #[clap(long, value_parser = parse_end_time_arg)]
+    until: Option<DateTime<Utc>>,
+
+    /// Only show entries for the given event (prompted, displayed, replied, resolved-by-rule or
+    /// dropped)
+    #[clap(short, long)]
+    event: Option<EventKind>,
+
+    /// Only show replies with the given action (allow or deny)
+    #[clap(short, long)]
+    action: Option<Action>,
+
+    /// Only show replies with the given lifespan (single, session, timespan or forever)
+    #[clap(short, long)]
+    lifespan: Option<Lifespan>,
+
+    /// Only show entries for prompts requesting a path matching the provided glob
+    #[clap(short, long, value_name = "GLOB")]
+    path: Option<String>,
+
+    /// The output format to use (table, json or csv)
+    #[clap(short, long, default_value_t = HistoryFormat::Table)]
+    format: HistoryFormat,
+
+    /// The history file to read, defaulting to the one written by the daemon
+    #[clap(long, value_name = "PATH")]
+    file: Option<PathBuf>,
+}
+
+fn parse_time_arg(raw: &str) -> std::result::Result<DateTime<Utc>, String> {
+    parse_time(raw).map_err(|e| e.to_string())
+}
+
+fn parse_end_time_arg(raw: &str) -> std::result::Result<DateTime<Utc>, String> {
+    parse_end_time(raw).map_err(|e| e.to_string())
+}
+
+#[tokio::main]
+async fn main() {
+    if let Err(e) = run(Args::parse()).await {
+        eprintln!("{e}");
+        exit(1);
+    }
+}
+
+async fn run(args: Args) -> Result<()> {
+    let file = args
+        .file
+        .or_else(History::default_path)
+        .ok_or(Error::UnableToLocateHistory)?;
+
+    let filter = HistoryFilter {
+        snap: args.snap,
+        since: args.since,
+        until: args.until,
+        event: args.event,
+        action: args.action,
+        lifespan: args.lifespan,
+        path: args.path.map(PathPattern::parse).transpose()?,
+    };
+
+    list_history(&file, &filter, args.format).await
+}
@@ -0,0 +1,137 @@
+//! A command line tool for inspecting and managing the prompting rules stored by snapd.
+use clap::{Parser, Subcommand};
+use prompting_client::{
+    cli_actions::{edit_rule, list_rules, remove_rules, OutputFormat, RuleFilter},
+    snapd_client::{
+        duration::GoDuration, path_pattern::PathPattern, Action, Lifespan, RuleEdit, RuleId,
+        SnapdSocketClient,
+    },
+    Result,
+};
+use std::process::exit;
+
+/// Manage the rules created in response to replying to apparmor prompts.
+///
+/// Replies to prompts with a lifespan other than "single" create rules within snapd that are
+/// used to automatically handle future requests. This command allows for listing the current
+/// rules along with editing or removing rules that are no longer wanted.
+#[derive(Debug, Parser)]
+#[clap(about, long_about = None)]
+struct Args {
+    #[clap(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// List the current rules, optionally filtered by snap, interface, path or lifespan
+    List {
+        /// Only show rules for the given snap
+        #[clap(short, long)]
+        snap: Option<String>,
+
+        /// Only show rules for the given interface
+        #[clap(short, long)]
+        interface: Option<String>,
+
+        /// Only show rules with a path pattern matching the provided glob
+        #[clap(short, long, value_name = "GLOB")]
+        path: Option<String>,
+
+        /// Only show rules with the given lifespan (session, timespan or forever)
+        #[clap(short, long)]
+        lifespan: Option<Lifespan>,
+
+        /// The output format to use (table or json)
+        #[clap(short, long, default_value_t = OutputFormat::Table)]
+        format: OutputFormat,
+    },
+
+    /// Remove one or more rules
+    Remove {
+        /// The IDs of the rules to remove
+        #[clap(required = true)]
+        ids: Vec<String>,
+    },
+
+    /// Edit an existing rule
+    Edit {
+        /// The ID of the rule to edit
+        id: String,
+
+        /// The new outcome for the rule (allow or deny)
+        #[clap(short, long)]
+        outcome: Option<Action>,
+
+        /// The new lifespan for the rule (session, timespan or forever)
+        #[clap(short, long)]
+        lifespan: Option<Lifespan>,
+
+        /// The duration to use for a timespan lifespan in the format accepted by snapd (e.g. 10m)
+        #[clap(short, long, requires = "lifespan")]
+        duration: Option<GoDuration>,
+
+        /// The new path pattern for the rule
+        #[clap(short, long)]
+        path_pattern: Option<String>,
+
+        /// The new permissions for the rule
+        #[clap(long, value_delimiter = ',')]
+        permissions: Option<Vec<String>>,
+    },
+}
+
+#[tokio::main]
+async fn main() {
+    let Args { command } = Args::parse();
+
+    if let Err(e) = run(command).await {
+        eprintln!("{e}");
+        exit(1);
+    }
+}
+
+async fn run(command: Command) -> Result<()> {
+    let c = SnapdSocketClient::default();
+    c.exit_if_prompting_not_enabled().await?;
+
+    match command {
+        Command::List {
+            snap,
+            interface,
+            path,
+            lifespan,
+            format,
+        } => {
+            let filter = RuleFilter {
+                snap,
+                interface,
+                path: path.map(PathPattern::parse).transpose()?,
+                lifespan,
+            };
+
+            list_rules(&c, &filter, format).await
+        }
+
+        Command::Remove { ids } => remove_rules(&c, ids.into_iter().map(RuleId).collect()).await,
+
+        Command::Edit {
+            id,
+            outcome,
+            lifespan,
+            duration,
+            path_pattern,
+            permissions,
+        } => {
+            let edit = RuleEdit {
+                outcome,
+                lifespan,
+                duration,
+                path_pattern,
+                permissions,
+            };
+
+            edit_rule(&c, RuleId(id), edit).await
+        }
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    daemon::{PollLoop, PromptUpdate},
+    daemon::{DaemonConfig, PollLoop, PromptUpdate},
     recording::PromptRecording,
     snapd_client::{PromptId, SnapdSocketClient, TypedPrompt},
     Result,
@@ -16,7 +16,11 @@ pub async fn run_echo_loop(
     let mut rec = PromptRecording::new(path);
 
     info!("starting poll loop");
-    let mut poll_loop = PollLoop::new(snapd_client.clone(), tx_prompts);
+    let mut poll_loop = PollLoop::new(
+        snapd_client.clone(),
+        tx_prompts,
+        DaemonConfig::default().fixed(),
+    );
     poll_loop.skip_outstanding_prompts();
     tokio::spawn(async move { poll_loop.run().await });
 
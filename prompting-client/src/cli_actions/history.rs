@@ -0,0 +1,400 @@
+use crate::{
+    cli_actions::format_table,
+    daemon::{read_history, HistoryEntry, HistoryEvent},
+    snapd_client::{path_pattern::PathPattern, Action, Lifespan, PromptId},
+    Error, Result,
+};
+use chrono::{DateTime, NaiveDate, NaiveTime, SecondsFormat, Utc};
+use std::{collections::HashMap, path::Path};
+use strum::{Display, EnumString};
+
+/// How history entries should be written to standard out.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum HistoryFormat {
+    #[default]
+    Table,
+    Json,
+    Csv,
+}
+
+/// The kinds of event recorded in the prompt history.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "kebab-case")]
+pub enum EventKind {
+    Prompted,
+    Displayed,
+    Replied,
+    ResolvedByRule,
+    Dropped,
+}
+
+impl EventKind {
+    fn of(event: &HistoryEvent) -> Self {
+        match event {
+            HistoryEvent::Prompted { .. } => Self::Prompted,
+            HistoryEvent::Displayed => Self::Displayed,
+            HistoryEvent::Replied { .. } => Self::Replied,
+            HistoryEvent::ResolvedByRule { .. } => Self::ResolvedByRule,
+            HistoryEvent::Dropped => Self::Dropped,
+        }
+    }
+}
+
+/// Restrict the history being listed to only those entries matching all of the provided fields.
+///
+/// Filtering on the action or lifespan only matches replies. Filtering on the path matches all
+/// entries for prompts whose requested path matches the glob.
+#[derive(Debug, Default, Clone)]
+pub struct HistoryFilter {
+    pub snap: Option<String>,
+    pub since: Option<DateTime<Utc>>,
+    pub until: Option<DateTime<Utc>>,
+    pub event: Option<EventKind>,
+    pub action: Option<Action>,
+    pub lifespan: Option<Lifespan>,
+    pub path: Option<PathPattern>,
+}
+
+impl HistoryFilter {
+    fn matches(&self, entry: &HistoryEntry, requested_path: Option<&str>) -> bool {
+        if matches!(&self.snap, Some(snap) if snap != &entry.snap) {
+            return false;
+        }
+        if matches!(self.event, Some(event) if event != EventKind::of(&entry.event)) {
+            return false;
+        }
+
+        if matches!(self.since, Some(since) if entry.timestamp < since) {
+            return false;
+        }
+        if matches!(self.until, Some(until) if entry.timestamp > until) {
+            return false;
+        }
+
+        if self.action.is_some() || self.lifespan.is_some() {
+            let HistoryEvent::Replied {
+                action, lifespan, ..
+            } = &entry.event
+            else {
+                return false;
+            };
+            if matches!(self.action, Some(a) if a != *action) {
+                return false;
+            }
+            if matches!(self.lifespan, Some(l) if l != *lifespan) {
+                return false;
+            }
+        }
+
+        match (&self.path, requested_path) {
+            (Some(glob), Some(path)) => glob.matches(path),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+/// The path pattern that was replied with, or the requested path for prompts.
+fn entry_path(entry: &HistoryEntry) -> Option<&str> {
+    match &entry.event {
+        HistoryEvent::Replied { path_pattern, .. } => path_pattern.as_deref(),
+        HistoryEvent::Prompted { constraints } => constraints["path"].as_str(),
+        _ => None,
+    }
+}
+
+/// The path requested by each of the prompts recorded in the history. Reply path patterns are
+/// globs themselves so entries are filtered on the path requested by their prompt instead.
+fn requested_paths(entries: &[HistoryEntry]) -> HashMap<&PromptId, &str> {
+    entries
+        .iter()
+        .filter_map(|e| match &e.event {
+            HistoryEvent::Prompted { constraints } => Some((&e.id, constraints["path"].as_str()?)),
+            _ => None,
+        })
+        .collect()
+}
+
+/// Parse a point in time from either an RFC 3339 timestamp or a YYYY-MM-DD date (taken as
+/// the start of that day in UTC).
+pub fn parse_time(raw: &str) -> Result<DateTime<Utc>> {
+    parse_time_or_date(raw, NaiveTime::MIN)
+}
+
+/// Parse the end of a time range from either an RFC 3339 timestamp or a YYYY-MM-DD date (taken
+/// as the end of that day in UTC) so that a date covers the whole day.
+pub fn parse_end_time(raw: &str) -> Result<DateTime<Utc>> {
+    let end_of_day = NaiveTime::from_hms_nano_opt(23, 59, 59, 999_999_999).expect("valid time");
+
+    parse_time_or_date(raw, end_of_day)
+}
+
+fn parse_time_or_date(raw: &str, time_of_day: NaiveTime) -> Result<DateTime<Utc>> {
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Ok(dt.to_utc());
+    }
+
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map(|d| d.and_time(time_of_day).and_utc())
+        .map_err(|_| Error::InvalidTimestamp {
+            raw: raw.to_string(),
+        })
+}
+
+/// List the entries in the prompt history file that match the provided filter.
+pub async fn list_history(
+    path: &Path,
+    filter: &HistoryFilter,
+    format: HistoryFormat,
+) -> Result<()> {
+    let all_entries = read_history(path).await?;
+    let paths = requested_paths(&all_entries);
+    let entries: Vec<HistoryEntry> = all_entries
+        .iter()
+        .filter(|e| filter.matches(e, paths.get(&e.id).copied()))
+        .cloned()
+        .collect();
+
+    match format {
+        HistoryFormat::Json => println!("{}", serde_json::to_string_pretty(&entries)?),
+        HistoryFormat::Table => print!("{}", format_table(HEADER, &history_rows(&entries))),
+        HistoryFormat::Csv => print!("{}", history_csv(&entries)),
+    }
+
+    Ok(())
+}
+
+const HEADER: [&str; 10] = [
+    "TIMESTAMP",
+    "ID",
+    "SNAP",
+    "INTERFACE",
+    "EVENT",
+    "ACTION",
+    "LIFESPAN",
+    "OUTCOME",
+    "PERMISSIONS",
+    "PATH",
+];
+
+fn history_rows(entries: &[HistoryEntry]) -> Vec<[String; 10]> {
+    entries
+        .iter()
+        .map(|e| {
+            let (action, lifespan, outcome, permissions) = match &e.event {
+                HistoryEvent::Replied {
+                    action,
+                    lifespan,
+                    permissions,
+                    outcome,
+                    ..
+                } => (
+                    action.to_string(),
+                    lifespan.to_string(),
+                    outcome.to_string(),
+                    permissions.join(","),
+                ),
+                _ => Default::default(),
+            };
+            let or_dash = |s: String| if s.is_empty() { "-".to_string() } else { s };
+
+            [
+                e.timestamp.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+                e.id.0.clone(),
+                e.snap.clone(),
+                e.interface.clone(),
+                EventKind::of(&e.event).to_string(),
+                or_dash(action),
+                or_dash(lifespan),
+                or_dash(outcome),
+                or_dash(permissions),
+                entry_path(e).unwrap_or("-").to_string(),
+            ]
+        })
+        .collect()
+}
+
+fn history_csv(entries: &[HistoryEntry]) -> String {
+    let escape = |field: &str| {
+        if field.contains([',', '"', '\n', '\r']) {
+            format!("\"{}\"", field.replace('"', "\"\""))
+        } else {
+            field.to_string()
+        }
+    };
+
+    let mut s = HEADER.map(|h| h.to_lowercase()).join(",");
+    s.push('\n');
+    for row in history_rows(entries) {
+        let fields: Vec<String> = row
+            .iter()
+            .map(|f| if f == "-" { String::new() } else { escape(f) })
+            .collect();
+        s.push_str(&fields.join(","));
+        s.push('\n');
+    }
+
+    s
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{daemon::ReplyOutcome, snapd_client::PromptId};
+    use simple_test_case::test_case;
+
+    fn entry(ts: &str, id: &str, snap: &str, event: HistoryEvent) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: ts.parse().unwrap(),
+            id: PromptId(id.to_string()),
+            snap: snap.to_string(),
+            interface: "home".to_string(),
+            event,
+        }
+    }
+
+    fn replied(action: Action, lifespan: Lifespan, path_pattern: &str) -> HistoryEvent {
+        HistoryEvent::Replied {
+            action,
+            lifespan,
+            path_pattern: Some(path_pattern.to_string()),
+            permissions: vec!["read".to_string(), "write".to_string()],
+            latency_ms: Some(1200),
+            outcome: ReplyOutcome::Success,
+        }
+    }
+
+    fn entries() -> Vec<HistoryEntry> {
+        vec![
+            entry(
+                "2024-07-31T10:00:00Z",
+                "1",
+                "firefox",
+                HistoryEvent::Prompted {
+                    constraints: serde_json::json!({ "path": "/home/user/Downloads/a.pdf" }),
+                },
+            ),
+            entry(
+                "2024-07-31T10:00:05Z",
+                "1",
+                "firefox",
+                replied(Action::Allow, Lifespan::Forever, "/home/user/Downloads/**"),
+            ),
+            entry(
+                "2024-08-02T09:00:00Z",
+                "2",
+                "cheese",
+                replied(Action::Deny, Lifespan::Single, "/home/user/Pictures/b.png"),
+            ),
+            entry(
+                "2024-08-03T09:00:00Z",
+                "3",
+                "firefox",
+                HistoryEvent::Dropped,
+            ),
+        ]
+    }
+
+    #[test_case(HistoryFilter::default(), &["1", "1", "2", "3"]; "no filter")]
+    #[test_case(HistoryFilter { snap: Some("firefox".into()), ..Default::default() }, &["1", "1", "3"]; "snap")]
+    #[test_case(HistoryFilter { since: Some(parse_time("2024-08-01").unwrap()), ..Default::default() }, &["2", "3"]; "since")]
+    #[test_case(HistoryFilter { until: Some(parse_time("2024-07-31T10:00:01Z").unwrap()), ..Default::default() }, &["1"]; "until")]
+    #[test_case(HistoryFilter { event: Some(EventKind::Replied), ..Default::default() }, &["1", "2"]; "event")]
+    #[test_case(HistoryFilter { action: Some(Action::Allow), ..Default::default() }, &["1"]; "action")]
+    #[test_case(HistoryFilter { lifespan: Some(Lifespan::Single), ..Default::default() }, &["2"]; "lifespan")]
+    #[test_case(HistoryFilter { path: Some(PathPattern::parse("/home/*/Downloads/**").unwrap()), ..Default::default() }, &["1", "1"]; "path")]
+    #[test_case(HistoryFilter { path: Some(PathPattern::parse("/home/user/Downloads/a.pdf").unwrap()), ..Default::default() }, &["1", "1"]; "path covered by reply pattern")]
+    #[test_case(HistoryFilter { path: Some(PathPattern::parse("/home/user/Downloads/\\*\\*").unwrap()), ..Default::default() }, &[]; "reply pattern is not matched literally")]
+    #[test_case(HistoryFilter { until: Some(parse_end_time("2024-07-31").unwrap()), ..Default::default() }, &["1", "1"]; "until date")]
+    #[test_case(
+        HistoryFilter {
+            snap: Some("firefox".into()),
+            since: Some(parse_time("2024-07-01").unwrap()),
+            until: Some(parse_time("2024-08-01").unwrap()),
+            action: Some(Action::Allow),
+            ..Default::default()
+        },
+        &["1"];
+        "allowed for a snap this month"
+    )]
+    #[test]
+    fn history_filter_works(filter: HistoryFilter, expected: &[&str]) {
+        let entries = entries();
+        let paths = requested_paths(&entries);
+        let ids: Vec<&str> = entries
+            .iter()
+            .filter(|e| filter.matches(e, paths.get(&e.id).copied()))
+            .map(|e| e.id.0.as_str())
+            .collect();
+
+        assert_eq!(ids, expected);
+    }
+
+    #[test_case("2024-08-01T12:30:00Z", "2024-08-01T12:30:00Z"; "rfc3339")]
+    #[test_case("2024-08-01T12:30:00+02:00", "2024-08-01T10:30:00Z"; "rfc3339 with offset")]
+    #[test_case("2024-08-01", "2024-08-01T00:00:00Z"; "date")]
+    #[test]
+    fn parse_time_works(raw: &str, expected: &str) {
+        let expected: DateTime<Utc> = expected.parse().unwrap();
+
+        assert_eq!(parse_time(raw).unwrap(), expected);
+    }
+
+    #[test_case("2024-08-01T12:30:00Z", "2024-08-01T12:30:00Z"; "rfc3339")]
+    #[test_case("2024-08-01", "2024-08-01T23:59:59.999999999Z"; "date")]
+    #[test]
+    fn parse_end_time_works(raw: &str, expected: &str) {
+        let expected: DateTime<Utc> = expected.parse().unwrap();
+
+        assert_eq!(parse_end_time(raw).unwrap(), expected);
+    }
+
+    #[test_case("yesterday"; "word")]
+    #[test_case("2024-13-01"; "invalid month")]
+    #[test]
+    fn parse_time_errors_for_invalid_input(raw: &str) {
+        match parse_time(raw) {
+            Err(Error::InvalidTimestamp { .. }) => (),
+            res => panic!("expected InvalidTimestamp, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn history_table_works() {
+        let expected = "\
+TIMESTAMP             ID  SNAP     INTERFACE  EVENT     ACTION  LIFESPAN  OUTCOME  PERMISSIONS  PATH
+2024-07-31T10:00:00Z  1   firefox  home       prompted  -       -         -        -            /home/user/Downloads/a.pdf
+2024-07-31T10:00:05Z  1   firefox  home       replied   allow   forever   success  read,write   /home/user/Downloads/**
+2024-08-02T09:00:00Z  2   cheese   home       replied   deny    single    success  read,write   /home/user/Pictures/b.png
+2024-08-03T09:00:00Z  3   firefox  home       dropped   -       -         -        -            -
+";
+
+        assert_eq!(format_table(HEADER, &history_rows(&entries())), expected);
+    }
+
+    #[test]
+    fn history_csv_works() {
+        let expected = "\
+timestamp,id,snap,interface,event,action,lifespan,outcome,permissions,path
+2024-07-31T10:00:00Z,1,firefox,home,prompted,,,,,/home/user/Downloads/a.pdf
+2024-07-31T10:00:05Z,1,firefox,home,replied,allow,forever,success,\"read,write\",/home/user/Downloads/**
+2024-08-02T09:00:00Z,2,cheese,home,replied,deny,single,success,\"read,write\",/home/user/Pictures/b.png
+2024-08-03T09:00:00Z,3,firefox,home,dropped,,,,,
+";
+
+        assert_eq!(history_csv(&entries()), expected);
+    }
+
+    #[test]
+    fn history_csv_quotes_carriage_returns() {
+        let entries = vec![entry(
+            "2024-08-03T09:00:00Z",
+            "3",
+            "fire\rfox",
+            HistoryEvent::Dropped,
+        )];
+
+        assert!(history_csv(&entries).contains(",\"fire\rfox\","));
+    }
+}
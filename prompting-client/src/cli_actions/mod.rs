@@ -1,7 +1,37 @@
 mod echo_loop;
+mod history;
 mod log_level;
+mod rules;
 mod scripted;
 
 pub use echo_loop::run_echo_loop;
+pub use history::{
+    list_history, parse_end_time, parse_time, EventKind, HistoryFilter, HistoryFormat,
+};
 pub use log_level::set_logging_filter;
+pub use rules::{edit_rule, list_rules, remove_rules, OutputFormat, RuleFilter};
 pub use scripted::ScriptedClient;
+
+/// Render rows as left aligned columns separated by two spaces, with trailing whitespace trimmed.
+fn format_table<const N: usize>(header: [&str; N], rows: &[[String; N]]) -> String {
+    let header = header.map(String::from);
+    let mut widths = header.clone().map(|s| s.len());
+    for row in rows.iter() {
+        for (w, col) in widths.iter_mut().zip(row.iter()) {
+            *w = (*w).max(col.len());
+        }
+    }
+
+    let mut s = String::new();
+    for row in std::iter::once(&header).chain(rows.iter()) {
+        let line: Vec<String> = row
+            .iter()
+            .zip(widths.iter())
+            .map(|(col, &w)| format!("{col:w$}"))
+            .collect();
+        s.push_str(line.join("  ").trim_end());
+        s.push('\n');
+    }
+
+    s
+}
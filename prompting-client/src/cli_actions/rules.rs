@@ -0,0 +1,182 @@
+use crate::{
+    cli_actions::format_table,
+    snapd_client::{
+        path_pattern::PathPattern, Lifespan, RuleEdit, RuleId, SnapdSocketClient, TypedRule,
+    },
+    Result,
+};
+use chrono::SecondsFormat;
+use strum::{Display, EnumString};
+
+/// How rules should be written to standard out.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumString)]
+#[strum(serialize_all = "lowercase")]
+pub enum OutputFormat {
+    #[default]
+    Table,
+    Json,
+}
+
+/// Restrict the rules being listed to only those matching all of the provided fields.
+#[derive(Debug, Default, Clone)]
+pub struct RuleFilter {
+    pub snap: Option<String>,
+    pub interface: Option<String>,
+    pub path: Option<PathPattern>,
+    pub lifespan: Option<Lifespan>,
+}
+
+impl RuleFilter {
+    fn matches(&self, rule: &TypedRule) -> bool {
+        if matches!(&self.snap, Some(snap) if snap != rule.snap()) {
+            return false;
+        }
+        if matches!(&self.interface, Some(interface) if interface != rule.interface()) {
+            return false;
+        }
+        if matches!(self.lifespan, Some(lifespan) if lifespan != rule.lifespan()) {
+            return false;
+        }
+
+        // Rule path patterns are themselves globs so we match against the literal pattern text
+        match (&self.path, rule.path_pattern()) {
+            (Some(glob), Some(pattern)) => glob.matches(pattern),
+            (Some(_), None) => false,
+            (None, _) => true,
+        }
+    }
+}
+
+/// List the rules currently stored by snapd that match the provided filter.
+pub async fn list_rules(
+    snapd_client: &SnapdSocketClient,
+    filter: &RuleFilter,
+    format: OutputFormat,
+) -> Result<()> {
+    let rules: Vec<TypedRule> = snapd_client
+        .list_rules(filter.snap.as_deref(), filter.interface.as_deref())
+        .await?
+        .into_iter()
+        .filter(|r| filter.matches(r))
+        .collect();
+
+    match format {
+        OutputFormat::Json => println!("{}", serde_json::to_string_pretty(&rules)?),
+        OutputFormat::Table => print!("{}", rules_table(&rules)),
+    }
+
+    Ok(())
+}
+
+/// Remove each of the specified rules, stopping at the first error.
+pub async fn remove_rules(snapd_client: &SnapdSocketClient, ids: Vec<RuleId>) -> Result<()> {
+    for id in ids {
+        let rule = snapd_client.remove_rule(&id).await?;
+        println!("removed rule {}", rule.id().0);
+    }
+
+    Ok(())
+}
+
+/// Apply the requested changes to an existing rule.
+pub async fn edit_rule(snapd_client: &SnapdSocketClient, id: RuleId, edit: RuleEdit) -> Result<()> {
+    let updated = snapd_client.edit_rule(&id, edit).await?;
+    print!("{}", rules_table(&[updated]));
+
+    Ok(())
+}
+
+fn rules_table(rules: &[TypedRule]) -> String {
+    let header = [
+        "ID",
+        "SNAP",
+        "INTERFACE",
+        "OUTCOME",
+        "LIFESPAN",
+        "EXPIRES",
+        "PERMISSIONS",
+        "PATH PATTERN",
+    ];
+
+    let rows: Vec<[String; 8]> = rules
+        .iter()
+        .map(|r| {
+            [
+                r.id().0.clone(),
+                r.snap().to_string(),
+                r.interface().to_string(),
+                r.outcome().to_string(),
+                r.lifespan().to_string(),
+                r.expiration()
+                    .map(|e| e.to_rfc3339_opts(SecondsFormat::AutoSi, true))
+                    .unwrap_or_else(|| "-".to_string()),
+                r.permissions().join(","),
+                r.path_pattern().unwrap_or("-").to_string(),
+            ]
+        })
+        .collect();
+
+    format_table(header, &rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple_test_case::test_case;
+
+    fn rules() -> Vec<TypedRule> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "id": "0000000000000002",
+                "timestamp": "2024-08-14T07:28:22.694800024Z",
+                "snap": "firefox",
+                "interface": "home",
+                "constraints": {
+                    "path-pattern": "/home/ubuntu/Downloads/**",
+                    "permissions": ["read", "write"]
+                },
+                "outcome": "allow",
+                "lifespan": "forever"
+            },
+            {
+                "id": "0000000000000003",
+                "timestamp": "2024-08-14T07:30:22.694800024Z",
+                "snap": "cheese",
+                "interface": "camera",
+                "constraints": { "permissions": ["access"] },
+                "outcome": "deny",
+                "lifespan": "timespan",
+                "expiration": "2024-08-14T08:30:22.694800024Z"
+            }
+        ]))
+        .unwrap()
+    }
+
+    #[test_case(RuleFilter::default(), &["0000000000000002", "0000000000000003"]; "no filter")]
+    #[test_case(RuleFilter { snap: Some("cheese".into()), ..Default::default() }, &["0000000000000003"]; "snap")]
+    #[test_case(RuleFilter { interface: Some("home".into()), ..Default::default() }, &["0000000000000002"]; "interface")]
+    #[test_case(RuleFilter { lifespan: Some(Lifespan::Forever), ..Default::default() }, &["0000000000000002"]; "lifespan")]
+    #[test_case(RuleFilter { path: Some(PathPattern::parse("/home/*/Downloads/**").unwrap()), ..Default::default() }, &["0000000000000002"]; "matching path")]
+    #[test_case(RuleFilter { path: Some(PathPattern::parse("/home/*/Pictures/**").unwrap()), ..Default::default() }, &[]; "non matching path")]
+    #[test]
+    fn rule_filter_works(filter: RuleFilter, expected: &[&str]) {
+        let ids: Vec<String> = rules()
+            .into_iter()
+            .filter(|r| filter.matches(r))
+            .map(|r| r.id().0.clone())
+            .collect();
+
+        assert_eq!(ids, expected);
+    }
+
+    #[test]
+    fn rules_table_works() {
+        let expected = "\
+ID                SNAP     INTERFACE  OUTCOME  LIFESPAN  EXPIRES                         PERMISSIONS  PATH PATTERN
+0000000000000002  firefox  home       allow    forever   -                               read,write   /home/ubuntu/Downloads/**
+0000000000000003  cheese   camera     deny     timespan  2024-08-14T08:30:22.694800024Z  access       -
+";
+
+        assert_eq!(rules_table(&rules()), expected);
+    }
+}
@@ -1,5 +1,5 @@
 use crate::{
-    daemon::{EnrichedPrompt, PollLoop, PromptUpdate},
+    daemon::{DaemonConfig, EnrichedPrompt, PollLoop, PromptUpdate},
     prompt_sequence::{MatchError, PromptFilter, PromptSequence},
     snapd_client::{
         interfaces::{
@@ -82,7 +82,7 @@ impl ScriptedClient {
                         Ok(TypedPrompt::Home(inner)) if filter.matches(&inner).is_success() => {
                             debug!("allowing read of script file");
                             let reply = HomeInterface::prompt_to_reply(inner, Action::Allow)
-                                .for_timespan("10s") // Using a timespan so our rule auto-removes
+                                .for_timespan(Duration::from_secs(10)) // Using a timespan so our rule auto-removes
                                 .into();
                             snapd_client.reply_to_prompt(&id, reply).await.unwrap();
                             return;
@@ -113,7 +113,11 @@ impl ScriptedClient {
         let (tx_prompts, mut rx_prompts) = unbounded_channel();
 
         info!("starting poll loop");
-        let mut poll_loop = PollLoop::new(snapd_client.clone(), tx_prompts);
+        let mut poll_loop = PollLoop::new(
+            snapd_client.clone(),
+            tx_prompts,
+            DaemonConfig::default().fixed(),
+        );
         poll_loop.skip_outstanding_prompts();
         tokio::spawn(async move { poll_loop.run().await });
 
@@ -161,11 +165,16 @@ impl ScriptedClient {
             TypedPrompt::Home(inner) if inner.constraints.path == self.path => {
                 Ok(TypedPromptReply::Home(
                     // Using a timespan so our rule auto-removes
-                    HomeInterface::prompt_to_reply(inner, Action::Allow).for_timespan("10s"),
+                    HomeInterface::prompt_to_reply(inner, Action::Allow)
+                        .for_timespan(Duration::from_secs(10)),
                 ))
             }
 
             _ => match self.seq.try_match_next(prompt) {
+                Ok(TypedPromptReply::Home(reply)) => {
+                    reply.validate_path_pattern()?;
+                    Ok(TypedPromptReply::Home(reply))
+                }
                 Ok(reply) => Ok(reply),
                 Err(error) => Err(Error::FailedPromptSequence { error }),
             },
@@ -0,0 +1,368 @@
+//! Configuration for the daemon, loaded from a TOML file at startup and reloaded on SIGHUP.
+//!
+//! All fields are optional, with any that are not provided in the config file taking their
+//! default values:
+//!
+//! ```toml
+//! log-level = "info"
+//! persistent-ui = false
+//! ui-command = "/snap/prompting-client/current/bin/prompting_client_ui"
+//! recv-timeout-ms = 200
+//! persistent-ui-recv-timeout-secs = 3600
+//! max-poll-retries = 3
+//! retry-sleep-ms = 200
+//! max-retry-sleep-ms = 30000
+//! long-poll-timeout-secs = 3600
+//! snap-meta-cache-secs = 3600
+//! history-max-bytes = 1048576
+//! history-max-files = 5
+//!
+//! [prompt-timeout]
+//! timeout-secs = 300
+//! policy = "deny-once"
+//! ```
+//!
+//! The `persistent-ui` and `ui-command` fields are only read at startup: changes to all other
+//! fields are picked up when the config is reloaded. Enabling `persistent-ui` requires setting a
+//! `ui-command` for a UI that watches for prompts (for example using `PromptingClient.watchPrompts`
+//! from the `prompting_client` Dart package) as the bundled UI still expects to be spawned for
+//! each prompt.
+use crate::{
+    daemon::{
+        server::SetLogFilter,
+        worker::{PromptTimeout, TimeoutPolicy},
+    },
+    log_filter, Error, Result, DEFAULT_LOG_LEVEL,
+};
+use serde::{Deserialize, Serialize};
+use std::{
+    env, fs, io,
+    path::{Path, PathBuf},
+    time::Duration,
+};
+use tokio::{
+    signal::unix::{signal, SignalKind},
+    sync::watch,
+};
+use tracing::{error, info, warn};
+use tracing_subscriber::EnvFilter;
+
+const CONFIG_FILE: &str = "config.toml";
+
+/// A receiver that is notified each time that the daemon config is reloaded.
+pub type ConfigHandle = watch::Receiver<DaemonConfig>;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields, rename_all = "kebab-case")]
+pub struct DaemonConfig {
+    /// The logging filter to use for the daemon.
+    pub log_level: String,
+    /// Keep a single instance of the UI running rather than spawning one for each prompt. This
+    /// requires a `ui-command` for a UI that subscribes to prompts using WatchPrompts: the UI
+    /// bundled in the snap only requests the current prompt when it starts.
+    pub persistent_ui: bool,
+    /// The UI command to run, defaulting to the UI bundled in the snap.
+    pub ui_command: Option<String>,
+    /// How long to wait for a reply when the UI is spawned for each prompt.
+    pub recv_timeout_ms: u64,
+    /// How long to wait for a reply when running the persistent UI.
+    pub persistent_ui_recv_timeout_secs: u64,
+    /// The number of consecutive failed attempts at establishing the notices long poll while
+    /// snapd is reachable before shutting down. Attempts made while snapd itself is down (for
+    /// example while it is being refreshed) are not counted.
+    pub max_poll_retries: usize,
+    /// The initial delay between attempts at establishing the notices long poll, doubling with
+    /// each failed attempt.
+    pub retry_sleep_ms: u64,
+    /// The maximum delay between attempts at establishing the notices long poll.
+    pub max_retry_sleep_ms: u64,
+    /// The timeout to request from snapd when long polling for notices.
+    pub long_poll_timeout_secs: u64,
+    /// How long to cache snap meta-data for before pulling it from snapd again.
+    pub snap_meta_cache_secs: u64,
+    /// The size at which the prompt history file is rotated.
+    pub history_max_bytes: u64,
+    /// The number of rotated prompt history files to keep.
+    pub history_max_files: usize,
+    /// An optional overall deadline for the user to reply to a prompt.
+    pub prompt_timeout: Option<PromptTimeoutConfig>,
+}
+
+impl Default for DaemonConfig {
+    fn default() -> Self {
+        Self {
+            log_level: DEFAULT_LOG_LEVEL.to_string(),
+            persistent_ui: false,
+            ui_command: None,
+            recv_timeout_ms: 200,
+            persistent_ui_recv_timeout_secs: 60 * 60,
+            max_poll_retries: 3,
+            retry_sleep_ms: 200,
+            max_retry_sleep_ms: 30 * 1000,
+            long_poll_timeout_secs: 60 * 60,
+            snap_meta_cache_secs: 60 * 60,
+            history_max_bytes: 1024 * 1024,
+            history_max_files: 5,
+            prompt_timeout: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields, rename_all = "kebab-case")]
+pub struct PromptTimeoutConfig {
+    pub timeout_secs: u64,
+    #[serde(default)]
+    pub policy: TimeoutPolicy,
+}
+
+impl DaemonConfig {
+    /// The location of the config file: `$SNAP_USER_DATA/config.toml` when running as a snap and
+    /// `$XDG_CONFIG_HOME/prompting-client/config.toml` otherwise.
+    pub fn path() -> Option<PathBuf> {
+        if let Ok(dir) = env::var("SNAP_USER_DATA") {
+            return Some(Path::new(&dir).join(CONFIG_FILE));
+        }
+
+        let config_dir = match env::var("XDG_CONFIG_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => Path::new(&env::var("HOME").ok()?).join(".config"),
+        };
+
+        Some(config_dir.join(crate::SNAP_NAME).join(CONFIG_FILE))
+    }
+
+    /// Load and validate the config file, falling back to the default config if there is no
+    /// config file present.
+    pub fn load() -> Result<Self> {
+        match Self::path() {
+            Some(path) => Self::load_from(&path),
+            None => Ok(Self::default()),
+        }
+    }
+
+    pub fn load_from(path: &Path) -> Result<Self> {
+        match fs::read_to_string(path) {
+            Ok(raw) => Self::parse(&raw),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Self::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    pub fn parse(raw: &str) -> Result<Self> {
+        let config: Self = toml::from_str(raw).map_err(|e| Error::InvalidConfig {
+            reason: e.message().to_string(),
+        })?;
+        config.validate()?;
+
+        Ok(config)
+    }
+
+    fn validate(&self) -> Result<()> {
+        let invalid = |reason: &str| {
+            Err(Error::InvalidConfig {
+                reason: reason.to_string(),
+            })
+        };
+
+        if log_filter(&self.log_level).parse::<EnvFilter>().is_err() {
+            return invalid(&format!("{:?} is not a valid log level", self.log_level));
+        }
+        if matches!(&self.ui_command, Some(cmd) if cmd.trim().is_empty()) {
+            return invalid("ui-command must not be empty");
+        }
+        if self.persistent_ui && self.ui_command.is_none() {
+            return invalid("persistent-ui requires a ui-command that watches for prompts");
+        }
+        if self.recv_timeout_ms == 0 || self.persistent_ui_recv_timeout_secs == 0 {
+            return invalid("recv timeouts must be greater than zero");
+        }
+        if self.retry_sleep_ms == 0 {
+            return invalid("retry-sleep-ms must be greater than zero");
+        }
+        if self.max_retry_sleep_ms < self.retry_sleep_ms {
+            return invalid("max-retry-sleep-ms must not be less than retry-sleep-ms");
+        }
+        if self.long_poll_timeout_secs == 0 {
+            return invalid("long-poll-timeout-secs must be greater than zero");
+        }
+        if self.history_max_bytes == 0 {
+            return invalid("history-max-bytes must be greater than zero");
+        }
+        if matches!(self.prompt_timeout, Some(t) if t.timeout_secs == 0) {
+            return invalid("prompt-timeout.timeout-secs must be greater than zero");
+        }
+
+        Ok(())
+    }
+
+    /// Wrap a config that will never be reloaded in a [ConfigHandle].
+    pub fn fixed(self) -> ConfigHandle {
+        watch::channel(self).1
+    }
+
+    pub fn ui_command(&self) -> String {
+        match &self.ui_command {
+            Some(cmd) => cmd.clone(),
+            None => {
+                let snap = env::var("SNAP").expect("SNAP env var to be set");
+                format!("{snap}/bin/prompting_client_ui")
+            }
+        }
+    }
+
+    pub fn recv_timeout(&self) -> Duration {
+        Duration::from_millis(self.recv_timeout_ms)
+    }
+
+    pub fn persistent_ui_recv_timeout(&self) -> Duration {
+        Duration::from_secs(self.persistent_ui_recv_timeout_secs)
+    }
+
+    pub fn retry_sleep(&self) -> Duration {
+        Duration::from_millis(self.retry_sleep_ms)
+    }
+
+    pub fn max_retry_sleep(&self) -> Duration {
+        Duration::from_millis(self.max_retry_sleep_ms)
+    }
+
+    pub fn long_poll_timeout(&self) -> Duration {
+        Duration::from_secs(self.long_poll_timeout_secs)
+    }
+
+    pub fn prompt_timeout(&self) -> Option<PromptTimeout> {
+        self.prompt_timeout.map(|t| PromptTimeout {
+            timeout: Duration::from_secs(t.timeout_secs),
+            policy: t.policy,
+        })
+    }
+}
+
+/// Reload the config file each time that we receive a SIGHUP (runs as a top level task).
+///
+/// Invalid configs are logged and ignored so that we keep running with the previous config.
+pub(crate) async fn reload_on_sighup(
+    tx: watch::Sender<DaemonConfig>,
+    log_filter_handle: impl SetLogFilter,
+) {
+    let mut sighup = match signal(SignalKind::hangup()) {
+        Ok(sighup) => sighup,
+        Err(error) => {
+            error!(%error, "unable to listen for SIGHUP: config reloading is disabled");
+            return;
+        }
+    };
+
+    while sighup.recv().await.is_some() {
+        info!("SIGHUP received: reloading config");
+        let config = match DaemonConfig::load() {
+            Ok(config) => config,
+            Err(error) => {
+                warn!(%error, "invalid config: keeping the current config");
+                continue;
+            }
+        };
+
+        if config.log_level != tx.borrow().log_level {
+            if let Err(error) = log_filter_handle.set_filter(&log_filter(&config.log_level)) {
+                warn!(%error, "unable to update the log level");
+            }
+        }
+
+        info!(?config, "config reloaded");
+        tx.send_replace(config);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple_test_case::test_case;
+
+    #[test]
+    fn empty_config_is_the_default() {
+        assert_eq!(DaemonConfig::parse("").unwrap(), DaemonConfig::default());
+    }
+
+    #[test]
+    fn missing_config_file_is_the_default() {
+        let config = DaemonConfig::load_from(Path::new("/does/not/exist/config.toml")).unwrap();
+
+        assert_eq!(config, DaemonConfig::default());
+    }
+
+    #[test]
+    fn parsing_config_works() {
+        let raw = r#"
+log-level = "debug"
+persistent-ui = true
+ui-command = "/usr/bin/prompting-ui"
+recv-timeout-ms = 500
+max-poll-retries = 5
+long-poll-timeout-secs = 60
+
+[prompt-timeout]
+timeout-secs = 30
+policy = "deny-for-session"
+"#;
+
+        let config = DaemonConfig::parse(raw).unwrap();
+
+        assert_eq!(
+            config,
+            DaemonConfig {
+                log_level: "debug".to_string(),
+                persistent_ui: true,
+                ui_command: Some("/usr/bin/prompting-ui".to_string()),
+                recv_timeout_ms: 500,
+                max_poll_retries: 5,
+                long_poll_timeout_secs: 60,
+                prompt_timeout: Some(PromptTimeoutConfig {
+                    timeout_secs: 30,
+                    policy: TimeoutPolicy::DenyForSession,
+                }),
+                ..Default::default()
+            }
+        );
+        assert_eq!(config.ui_command(), "/usr/bin/prompting-ui");
+        assert_eq!(
+            config.prompt_timeout(),
+            Some(PromptTimeout {
+                timeout: Duration::from_secs(30),
+                policy: TimeoutPolicy::DenyForSession,
+            })
+        );
+    }
+
+    #[test]
+    fn prompt_timeout_policy_defaults_to_deny_once() {
+        let config = DaemonConfig::parse("[prompt-timeout]\ntimeout-secs = 30").unwrap();
+
+        assert_eq!(
+            config.prompt_timeout().map(|t| t.policy),
+            Some(TimeoutPolicy::DenyOnce)
+        );
+    }
+
+    #[test_case("log-level = 'info,,=[['"; "invalid log level")]
+    #[test_case("ui-command = ' '"; "empty ui command")]
+    #[test_case("persistent-ui = true"; "persistent ui without ui command")]
+    #[test_case("recv-timeout-ms = 0"; "zero recv timeout")]
+    #[test_case("retry-sleep-ms = 0"; "zero retry sleep")]
+    #[test_case("retry-sleep-ms = 500\nmax-retry-sleep-ms = 100"; "max retry sleep below retry sleep")]
+    #[test_case("long-poll-timeout-secs = 0"; "zero long poll timeout")]
+    #[test_case("history-max-bytes = 0"; "zero history max bytes")]
+    #[test_case("[prompt-timeout]\ntimeout-secs = 0"; "zero prompt timeout")]
+    #[test_case("[prompt-timeout]\npolicy = 'deny-once'"; "prompt timeout without timeout")]
+    #[test_case("[prompt-timeout]\ntimeout-secs = 10\npolicy = 'allow'"; "unknown policy")]
+    #[test_case("recv-timeout = 10"; "unknown field")]
+    #[test_case("max-poll-retries = -1"; "negative retries")]
+    #[test]
+    fn invalid_config_errors(raw: &str) {
+        match DaemonConfig::parse(raw) {
+            Err(Error::InvalidConfig { .. }) => (),
+            res => panic!("expected InvalidConfig, got {res:?}"),
+        }
+    }
+}
@@ -0,0 +1,652 @@
+//! A persistent audit log of the prompts we have seen and how they were replied to.
+//!
+//! Entries are appended as JSON lines to `history.jsonl` under the user's data directory by a
+//! background task, with the file being rotated once it reaches the size configured in the
+//! [DaemonConfig]. Rotated files are suffixed with their generation (`history.jsonl.1` being the
+//! most recent) and the oldest are removed once there are more than the configured number.
+use crate::{
+    daemon::{ConfigHandle, DaemonConfig},
+    snapd_client::{Action, Lifespan, PromptId, TypedPrompt, TypedPromptReply},
+    Error, Result,
+};
+use chrono::{DateTime, Utc};
+use hyper::StatusCode;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::HashMap,
+    env, io,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+use strum::Display;
+use tokio::{
+    fs::{self, OpenOptions},
+    io::AsyncWriteExt,
+    sync::mpsc::{unbounded_channel, UnboundedReceiver, UnboundedSender},
+};
+use tracing::{debug, warn};
+
+const HISTORY_FILE: &str = "history.jsonl";
+
+/// Prompts that we have not seen any activity for within this time are assumed to have been
+/// removed without us being notified and are no longer tracked.
+const MAX_TRACKED_AGE: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// A single event in the life of a prompt.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct HistoryEntry {
+    pub timestamp: DateTime<Utc>,
+    pub id: PromptId,
+    pub snap: String,
+    pub interface: String,
+    #[serde(flatten)]
+    pub event: HistoryEvent,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "event", rename_all = "kebab-case")]
+pub enum HistoryEvent {
+    /// The prompt was received from snapd.
+    Prompted { constraints: serde_json::Value },
+    /// The prompt was presented to the user.
+    Displayed,
+    /// A reply was sent to snapd for the prompt.
+    #[serde(rename_all = "kebab-case")]
+    Replied {
+        action: Action,
+        lifespan: Lifespan,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        path_pattern: Option<String>,
+        #[serde(default)]
+        permissions: Vec<String>,
+        /// The time between the prompt being displayed (or received if it was never displayed)
+        /// and the reply being sent.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        latency_ms: Option<u64>,
+        outcome: ReplyOutcome,
+    },
+    /// The prompt was actioned by snapd as it was covered by a rule created by replying to
+    /// another prompt.
+    #[serde(rename_all = "kebab-case")]
+    ResolvedByRule {
+        /// The prompt whose reply created the rule.
+        by: PromptId,
+    },
+    /// The prompt was removed by snapd without us having replied to it.
+    Dropped,
+}
+
+/// How a reply to a prompt came to be sent and whether snapd accepted it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Display)]
+#[serde(rename_all = "kebab-case")]
+#[strum(serialize_all = "kebab-case")]
+pub enum ReplyOutcome {
+    /// The user's reply was accepted by snapd.
+    Success,
+    /// The prompt was no longer present in snapd when replying.
+    NotFound,
+    /// The prompt was denied after the user failed to reply in time.
+    TimeoutDeny,
+    /// The prompt was denied as we were unable to map it to the UI.
+    MappingFailureDeny,
+    /// Snapd returned an error other than the prompt not being found.
+    Failed,
+}
+
+#[derive(Debug, Clone)]
+struct Tracked {
+    snap: String,
+    interface: String,
+    seen_at: Instant,
+}
+
+/// A handle for recording prompt history that is shared between the worker and the GRPC server.
+///
+/// The default handle does not record anything.
+#[derive(Debug, Clone, Default)]
+pub struct History {
+    tx: Option<UnboundedSender<HistoryEntry>>,
+    path: Option<PathBuf>,
+    tracked: Arc<Mutex<HashMap<PromptId, Tracked>>>,
+}
+
+impl History {
+    /// Create a new handle along with the [HistoryWriter] for persisting the entries it records.
+    pub fn new(path: PathBuf, config: ConfigHandle) -> (Self, HistoryWriter) {
+        let (tx, rx) = unbounded_channel();
+        let history = Self {
+            tx: Some(tx),
+            path: Some(path.clone()),
+            tracked: Default::default(),
+        };
+
+        (history, HistoryWriter { path, config, rx })
+    }
+
+    /// The location of the history file: `$SNAP_USER_DATA/history.jsonl` when running as a snap
+    /// and `$XDG_DATA_HOME/prompting-client/history.jsonl` otherwise.
+    pub fn default_path() -> Option<PathBuf> {
+        if let Ok(dir) = env::var("SNAP_USER_DATA") {
+            return Some(Path::new(&dir).join(HISTORY_FILE));
+        }
+
+        let data_dir = match env::var("XDG_DATA_HOME") {
+            Ok(dir) if !dir.is_empty() => PathBuf::from(dir),
+            _ => Path::new(&env::var("HOME").ok()?).join(".local/share"),
+        };
+
+        Some(data_dir.join(crate::SNAP_NAME).join(HISTORY_FILE))
+    }
+
+    /// The file that entries are being written to, if history is being recorded.
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+
+    pub(crate) fn prompted(&self, prompt: &TypedPrompt) {
+        if self.tx.is_none() {
+            return;
+        }
+
+        let tracked = Tracked {
+            snap: prompt.snap().to_string(),
+            interface: prompt.interface().to_string(),
+            seen_at: Instant::now(),
+        };
+        let entry = new_entry(
+            prompt.id(),
+            &tracked,
+            HistoryEvent::Prompted {
+                constraints: prompt.constraints_json(),
+            },
+        );
+        let mut all_tracked = self.tracked();
+        evict_stale(&mut all_tracked, Instant::now());
+        all_tracked.insert(prompt.id().clone(), tracked);
+        drop(all_tracked);
+        self.record(entry);
+    }
+
+    pub(crate) fn displayed(&self, id: &PromptId) {
+        let mut tracked = self.tracked();
+        if let Some(t) = tracked.get_mut(id) {
+            t.seen_at = Instant::now();
+            let entry = new_entry(id, t, HistoryEvent::Displayed);
+            drop(tracked);
+            self.record(entry);
+        }
+    }
+
+    pub(crate) fn replied(&self, id: &PromptId, reply: &TypedPromptReply, outcome: ReplyOutcome) {
+        if self.tx.is_none() {
+            return;
+        }
+
+        // Prompts we have not seen are recorded without their snap and interface. Failed replies
+        // may be retried so we keep tracking the prompt.
+        let tracked = match outcome {
+            ReplyOutcome::Failed => self.tracked().get(id).cloned(),
+            _ => self.tracked().remove(id),
+        };
+        let (tracked, latency_ms) = match tracked {
+            Some(t) => {
+                let latency_ms = t.seen_at.elapsed().as_millis() as u64;
+                (t, Some(latency_ms))
+            }
+            None => (
+                Tracked {
+                    snap: String::new(),
+                    interface: String::new(),
+                    seen_at: Instant::now(),
+                },
+                None,
+            ),
+        };
+
+        self.record(new_entry(
+            id,
+            &tracked,
+            HistoryEvent::Replied {
+                action: reply.action(),
+                lifespan: reply.lifespan(),
+                path_pattern: reply.path_pattern().map(String::from),
+                permissions: reply.permissions(),
+                latency_ms,
+                outcome,
+            },
+        ));
+    }
+
+    /// Record a reply to a prompt that snapd returned an error for.
+    pub(crate) fn reply_failed(&self, id: &PromptId, reply: &TypedPromptReply, error: &Error) {
+        let outcome = match error {
+            Error::SnapdError { status, .. } if *status == StatusCode::NOT_FOUND => {
+                ReplyOutcome::NotFound
+            }
+            _ => ReplyOutcome::Failed,
+        };
+
+        self.replied(id, reply, outcome);
+    }
+
+    /// Record the prompts that snapd actioned as a result of the rule created by replying to the
+    /// prompt with the given id.
+    pub(crate) fn resolved_by_rule(&self, by: &PromptId, ids: &[PromptId]) {
+        for id in ids {
+            if let Some(tracked) = self.tracked().remove(id) {
+                let event = HistoryEvent::ResolvedByRule { by: by.clone() };
+                self.record(new_entry(id, &tracked, event));
+            }
+        }
+    }
+
+    /// Only prompts that have not already been replied to are recorded as dropped.
+    pub(crate) fn dropped(&self, id: &PromptId) {
+        if let Some(tracked) = self.tracked().remove(id) {
+            self.record(new_entry(id, &tracked, HistoryEvent::Dropped));
+        }
+    }
+
+    fn tracked(&self) -> std::sync::MutexGuard<'_, HashMap<PromptId, Tracked>> {
+        self.tracked.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn record(&self, entry: HistoryEntry) {
+        if let Some(tx) = &self.tx {
+            if tx.send(entry).is_err() {
+                warn!("history writer has exited: unable to record history");
+            }
+        }
+    }
+}
+
+/// Stop tracking any prompts that we have not seen activity for within [MAX_TRACKED_AGE].
+fn evict_stale(tracked: &mut HashMap<PromptId, Tracked>, now: Instant) {
+    tracked.retain(|id, t| {
+        let keep = now.saturating_duration_since(t.seen_at) < MAX_TRACKED_AGE;
+        if !keep {
+            debug!(id=%id.0, "no longer tracking stale prompt");
+        }
+        keep
+    });
+}
+
+fn new_entry(id: &PromptId, tracked: &Tracked, event: HistoryEvent) -> HistoryEntry {
+    HistoryEntry {
+        timestamp: Utc::now(),
+        id: id.clone(),
+        snap: tracked.snap.clone(),
+        interface: tracked.interface.clone(),
+        event,
+    }
+}
+
+/// Appends recorded entries to the history file, rotating it as needed.
+#[derive(Debug)]
+pub struct HistoryWriter {
+    path: PathBuf,
+    config: ConfigHandle,
+    rx: UnboundedReceiver<HistoryEntry>,
+}
+
+impl HistoryWriter {
+    /// Write entries as they are recorded until all [History] handles have been dropped (runs as
+    /// a top level task).
+    pub async fn run(mut self) {
+        if let Some(dir) = self.path.parent() {
+            if let Err(error) = fs::create_dir_all(dir).await {
+                warn!(%error, ?dir, "unable to create history directory");
+            }
+        }
+
+        while let Some(entry) = self.rx.recv().await {
+            if let Err(error) = self.append(&entry).await {
+                warn!(%error, "unable to write history entry");
+            }
+        }
+    }
+
+    async fn append(&self, entry: &HistoryEntry) -> Result<()> {
+        let mut line = serde_json::to_vec(entry)?;
+        line.push(b'\n');
+
+        let (max_bytes, max_files) = {
+            let config: &DaemonConfig = &self.config.borrow();
+            (config.history_max_bytes, config.history_max_files)
+        };
+
+        let size = match fs::metadata(&self.path).await {
+            Ok(meta) => meta.len(),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => 0,
+            Err(e) => return Err(e.into()),
+        };
+        if size > 0 && size + line.len() as u64 > max_bytes {
+            rotate(&self.path, max_files).await?;
+        }
+
+        let mut f = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)
+            .await?;
+        f.write_all(&line).await?;
+        // Tokio files complete writes in the background so we need to wait for them here
+        f.flush().await?;
+
+        Ok(())
+    }
+}
+
+fn rotated_path(path: &Path, generation: usize) -> PathBuf {
+    let mut s = path.as_os_str().to_owned();
+    s.push(format!(".{generation}"));
+
+    PathBuf::from(s)
+}
+
+/// Shift each rotated file up a generation, overwriting the oldest, before moving the current
+/// file into the first generation.
+async fn rotate(path: &Path, max_files: usize) -> Result<()> {
+    debug!(?path, "rotating history file");
+    if max_files == 0 {
+        return Ok(fs::remove_file(path).await?);
+    }
+
+    for generation in (1..max_files).rev() {
+        let from = rotated_path(path, generation);
+        match fs::rename(&from, rotated_path(path, generation + 1)).await {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => return Err(e.into()),
+            _ => (),
+        }
+    }
+
+    Ok(fs::rename(path, rotated_path(path, 1)).await?)
+}
+
+/// Read all of the entries in the history file along with any rotated files, from oldest to most
+/// recent. Lines that fail to parse are skipped.
+pub async fn read_history(path: &Path) -> Result<Vec<HistoryEntry>> {
+    let mut paths = vec![path.to_path_buf()];
+    for generation in 1.. {
+        let rotated = rotated_path(path, generation);
+        if !fs::try_exists(&rotated).await? {
+            break;
+        }
+        paths.push(rotated);
+    }
+
+    let mut entries = Vec::new();
+    for path in paths.iter().rev() {
+        let raw = match fs::read_to_string(path).await {
+            Ok(raw) => raw,
+            Err(e) if e.kind() == io::ErrorKind::NotFound => continue,
+            Err(e) => return Err(e.into()),
+        };
+
+        for line in raw.lines().filter(|l| !l.trim().is_empty()) {
+            match serde_json::from_str(line) {
+                Ok(entry) => entries.push(entry),
+                Err(error) => warn!(%error, ?path, "skipping malformed history entry"),
+            }
+        }
+    }
+
+    Ok(entries)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapd_client::{
+        interfaces::home::{HomeConstraints, HomeInterface, HomeReplyConstraints, PermissionSet},
+        Prompt, PromptReply,
+    };
+    use simple_test_case::test_case;
+    use uuid::Uuid;
+
+    fn home_prompt(id: &str) -> TypedPrompt {
+        TypedPrompt::Home(Prompt {
+            id: PromptId(id.to_string()),
+            timestamp: Default::default(),
+            snap: "firefox".to_string(),
+            interface: "home".to_string(),
+            constraints: HomeConstraints {
+                path: "/home/user/foo.txt".to_string(),
+                requested_permissions: PermissionSet::from(["read"]),
+                available_permissions: PermissionSet::from(["read", "write"]),
+            },
+        })
+    }
+
+    fn home_reply() -> TypedPromptReply {
+        TypedPromptReply::Home(PromptReply::<HomeInterface> {
+            action: Action::Allow,
+            lifespan: Lifespan::Forever,
+            duration: None,
+            constraints: HomeReplyConstraints {
+                path_pattern: "/home/user/**".to_string(),
+                permissions: PermissionSet::from(["read"]),
+                ..Default::default()
+            },
+        })
+    }
+
+    fn test_dir() -> PathBuf {
+        let dir = env::temp_dir().join(Uuid::new_v4().to_string());
+        std::fs::create_dir_all(&dir).unwrap();
+
+        dir
+    }
+
+    fn events(entries: &[HistoryEntry]) -> Vec<(&str, &str)> {
+        entries
+            .iter()
+            .map(|e| {
+                let event = match &e.event {
+                    HistoryEvent::Prompted { .. } => "prompted",
+                    HistoryEvent::Displayed => "displayed",
+                    HistoryEvent::Replied { .. } => "replied",
+                    HistoryEvent::ResolvedByRule { .. } => "resolved-by-rule",
+                    HistoryEvent::Dropped => "dropped",
+                };
+                (e.id.0.as_str(), event)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn entries_round_trip_through_json() {
+        let entry = HistoryEntry {
+            timestamp: "2024-08-14T07:28:22.694800024Z".parse().unwrap(),
+            id: PromptId("1".to_string()),
+            snap: "firefox".to_string(),
+            interface: "home".to_string(),
+            event: HistoryEvent::Replied {
+                action: Action::Allow,
+                lifespan: Lifespan::Forever,
+                path_pattern: Some("/home/user/**".to_string()),
+                permissions: vec!["read".to_string()],
+                latency_ms: Some(1234),
+                outcome: ReplyOutcome::Success,
+            },
+        };
+
+        let json = serde_json::to_value(&entry).unwrap();
+        let expected = serde_json::json!({
+            "timestamp": "2024-08-14T07:28:22.694800024Z",
+            "id": "1",
+            "snap": "firefox",
+            "interface": "home",
+            "event": "replied",
+            "action": "allow",
+            "lifespan": "forever",
+            "path-pattern": "/home/user/**",
+            "permissions": ["read"],
+            "latency-ms": 1234,
+            "outcome": "success"
+        });
+
+        assert_eq!(json, expected);
+        assert_eq!(serde_json::from_value::<HistoryEntry>(json).unwrap(), entry);
+    }
+
+    #[tokio::test]
+    async fn history_is_written_and_read_back() {
+        let path = test_dir().join(HISTORY_FILE);
+        let (history, writer) = History::new(path.clone(), DaemonConfig::default().fixed());
+
+        history.prompted(&home_prompt("1"));
+        history.prompted(&home_prompt("2"));
+        history.displayed(&PromptId("1".to_string()));
+        history.replied(
+            &PromptId("1".to_string()),
+            &home_reply(),
+            ReplyOutcome::Success,
+        );
+        history.dropped(&PromptId("1".to_string())); // already replied to so not recorded
+        history.dropped(&PromptId("2".to_string()));
+        drop(history);
+        writer.run().await;
+
+        let entries = read_history(&path).await.unwrap();
+
+        assert_eq!(
+            events(&entries),
+            vec![
+                ("1", "prompted"),
+                ("2", "prompted"),
+                ("1", "displayed"),
+                ("1", "replied"),
+                ("2", "dropped"),
+            ]
+        );
+        match &entries[3].event {
+            HistoryEvent::Replied {
+                path_pattern,
+                outcome,
+                ..
+            } => {
+                assert_eq!(path_pattern.as_deref(), Some("/home/user/**"));
+                assert_eq!(*outcome, ReplyOutcome::Success);
+            }
+            event => panic!("expected a reply, got {event:?}"),
+        }
+        assert_eq!(entries[0].snap, "firefox");
+        assert_eq!(entries[0].interface, "home");
+    }
+
+    #[tokio::test]
+    async fn failed_and_resolved_prompts_are_recorded() {
+        let path = test_dir().join(HISTORY_FILE);
+        let (history, writer) = History::new(path.clone(), DaemonConfig::default().fixed());
+        let id = |s: &str| PromptId(s.to_string());
+
+        for i in ["1", "2", "3"] {
+            history.prompted(&home_prompt(i));
+        }
+        let err = Error::Io(io::Error::other("boom"));
+        history.reply_failed(&id("1"), &home_reply(), &err);
+        history.replied(&id("1"), &home_reply(), ReplyOutcome::Success);
+        history.resolved_by_rule(&id("1"), &[id("2"), id("4")]);
+        history.dropped(&id("2")); // already resolved so not recorded
+        history.dropped(&id("3"));
+        drop(history);
+        writer.run().await;
+
+        let entries = read_history(&path).await.unwrap();
+
+        assert_eq!(
+            events(&entries),
+            vec![
+                ("1", "prompted"),
+                ("2", "prompted"),
+                ("3", "prompted"),
+                ("1", "replied"),
+                ("1", "replied"),
+                ("2", "resolved-by-rule"),
+                ("3", "dropped"),
+            ]
+        );
+        let outcomes: Vec<_> = entries[3..5]
+            .iter()
+            .map(|e| match &e.event {
+                HistoryEvent::Replied { outcome, .. } => *outcome,
+                event => panic!("expected a reply, got {event:?}"),
+            })
+            .collect();
+        // The prompt is still tracked after the failed reply
+        assert_eq!(outcomes, vec![ReplyOutcome::Failed, ReplyOutcome::Success]);
+        assert_eq!(entries[4].snap, "firefox");
+        assert_eq!(
+            entries[5].event,
+            HistoryEvent::ResolvedByRule { by: id("1") }
+        );
+    }
+
+    #[test]
+    fn stale_prompts_are_no_longer_tracked() {
+        let (history, _writer) = History::new(
+            test_dir().join(HISTORY_FILE),
+            DaemonConfig::default().fixed(),
+        );
+        history.prompted(&home_prompt("1"));
+
+        evict_stale(&mut history.tracked(), Instant::now());
+        assert_eq!(history.tracked().len(), 1);
+
+        evict_stale(&mut history.tracked(), Instant::now() + MAX_TRACKED_AGE);
+        assert!(history.tracked().is_empty());
+    }
+
+    #[test]
+    fn default_history_records_nothing() {
+        let history = History::default();
+        history.prompted(&home_prompt("1"));
+
+        assert!(history.tracked().is_empty());
+        assert!(history.path().is_none());
+    }
+
+    #[test_case(1, &["5"], &["4"]; "single rotated file")]
+    #[test_case(3, &["5"], &["4", "3", "2"]; "multiple rotated files")]
+    #[test_case(0, &["5"], &[]; "no rotated files")]
+    #[tokio::test]
+    async fn history_files_are_rotated(max_files: usize, current: &[&str], rotated: &[&str]) {
+        let path = test_dir().join(HISTORY_FILE);
+        let config = DaemonConfig {
+            history_max_bytes: 10,
+            history_max_files: max_files,
+            ..Default::default()
+        };
+        let (history, writer) = History::new(path.clone(), config.fixed());
+
+        // Every entry is larger than the max file size so each ends up in its own file
+        for id in ["1", "2", "3", "4", "5"] {
+            history.prompted(&home_prompt(id));
+        }
+        drop(history);
+        writer.run().await;
+
+        let ids_in = |p: &Path| -> Vec<String> {
+            std::fs::read_to_string(p)
+                .unwrap()
+                .lines()
+                .map(|l| serde_json::from_str::<HistoryEntry>(l).unwrap().id.0)
+                .collect()
+        };
+
+        assert_eq!(ids_in(&path), current);
+        for (i, id) in rotated.iter().enumerate() {
+            assert_eq!(ids_in(&rotated_path(&path, i + 1)), &[*id]);
+        }
+        assert!(!rotated_path(&path, rotated.len() + 1).exists());
+
+        let mut all: Vec<&str> = rotated.iter().rev().copied().collect();
+        all.extend(current);
+        let entries = read_history(&path).await.unwrap();
+        let ids: Vec<&str> = entries.iter().map(|e| e.id.0.as_str()).collect();
+        assert_eq!(ids, all);
+    }
+}
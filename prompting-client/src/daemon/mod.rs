@@ -1,22 +1,42 @@
 use crate::{
-    snapd_client::{PromptId, SnapMeta, SnapdSocketClient, TypedPrompt, TypedPromptReply},
+    snapd_client::{
+        PromptId, RuleEdit, RuleId, SnapMeta, SnapdSocketClient, TypedPrompt, TypedPromptReply,
+        TypedRule,
+    },
     Result, SOCKET_ENV_VAR,
 };
 use serde::{Deserialize, Serialize};
-use std::{env, fs, sync::Arc};
-use tokio::sync::mpsc::unbounded_channel;
+use std::{env, fs, sync::Arc, time::Duration};
+use tokio::{
+    select,
+    signal::unix::{signal, SignalKind},
+    sync::{broadcast, mpsc::unbounded_channel, watch},
+    time::timeout,
+};
 use tokio_stream::wrappers::UnixListenerStream;
+use tokio_util::sync::CancellationToken;
 use tonic::{async_trait, transport::Server};
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::{reload::Handle, EnvFilter};
 
+mod config;
+mod history;
 mod poll;
 mod server;
 mod worker;
 
+use config::reload_on_sighup;
+pub use config::{ConfigHandle, DaemonConfig, PromptTimeoutConfig};
+pub use history::{read_history, History, HistoryEntry, HistoryEvent, ReplyOutcome};
 pub use poll::PollLoop;
 use server::new_server_and_listener;
-use worker::Worker;
+pub use worker::TimeoutPolicy;
+use worker::{DaemonUi, Worker};
+
+/// The number of rule updates buffered for each connected UI before the oldest are dropped.
+const RULE_UPDATE_BUFFER: usize = 64;
+/// How long to wait for the worker and poll loop to stop once shutdown has been triggered.
+const SHUTDOWN_GRACE_PERIOD: Duration = Duration::from_secs(5);
 
 #[async_trait]
 pub trait ReplyToPrompt: Send + Sync + 'static {
@@ -30,11 +50,47 @@ impl ReplyToPrompt for SnapdSocketClient {
     }
 }
 
+#[async_trait]
+pub trait ManageRules: Send + Sync + 'static {
+    async fn list_rules(
+        &self,
+        snap: Option<&str>,
+        interface: Option<&str>,
+    ) -> crate::Result<Vec<TypedRule>>;
+
+    async fn remove_rule(&self, id: &RuleId) -> crate::Result<TypedRule>;
+
+    async fn edit_rule(&self, id: &RuleId, edit: RuleEdit) -> crate::Result<TypedRule>;
+}
+
+#[async_trait]
+impl ManageRules for SnapdSocketClient {
+    async fn list_rules(
+        &self,
+        snap: Option<&str>,
+        interface: Option<&str>,
+    ) -> crate::Result<Vec<TypedRule>> {
+        SnapdSocketClient::list_rules(self, snap, interface).await
+    }
+
+    async fn remove_rule(&self, id: &RuleId) -> crate::Result<TypedRule> {
+        SnapdSocketClient::remove_rule(self, id).await
+    }
+
+    async fn edit_rule(&self, id: &RuleId, edit: RuleEdit) -> crate::Result<TypedRule> {
+        SnapdSocketClient::edit_rule(self, id, edit).await
+    }
+}
+
 // Poll loop -> worker
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct EnrichedPrompt {
     pub(crate) prompt: TypedPrompt,
     meta: Option<SnapMeta>,
+    /// Related prompts that have been coalesced into this one so that they can be replied to
+    /// together.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub(crate) grouped: Vec<TypedPrompt>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -44,6 +100,14 @@ pub enum PromptUpdate {
     Drop(PromptId),
 }
 
+// Poll loop -> server
+#[derive(Debug, Clone)]
+pub enum RuleUpdate {
+    Changed(Box<TypedRule>),
+    Removed(RuleId),
+    Expired(RuleId),
+}
+
 // Server -> worker
 #[derive(Debug, Clone)]
 pub enum ActionedPrompt {
@@ -53,16 +117,58 @@ pub enum ActionedPrompt {
 
 /// Start our backgroud polling and processing loops before dropping into running the tonic GRPC
 /// server for handling incoming requestes from the Flutter UI client.
-pub async fn run_daemon<L, S>(c: SnapdSocketClient, reload_handle: Handle<L, S>) -> Result<()>
+///
+/// All tasks are shut down when we receive SIGTERM or SIGINT, or if any of them are unable to
+/// continue running, after which our socket file is removed.
+pub async fn run_daemon<L, S>(
+    c: SnapdSocketClient,
+    reload_handle: Handle<L, S>,
+    config: DaemonConfig,
+) -> Result<()>
 where
     L: From<EnvFilter> + Send + Sync + 'static,
     S: 'static,
 {
     let (tx_prompts, rx_prompts) = unbounded_channel();
     let (tx_actioned, rx_actioned) = unbounded_channel();
+    let (tx_rule_updates, _) = broadcast::channel(RULE_UPDATE_BUFFER);
+    let reload_handle = Arc::new(reload_handle);
+    let shutdown = CancellationToken::new();
+
+    info!("spawning signal handler");
+    tokio::spawn(cancel_on_signal(shutdown.clone()));
+
+    let ui = DaemonUi::from_config(&config);
+    let persistent_ui = config.persistent_ui;
+    let (tx_config, rx_config) = watch::channel(config);
+
+    info!("spawning config reload task");
+    tokio::spawn(reload_on_sighup(tx_config, reload_handle.clone()));
 
-    let mut worker = Worker::new(rx_prompts, rx_actioned, c.clone());
+    let history = match History::default_path() {
+        Some(path) => {
+            info!(?path, "spawning history writer");
+            let (history, writer) = History::new(path, rx_config.clone());
+            tokio::spawn(writer.run());
+            history
+        }
+        None => {
+            warn!("unable to determine the history file location: history will not be recorded");
+            History::default()
+        }
+    };
+
+    info!(%persistent_ui, "spawning worker thread");
+    let token = shutdown.clone();
+    let mut worker = Worker::new(rx_prompts, rx_actioned, c.clone(), rx_config.clone(), ui)
+        .with_history(history.clone());
     let active_prompt = worker.read_only_active_prompt();
+    let worker_task = tokio::spawn(async move {
+        if let Err(error) = worker.run(token.clone()).await {
+            error!(%error, "worker exited with an error");
+        }
+        token.cancel();
+    });
 
     let path = env::var(SOCKET_ENV_VAR).expect("socket env var not set");
     if let Err(e) = fs::remove_file(&path) {
@@ -70,29 +176,66 @@ where
     }
     let (server, listener) = new_server_and_listener(
         c.clone(),
-        Arc::new(reload_handle),
+        reload_handle,
         active_prompt,
+        history,
         tx_actioned,
-        path,
+        tx_rule_updates.clone(),
+        path.clone(),
     );
 
     info!("spawning poll loop");
-    let poll_loop = PollLoop::new(c, tx_prompts);
-    tokio::spawn(async move { poll_loop.run().await });
-
-    info!("spawning worker thread");
-    tokio::spawn(async move { worker.run().await });
+    let mut poll_loop = PollLoop::new(c, tx_prompts, rx_config);
+    poll_loop.watch_rules(tx_rule_updates);
+    poll_loop.shutdown_on(shutdown.clone());
+    let poll_task = tokio::spawn(async move { poll_loop.run().await });
 
     info!("serving incoming grpc connections");
     let res = Server::builder()
         .add_service(server)
-        .serve_with_incoming(UnixListenerStream::new(listener))
+        .serve_with_incoming_shutdown(UnixListenerStream::new(listener), shutdown.cancelled())
         .await;
 
-    if let Err(error) = res {
+    if let Err(error) = &res {
         error!(%error, "grpc server fatal error");
-        panic!("{error}");
     }
 
-    Ok(())
+    info!("shutting down");
+    shutdown.cancel();
+    let tasks = async {
+        let _ = worker_task.await;
+        let _ = poll_task.await;
+    };
+    if timeout(SHUTDOWN_GRACE_PERIOD, tasks).await.is_err() {
+        warn!("timed out waiting for background tasks to stop");
+    }
+
+    if let Err(error) = fs::remove_file(&path) {
+        warn!(%path, %error, "unable to remove socket file");
+    }
+
+    Ok(res?)
+}
+
+/// Trigger a shutdown of the daemon when we receive SIGTERM or SIGINT (runs as a top level task).
+async fn cancel_on_signal(shutdown: CancellationToken) {
+    let signals = (
+        signal(SignalKind::terminate()),
+        signal(SignalKind::interrupt()),
+    );
+    let (mut sigterm, mut sigint) = match signals {
+        (Ok(sigterm), Ok(sigint)) => (sigterm, sigint),
+        (Err(error), _) | (_, Err(error)) => {
+            error!(%error, "unable to listen for SIGTERM and SIGINT");
+            return;
+        }
+    };
+
+    select! {
+        _ = sigterm.recv() => info!("SIGTERM received"),
+        _ = sigint.recv() => info!("SIGINT received"),
+        _ = shutdown.cancelled() => return,
+    }
+
+    shutdown.cancel();
 }
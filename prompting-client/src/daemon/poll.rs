@@ -4,46 +4,70 @@
 //! all of the required data we need from snapd in order to be able to serve the prompt UI. The
 //! enriched prompts themselves are simply passed off on a channel for downstream consumption and
 //! mapping into the data required for the prompt UI.
+//!
+//! When watching rules, changes to prompting rules are also pulled from snapd and broadcast to any
+//! connected UIs.
 use crate::{
-    daemon::{EnrichedPrompt, PromptUpdate},
-    snapd_client::{PromptId, SnapMeta, SnapdSocketClient, TypedPrompt},
-    Error,
+    daemon::{ConfigHandle, EnrichedPrompt, PromptUpdate, RuleUpdate},
+    snapd_client::{
+        PromptId, RuleId, RuleRemoval, SnapMeta, SnapdNotice, SnapdSocketClient, TypedPrompt,
+    },
+    Error, Result,
 };
-use cached::proc_macro::cached;
+use cached::{Cached, TimedCache};
 use hyper::StatusCode;
-use std::{process::exit, time::Duration};
-use tokio::{sync::mpsc::UnboundedSender, time::sleep};
+use std::{collections::HashSet, time::Duration};
+use tokio::{
+    select,
+    sync::{broadcast, mpsc::UnboundedSender},
+    time::sleep,
+};
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-const MAX_POLL_RETRIES: usize = 3;
-const RETRY_SLEEP_DURATION: Duration = Duration::from_millis(200);
-
-#[cached(
-    time = 3600,  // seconds
-    option = true,
-    sync_writes = true,
-    key = "String",
-    convert = r#"{ String::from(snap) }"#
-)]
-async fn get_snap_meta(client: &SnapdSocketClient, snap: &str) -> Option<SnapMeta> {
-    client.snap_metadata(snap).await
+/// The result of probing snapd after failing to poll for notices.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SnapdHealth {
+    Reachable,
+    Unreachable,
+    PromptingDisabled,
 }
 
 #[derive(Debug, Clone)]
 pub struct PollLoop {
     client: SnapdSocketClient,
     tx: UnboundedSender<PromptUpdate>,
+    tx_rules: Option<broadcast::Sender<RuleUpdate>>,
+    config: ConfigHandle,
+    snap_meta: TimedCache<String, SnapMeta>,
+    shutdown: CancellationToken,
     running: bool,
     skip_outstanding_prompts: bool,
+    /// The prompts that we have sent downstream and not yet seen dropped.
+    known_prompts: HashSet<PromptId>,
+    /// Whether we have lost our connection to snapd and need to re-sync once it returns.
+    disconnected: bool,
 }
 
 impl PollLoop {
-    pub fn new(client: SnapdSocketClient, tx: UnboundedSender<PromptUpdate>) -> Self {
+    pub fn new(
+        client: SnapdSocketClient,
+        tx: UnboundedSender<PromptUpdate>,
+        config: ConfigHandle,
+    ) -> Self {
+        let snap_meta = TimedCache::with_lifespan(config.borrow().snap_meta_cache_secs);
+
         Self {
             client,
             tx,
+            tx_rules: None,
+            config,
+            snap_meta,
+            shutdown: CancellationToken::new(),
             running: true,
             skip_outstanding_prompts: false,
+            known_prompts: HashSet::new(),
+            disconnected: false,
         }
     }
 
@@ -51,6 +75,17 @@ impl PollLoop {
         self.skip_outstanding_prompts = true;
     }
 
+    /// Also poll for changes to prompting rules, sending them on the provided channel.
+    pub fn watch_rules(&mut self, tx_rules: broadcast::Sender<RuleUpdate>) {
+        self.tx_rules = Some(tx_rules);
+    }
+
+    /// Stop polling when the provided token is cancelled. The token is also cancelled by the
+    /// poll loop itself if it is no longer able to poll snapd for notices.
+    pub fn shutdown_on(&mut self, shutdown: CancellationToken) {
+        self.shutdown = shutdown;
+    }
+
     /// Run our poll loop for prompting notices from snapd (runs as a top level task).
     ///
     /// This first checks for any outstanding (unactioned) prompts on the system for the user
@@ -58,15 +93,27 @@ impl PollLoop {
     /// This task is responsible for pulling prompt details and snap meta-data from snapd but
     /// does not directly process the prompts themselves.
     pub async fn run(mut self) {
+        let shutdown = self.shutdown.clone();
+
+        select! {
+            _ = shutdown.cancelled() => info!("shutting down poll loop"),
+            _ = self.poll() => (),
+        }
+    }
+
+    async fn poll(&mut self) {
         if !self.skip_outstanding_prompts {
             self.handle_outstanding_prompts().await;
         }
 
+        // Failed attempts are always backed off but only count against our retries while snapd is
+        // reachable, so that we wait out snapd being restarted (for example during a refresh)
+        let mut attempts = 0;
         let mut retries = 0;
 
         while self.running {
             info!("polling for notices");
-            let pending = match self.client.pending_prompt_ids().await {
+            let pending = match self.pending_notices().await {
                 Ok(pending) => pending,
 
                 Err(Error::SnapdError {
@@ -74,34 +121,162 @@ impl PollLoop {
                     ..
                 }) => {
                     // If we're now getting permission denied after initially starting cleanly
-                    // then we trigger a restart with snapd so that our startup checks can run
-                    // again and we avoid spinning if snapd is now reporting that prompting is not
-                    // enabled / supported.
-                    exit(0);
+                    // then we shut down and trigger a restart with snapd so that our startup
+                    // checks can run again and we avoid spinning if snapd is now reporting that
+                    // prompting is not enabled / supported.
+                    warn!("permission denied polling for notices: shutting down");
+                    self.shutdown.cancel();
+                    return;
                 }
 
-                Err(error) if retries < MAX_POLL_RETRIES => {
-                    error!(%error, "unable to pull prompt ids: retrying");
-                    sleep(RETRY_SLEEP_DURATION).await;
-                    retries += 1;
+                Err(error) if retries < self.config.borrow().max_poll_retries => {
+                    if attempts == 0 {
+                        self.disconnected = true;
+                    }
+                    let delay = {
+                        let config = self.config.borrow();
+                        backoff(attempts, config.retry_sleep(), config.max_retry_sleep())
+                    };
+                    error!(%error, ?delay, %retries, "unable to poll for notices: retrying");
+                    sleep(delay).await;
+                    attempts += 1;
+
+                    match self.check_snapd_health().await {
+                        SnapdHealth::Reachable => retries += 1,
+                        SnapdHealth::Unreachable => (),
+                        SnapdHealth::PromptingDisabled => {
+                            self.shutdown.cancel();
+                            return;
+                        }
+                    }
                     continue;
                 }
 
                 Err(error) => {
-                    error!(%error, "retries exceeded trying to establish notices long poll: exiting");
-                    exit(0);
+                    error!(%error, "retries exceeded trying to establish notices long poll: shutting down");
+                    self.shutdown.cancel();
+                    return;
                 }
             };
 
+            attempts = 0;
             retries = 0;
+            if self.disconnected {
+                info!("reconnected to snapd: re-syncing outstanding prompts");
+                self.resync().await;
+            }
             debug!(?pending, "processing notices");
-            for id in pending {
-                self.pull_and_process_prompt(id).await;
+            for notice in pending {
+                match notice {
+                    SnapdNotice::Prompt(id) => self.pull_and_process_prompt(id).await,
+                    SnapdNotice::Rule { id, removed } => {
+                        self.process_rule_notice(id, removed).await
+                    }
+                }
+            }
+        }
+    }
+
+    /// Probe snapd between attempts at polling for notices to determine if it is reachable and
+    /// whether we should shut down as prompting is no longer enabled.
+    ///
+    /// The first time that snapd is reachable after we lost our connection to it we re-sync any
+    /// outstanding prompts in case it was restarted (for example during a refresh) while we were
+    /// unable to poll for notices.
+    async fn check_snapd_health(&mut self) -> SnapdHealth {
+        match self.client.is_prompting_enabled().await {
+            Ok(true) => {
+                if self.disconnected {
+                    info!("snapd is reachable: re-syncing outstanding prompts");
+                    self.resync().await;
+                }
+                SnapdHealth::Reachable
             }
+
+            Ok(false) => {
+                warn!("the prompting feature is no longer enabled: shutting down");
+                SnapdHealth::PromptingDisabled
+            }
+
+            Err(error) => {
+                debug!(%error, "snapd is not currently reachable");
+                self.disconnected = true;
+                SnapdHealth::Unreachable
+            }
+        }
+    }
+
+    /// Pull all outstanding prompts from snapd after reconnecting, dropping any that we know
+    /// about which snapd no longer has as we will have missed the notices for them.
+    async fn resync(&mut self) {
+        if let Some(outstanding) = self.handle_outstanding_prompts().await {
+            self.drop_missing_prompts(&outstanding);
+            self.disconnected = false;
+        }
+    }
+
+    fn drop_missing_prompts(&mut self, outstanding: &[PromptId]) {
+        let missing: Vec<PromptId> = self
+            .known_prompts
+            .iter()
+            .filter(|id| !outstanding.contains(id))
+            .cloned()
+            .collect();
+
+        for id in missing {
+            info!(id=%id.0, "prompt was removed by snapd while we were disconnected");
+            self.send_update(PromptUpdate::Drop(id));
+        }
+    }
+
+    async fn pending_notices(&mut self) -> Result<Vec<SnapdNotice>> {
+        let long_poll_timeout = self.config.borrow().long_poll_timeout();
+        self.client.set_long_poll_timeout(long_poll_timeout);
+
+        if self.tx_rules.is_some() {
+            return self.client.pending_notices().await;
+        }
+
+        let ids = self.client.pending_prompt_ids().await?;
+
+        Ok(ids.into_iter().map(SnapdNotice::Prompt).collect())
+    }
+
+    async fn process_rule_notice(&mut self, id: RuleId, removed: Option<RuleRemoval>) {
+        let Some(tx_rules) = &self.tx_rules else {
+            return;
+        };
+
+        let update = match removed {
+            Some(RuleRemoval::Removed) => RuleUpdate::Removed(id),
+            Some(RuleRemoval::Expired) => RuleUpdate::Expired(id),
+
+            None => match self.client.rule_details(&id).await {
+                Ok(rule) => RuleUpdate::Changed(Box::new(rule)),
+
+                Err(Error::SnapdError { status, .. }) if status == StatusCode::NOT_FOUND => {
+                    RuleUpdate::Removed(id)
+                }
+
+                Err(e) => {
+                    warn!(%e, "unable to pull rule");
+                    return;
+                }
+            },
+        };
+
+        // Sending only fails if there are currently no connected UIs watching for updates
+        if tx_rules.send(update).is_err() {
+            debug!("no active receivers for rule updates");
         }
     }
 
     fn send_update(&mut self, update: PromptUpdate) {
+        match &update {
+            PromptUpdate::Add(ep) => self.known_prompts.insert(ep.prompt.id().clone()),
+            PromptUpdate::Drop(id) => self.known_prompts.remove(id),
+        };
+
         if let Err(error) = self.tx.send(update) {
             warn!(%error, "receiver channel for enriched prompts has been dropped. Exiting.");
             self.running = false;
@@ -130,27 +305,54 @@ impl PollLoop {
     }
 
     async fn process_prompt(&mut self, prompt: TypedPrompt) {
-        let meta = get_snap_meta(&self.client, prompt.snap()).await;
-        self.send_update(PromptUpdate::Add(EnrichedPrompt { prompt, meta }));
+        let meta = self.snap_meta(prompt.snap()).await;
+        self.send_update(PromptUpdate::Add(EnrichedPrompt {
+            prompt,
+            meta,
+            grouped: Vec::new(),
+        }));
+    }
+
+    /// Snap meta-data is cached to avoid pulling it from snapd for every prompt.
+    async fn snap_meta(&mut self, snap: &str) -> Option<SnapMeta> {
+        let lifespan = self.config.borrow().snap_meta_cache_secs;
+        if self.snap_meta.cache_lifespan() != Some(lifespan) {
+            self.snap_meta.cache_set_lifespan(lifespan);
+        }
+
+        if let Some(meta) = self.snap_meta.cache_get(snap) {
+            return Some(meta.clone());
+        }
+
+        let meta = self.client.snap_metadata(snap).await?;
+        self.snap_meta.cache_set(snap.to_string(), meta.clone());
+
+        Some(meta)
     }
 
-    /// Catch up on all pending prompts before dropping into polling the notices API
-    async fn handle_outstanding_prompts(&mut self) {
+    /// Catch up on all pending prompts before dropping into polling the notices API, returning
+    /// the IDs of all outstanding prompts if we were able to pull them from snapd.
+    async fn handle_outstanding_prompts(&mut self) -> Option<Vec<PromptId>> {
         info!("checking for pending prompts");
         let pending = match self.client.all_pending_prompt_details().await {
             Err(error) => {
                 error!(%error, "unable to pull pending prompts");
-                return;
+                return None;
             }
             Ok(pending) if pending.is_empty() => {
                 info!("no currently pending prompts");
-                return;
+                pending
+            }
+            Ok(mut pending) => {
+                // Prompts are queued in the order we process them so make sure that the user
+                // sees the ones that have been waiting longest first.
+                pending.sort_by_key(|p| p.timestamp());
+                let n_prompts = pending.len();
+                info!(%n_prompts, "processing {n_prompts} pending prompts");
+                pending
             }
-            Ok(pending) => pending,
         };
 
-        let n_prompts = pending.len();
-        info!(%n_prompts, "processing {n_prompts} pending prompts");
         let mut seen = Vec::with_capacity(pending.len());
         for prompt in pending {
             seen.push(prompt.id().clone());
@@ -159,20 +361,86 @@ impl PollLoop {
 
         // The timestamps we get back from the prompts API are not semantically compatible with
         // the ones that we need to provide for the notices API, so we deliberately set up an
-        // overlap between pulling all pending prompts first before pulling pending prompt IDs
-        // and updating our internal `after` timestamp.
-        let pending = match self.client.pending_prompt_ids().await {
-            Ok(pending) => pending,
+        // overlap between pulling all pending prompts first before pulling pending notices and
+        // updating our internal `after` timestamp. When watching rules this includes any rule
+        // notices so that we do not skip over those that occurred while we were disconnected.
+        let notices = match self.pending_notices().await {
+            Ok(notices) => notices,
             Err(error) => {
-                error!(%error, "unable to pull pending prompt ids");
-                return;
+                error!(%error, "unable to pull pending notices");
+                return None;
             }
         };
 
-        for id in pending {
-            if !seen.contains(&id) {
-                self.pull_and_process_prompt(id).await;
+        for notice in notices {
+            match notice {
+                SnapdNotice::Prompt(id) if !seen.contains(&id) => {
+                    seen.push(id.clone());
+                    self.pull_and_process_prompt(id).await;
+                }
+                SnapdNotice::Prompt(_) => (),
+                SnapdNotice::Rule { id, removed } => self.process_rule_notice(id, removed).await,
             }
         }
+
+        Some(seen)
+    }
+}
+
+/// The delay before retrying after `attempt` consecutive failures: doubling from `base` with each
+/// attempt up to `max`, with jitter applied so that the actual delay falls between half and all
+/// of that value.
+fn backoff(attempt: usize, base: Duration, max: Duration) -> Duration {
+    let exp = u32::try_from(attempt).unwrap_or(u32::MAX);
+    let delay = base.saturating_mul(2u32.saturating_pow(exp)).min(max);
+    let half = delay / 2;
+
+    half + half.mul_f64(fastrand::f64())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::daemon::DaemonConfig;
+    use simple_test_case::test_case;
+    use tokio::sync::mpsc::unbounded_channel;
+
+    #[test]
+    fn prompts_missing_after_a_resync_are_dropped() {
+        let (tx, mut rx) = unbounded_channel();
+        let mut poll_loop = PollLoop::new(
+            SnapdSocketClient::default(),
+            tx,
+            DaemonConfig::default().fixed(),
+        );
+        let id = |s: &str| PromptId(s.to_string());
+        poll_loop.known_prompts.extend([id("1"), id("2")]);
+
+        poll_loop.drop_missing_prompts(&[id("1"), id("3")]);
+
+        match rx.try_recv() {
+            Ok(PromptUpdate::Drop(dropped)) => assert_eq!(dropped, id("2")),
+            res => panic!("expected a drop, got {res:?}"),
+        }
+        assert!(rx.try_recv().is_err());
+        assert_eq!(poll_loop.known_prompts, HashSet::from([id("1")]));
+    }
+
+    #[test_case(0, 100; "first attempt")]
+    #[test_case(1, 200; "second attempt")]
+    #[test_case(3, 800; "fourth attempt")]
+    #[test_case(10, 5000; "capped")]
+    #[test_case(usize::MAX, 5000; "capped without overflow")]
+    #[test]
+    fn backoff_is_exponential_with_jitter(attempt: usize, expected_max_ms: u64) {
+        let base = Duration::from_millis(100);
+        let max = Duration::from_secs(5);
+        let expected_max = Duration::from_millis(expected_max_ms);
+
+        for _ in 0..100 {
+            let delay = backoff(attempt, base, max);
+            assert!(delay >= expected_max / 2, "{delay:?} too short");
+            assert!(delay <= expected_max, "{delay:?} too long");
+        }
     }
 }
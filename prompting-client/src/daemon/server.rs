@@ -1,29 +1,57 @@
 //! The GRPC server that handles incoming connections from client UIs.
 use crate::{
-    daemon::{worker::ReadOnlyActivePrompt, ActionedPrompt, ReplyToPrompt},
+    daemon::{
+        history::{self, read_history, History, HistoryEntry, HistoryEvent, ReplyOutcome},
+        worker::{ActivePrompt, ReadOnlyActivePrompt},
+        ActionedPrompt, ManageRules, ReplyToPrompt, RuleUpdate,
+    },
     log_filter,
     protos::{
         apparmor_prompting::{
-            self, get_current_prompt_response::Prompt, home_prompt::PatternOption,
-            prompt_reply::PromptReply::HomePromptReply, prompt_reply_response::PromptReplyType,
-            HomePatternType, MetaData, PromptReply, SetLoggingFilterResponse,
+            self, generic_prompt::Constraint, get_current_prompt_response::Prompt, history_entry,
+            home_prompt::PatternOption, meta_data::PublisherValidation as ProtoPublisherValidation,
+            prompt_reply, prompt_reply_response::PromptReplyType, rule_update::RuleUpdateType,
+            watch_prompts_response::PromptStatus, HomePatternType, MetaData, PromptReply,
+            SetLoggingFilterResponse,
         },
-        AppArmorPrompting, AppArmorPromptingServer, GetCurrentPromptResponse, HomePrompt,
-        PromptReplyResponse, ResolveHomePatternTypeResponse,
+        AppArmorPrompting, AppArmorPromptingServer, AudioRecordPrompt, CameraPrompt, GenericPrompt,
+        GetCurrentPromptResponse, GetHistoryRequest, GetHistoryResponse,
+        HistoryEntry as ProtoHistoryEntry, HomePrompt, ListRulesRequest, ListRulesResponse,
+        PromptQueue, PromptReplyResponse, QueuedPrompt, ReplyToPromptsResponse,
+        ResolveHomePatternTypeResponse, Rule, RuleUpdate as ProtoRuleUpdate, UpdateRuleRequest,
+        WatchPromptsResponse,
     },
     snapd_client::{
         self,
-        interfaces::home::{
-            HomeInterface, HomeReplyConstraints, HomeUiInputData, PatternType, TypedPathPattern,
+        duration::GoDuration,
+        interfaces::{
+            audio_record::AudioRecordInterface,
+            camera::CameraInterface,
+            device::DeviceReplyConstraints,
+            home::{
+                HomeInterface, HomeReplyConstraints, HomeUiInputData, PatternType, PermissionSet,
+                TypedPathPattern,
+            },
+            unknown::UnknownInterface,
+            with_interfaces, SnapInterface,
         },
-        PromptId, PromptReply as SnapPromptReply, SnapMeta, TypedPromptReply, TypedUiInput,
-        UiInput,
+        path_pattern::validate_path_pattern,
+        PromptId, PromptReply as SnapPromptReply, RuleEdit, RuleId, SnapMeta, TypedPrompt,
+        TypedPromptReply, TypedRule, TypedUiInput, UiInput,
     },
     Error,
 };
+use chrono::{DateTime, SecondsFormat, Utc};
 use hyper::StatusCode;
-use std::sync::Arc;
-use tokio::{net::UnixListener, sync::mpsc::UnboundedSender};
+use std::{pin::Pin, sync::Arc};
+use tokio::{
+    net::UnixListener,
+    sync::{broadcast, mpsc::UnboundedSender},
+};
+use tokio_stream::{
+    wrappers::{errors::BroadcastStreamRecvError, BroadcastStream, WatchStream},
+    Stream, StreamExt,
+};
 use tonic::{async_trait, Code, Request, Response, Status};
 use tracing::{info, warn};
 use tracing_subscriber::{reload::Handle, EnvFilter};
@@ -50,18 +78,22 @@ pub fn new_server_and_listener<R, S>(
     client: R,
     reload_handle: S,
     active_prompt: ReadOnlyActivePrompt,
+    history: History,
     tx_actioned_prompts: UnboundedSender<ActionedPrompt>,
+    tx_rule_updates: broadcast::Sender<RuleUpdate>,
     socket_path: String,
 ) -> (AppArmorPromptingServer<Service<R, S>>, UnixListener)
 where
-    R: ReplyToPrompt + Clone,
+    R: ReplyToPrompt + ManageRules + Clone,
     S: SetLogFilter,
 {
     let service = Service::new(
         client.clone(),
         reload_handle,
         active_prompt,
+        history,
         tx_actioned_prompts,
+        tx_rule_updates,
     );
     let listener = UnixListener::bind(&socket_path).expect("to be able to bind to our socket");
 
@@ -101,7 +133,9 @@ where
     client: R,
     reload_handle: S,
     active_prompt: ReadOnlyActivePrompt,
+    history: History,
     tx_actioned_prompts: UnboundedSender<ActionedPrompt>,
+    tx_rule_updates: broadcast::Sender<RuleUpdate>,
 }
 
 impl<R, S> Service<R, S>
@@ -113,13 +147,17 @@ where
         client: R,
         reload_handle: S,
         active_prompt: ReadOnlyActivePrompt,
+        history: History,
         tx_actioned_prompts: UnboundedSender<ActionedPrompt>,
+        tx_rule_updates: broadcast::Sender<RuleUpdate>,
     ) -> Self {
         Self {
             client,
             reload_handle,
             active_prompt,
+            history,
             tx_actioned_prompts,
+            tx_rule_updates,
         }
     }
 
@@ -128,23 +166,145 @@ where
             panic!("send on closed tx_actioned_prompts channel: {e}");
         }
     }
+
+    /// Reply to the prompt from the UI along with any prompts that the worker has grouped with it,
+    /// optionally applying the same action to all queued prompts from the same snap. Returns the
+    /// response for the UI along with the IDs of any related prompts that were replied to.
+    async fn handle_reply(
+        &self,
+        req: PromptReply,
+        batch: bool,
+    ) -> Result<(PromptReplyResponse, Vec<PromptId>), Status> {
+        let reply = map_prompt_reply(req.clone(), &self.active_prompt)?;
+        let id = PromptId(req.prompt_id.clone());
+        let mut related = self.active_prompt.grouped(&id);
+        if batch {
+            related.extend(self.queued_for_same_snap(&id)?);
+        }
+
+        info!(id=%id.0, "replying to prompt id={}", id.0);
+        let res = match self.client.reply(&id, reply.clone()).await {
+            Ok(mut others) => {
+                self.history.replied(&id, &reply, ReplyOutcome::Success);
+                self.history.resolved_by_rule(&id, &others);
+                // Related prompts covered by a rule created from this reply will have been
+                // actioned by snapd and are included in others
+                let batched = self.reply_to_related(related, &reply, &others).await;
+                others.extend(batched.iter().cloned());
+                self.update_worker(ActionedPrompt::Actioned { id, others })
+                    .await;
+
+                let resp = PromptReplyResponse {
+                    prompt_reply_type: PromptReplyType::Success as i32,
+                    message: "success".to_string(),
+                };
+
+                (resp, batched)
+            }
+
+            Err(e @ Error::SnapdError { status, .. }) if status == StatusCode::NOT_FOUND => {
+                warn!(id=%id.0, "prompt not found (id={})", id.0);
+                self.history.reply_failed(&id, &reply, &e);
+                self.update_worker(ActionedPrompt::NotFound { id }).await;
+
+                let resp = PromptReplyResponse {
+                    prompt_reply_type: PromptReplyType::PromptNotFound as i32,
+                    message: "prompt not found".to_string(),
+                };
+
+                (resp, Vec::new())
+            }
+
+            Err(e) => {
+                warn!(id=%id.0, "got error from snapd when replying to prompt (id={}): {e}", id.0);
+                self.history.reply_failed(&id, &reply, &e);
+                let resp = PromptReplyResponse {
+                    prompt_reply_type: PromptReplyType::Unknown as i32,
+                    message: e.to_string(),
+                };
+
+                (resp, Vec::new())
+            }
+        };
+
+        Ok(res)
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn queued_for_same_snap(&self, id: &PromptId) -> Result<Vec<TypedPrompt>, Status> {
+        let snap = match self.active_prompt.get() {
+            Some(input) if input.id() == id => input.snap().to_string(),
+            _ => {
+                return Err(Status::new(
+                    Code::FailedPrecondition,
+                    "batch replies are only supported for the active prompt",
+                ))
+            }
+        };
+
+        let queued = self.active_prompt.queued().into_iter();
+
+        Ok(queued.filter(|p| p.snap() == snap).collect())
+    }
+
+    /// Reply to each of the related prompts in the same way as the user replied to the active
+    /// prompt, skipping any that snapd has already actioned.
+    async fn reply_to_related(
+        &self,
+        related: Vec<TypedPrompt>,
+        user_reply: &TypedPromptReply,
+        already_actioned: &[PromptId],
+    ) -> Vec<PromptId> {
+        let mut actioned: Vec<PromptId> = Vec::new();
+
+        for prompt in related {
+            let id = prompt.id().clone();
+            if already_actioned.contains(&id) || actioned.contains(&id) {
+                continue;
+            }
+
+            let reply = prompt.into_reply_like(user_reply);
+            match self.client.reply(&id, reply.clone()).await {
+                Ok(others) => {
+                    self.history.replied(&id, &reply, ReplyOutcome::Success);
+                    self.history.resolved_by_rule(&id, &others);
+                    actioned.push(id);
+                    for other in others {
+                        if !actioned.contains(&other) {
+                            actioned.push(other);
+                        }
+                    }
+                }
+                Err(e) => {
+                    warn!(id=%id.0, "unable to apply reply to related prompt (id={}): {e}", id.0);
+                    self.history.reply_failed(&id, &reply, &e);
+                }
+            }
+        }
+
+        actioned
+    }
 }
 
 #[async_trait]
 impl<R, S> AppArmorPrompting for Service<R, S>
 where
-    R: ReplyToPrompt,
+    R: ReplyToPrompt + ManageRules,
     S: SetLogFilter,
 {
+    type WatchPromptsStream =
+        Pin<Box<dyn Stream<Item = Result<WatchPromptsResponse, Status>> + Send>>;
+    type WatchRulesStream = Pin<Box<dyn Stream<Item = Result<ProtoRuleUpdate, Status>> + Send>>;
+
     async fn get_current_prompt(
         &self,
         _request: Request<()>,
     ) -> Result<Response<GetCurrentPromptResponse>, Status> {
         let prompt = match self.active_prompt.get() {
-            Some(TypedUiInput::Home(input)) => {
-                let id = &input.id.0;
+            Some(input) => {
+                let id = &input.id().0;
                 info!(%id, "serving request for active prompt (id={id})");
-                Some(map_home_response(input))
+                Some(map_ui_input(input, Utc::now()))
             }
 
             None => {
@@ -156,57 +316,81 @@ where
         Ok(Response::new(GetCurrentPromptResponse { prompt }))
     }
 
-    async fn reply_to_prompt(
+    async fn get_prompt_queue(
         &self,
-        request: Request<PromptReply>,
-    ) -> Result<Response<PromptReplyResponse>, Status> {
-        let req = request.into_inner();
-        let reply = map_prompt_reply(req.clone())?;
-        let id = PromptId(req.prompt_id.clone());
+        _request: Request<()>,
+    ) -> Result<Response<PromptQueue>, Status> {
+        Ok(Response::new(map_prompt_queue(self.active_prompt.queued())))
+    }
 
-        info!(id=%id.0, "replying to prompt id={}", id.0);
-        let resp = match self.client.reply(&id, reply).await {
-            Ok(others) => {
-                self.update_worker(ActionedPrompt::Actioned { id, others })
-                    .await;
+    async fn watch_prompts(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<Self::WatchPromptsStream>, Status> {
+        info!("new subscriber for prompt updates");
+        let active_prompt = self.active_prompt.clone();
 
-                PromptReplyResponse {
-                    prompt_reply_type: PromptReplyType::Success as i32,
-                    message: "success".to_string(),
-                }
-            }
+        // We only need to know that something has changed as each update contains the full state
+        let changes = WatchStream::new(active_prompt.watch())
+            .map(|_| ())
+            .merge(WatchStream::from_changes(active_prompt.watch_queue()).map(|_| ()));
 
-            Err(Error::SnapdError { status, .. }) if status == StatusCode::NOT_FOUND => {
-                warn!(id=%id.0, "prompt not found (id={})", id.0);
-                self.update_worker(ActionedPrompt::NotFound { id }).await;
+        // Stream items are gRPC results so the Status error is part of the stream type
+        #[allow(clippy::result_large_err)]
+        let stream = changes.map(move |_| {
+            let state = active_prompt.watch().borrow().clone();
+            Ok(map_active_prompt(state, active_prompt.queued(), Utc::now()))
+        });
 
-                PromptReplyResponse {
-                    prompt_reply_type: PromptReplyType::PromptNotFound as i32,
-                    message: "prompt not found".to_string(),
-                }
-            }
+        Ok(Response::new(Box::pin(stream)))
+    }
 
-            Err(e) => {
-                warn!(id=%id.0, "got error from snapd when replying to prompt (id={}): {e}", id.0);
-                PromptReplyResponse {
-                    prompt_reply_type: PromptReplyType::Unknown as i32,
-                    message: e.to_string(),
-                }
-            }
-        };
+    async fn reply_to_prompt(
+        &self,
+        request: Request<PromptReply>,
+    ) -> Result<Response<PromptReplyResponse>, Status> {
+        let (resp, _) = self.handle_reply(request.into_inner(), false).await?;
 
         Ok(Response::new(resp))
     }
 
+    async fn reply_to_prompts(
+        &self,
+        request: Request<PromptReply>,
+    ) -> Result<Response<ReplyToPromptsResponse>, Status> {
+        let (reply, actioned) = self.handle_reply(request.into_inner(), true).await?;
+
+        Ok(Response::new(ReplyToPromptsResponse {
+            reply: Some(reply),
+            actioned_prompt_ids: actioned.into_iter().map(|id| id.0).collect(),
+        }))
+    }
+
     async fn resolve_home_pattern_type(
         &self,
-        _: Request<String>,
+        request: Request<String>,
     ) -> Result<Response<ResolveHomePatternTypeResponse>, Status> {
-        // FIXME: finish this endpoint
-        Err(Status::new(
-            Code::Unimplemented,
-            "this endpoint is not yet implemented",
-        ))
+        let pattern = request.into_inner();
+        let input = match self.active_prompt.get() {
+            Some(TypedUiInput::Home(input)) => input,
+            _ => {
+                return Err(Status::new(
+                    Code::FailedPrecondition,
+                    "there is no active home prompt",
+                ))
+            }
+        };
+
+        match input.data.resolve_pattern_type(&pattern) {
+            Ok(pattern_type) => Ok(Response::new(ResolveHomePatternTypeResponse {
+                home_pattern_type: map_pattern_type(pattern_type) as i32,
+            })),
+
+            Err(e) => {
+                warn!(id=%input.id.0, "unable to resolve pattern type for {pattern:?}: {e}");
+                Err(Status::new(Code::InvalidArgument, e.to_string()))
+            }
+        }
     }
 
     async fn set_logging_filter(
@@ -223,44 +407,339 @@ where
             )),
         }
     }
+
+    async fn list_rules(
+        &self,
+        request: Request<ListRulesRequest>,
+    ) -> Result<Response<ListRulesResponse>, Status> {
+        let ListRulesRequest { snap, interface } = request.into_inner();
+        let snap = (!snap.is_empty()).then_some(snap.as_str());
+        let interface = (!interface.is_empty()).then_some(interface.as_str());
+
+        let rules = self
+            .client
+            .list_rules(snap, interface)
+            .await
+            .map_err(map_rule_error)?;
+
+        Ok(Response::new(ListRulesResponse {
+            rules: rules.iter().map(map_rule).collect(),
+        }))
+    }
+
+    async fn remove_rule(&self, request: Request<String>) -> Result<Response<Rule>, Status> {
+        let id = RuleId(request.into_inner());
+        info!(id=%id.0, "removing rule id={}", id.0);
+
+        match self.client.remove_rule(&id).await {
+            Ok(rule) => Ok(Response::new(map_rule(&rule))),
+            Err(e) => {
+                warn!(id=%id.0, "unable to remove rule (id={}): {e}", id.0);
+                Err(map_rule_error(e))
+            }
+        }
+    }
+
+    async fn update_rule(
+        &self,
+        request: Request<UpdateRuleRequest>,
+    ) -> Result<Response<Rule>, Status> {
+        let req = request.into_inner();
+        let id = RuleId(req.id.clone());
+        let edit = map_rule_edit(req)?;
+        info!(id=%id.0, "updating rule id={}", id.0);
+
+        match self.client.edit_rule(&id, edit).await {
+            Ok(rule) => Ok(Response::new(map_rule(&rule))),
+            Err(e) => {
+                warn!(id=%id.0, "unable to update rule (id={}): {e}", id.0);
+                Err(map_rule_error(e))
+            }
+        }
+    }
+
+    async fn watch_rules(
+        &self,
+        _request: Request<()>,
+    ) -> Result<Response<Self::WatchRulesStream>, Status> {
+        info!("new subscriber for rule updates");
+        let stream = BroadcastStream::new(self.tx_rule_updates.subscribe()).filter_map(|res| {
+            match res {
+                Ok(update) => Some(Ok(map_rule_update(update))),
+                // The UI is expected to call ListRules again if it needs to resync
+                Err(BroadcastStreamRecvError::Lagged(n)) => {
+                    warn!(%n, "rule update subscriber lagged: {n} updates dropped");
+                    None
+                }
+            }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn get_history(
+        &self,
+        request: Request<GetHistoryRequest>,
+    ) -> Result<Response<GetHistoryResponse>, Status> {
+        let Some(path) = self.history.path() else {
+            return Ok(Response::new(GetHistoryResponse::default()));
+        };
+
+        let entries = read_history(path).await.map_err(|e| {
+            warn!("unable to read prompt history: {e}");
+            Status::new(Code::Internal, format!("unable to read history: {e}"))
+        })?;
+
+        Ok(Response::new(map_history(entries, request.into_inner())))
+    }
+}
+
+/// Generate the mapping between the protobuf messages and typed enums for all supported
+/// interfaces.
+///
+/// See [with_interfaces] for how the list of interfaces is defined.
+macro_rules! proto_mapping {
+    (
+        known: [$($variant:ident($iface:ty, $proto:ident)),+ $(,)?],
+        fallback: $fallback:ident($fallback_iface:ty, $fallback_proto:ident) $(,)?
+    ) => {
+        #[allow(clippy::result_large_err)]
+        fn map_prompt_reply(
+            mut reply: PromptReply,
+            active_prompt: &ReadOnlyActivePrompt,
+        ) -> Result<TypedPromptReply, Status> {
+            let prompt_type = reply.prompt_reply.take().ok_or(Status::new(
+                Code::InvalidArgument,
+                "recieved empty prompt_reply",
+            ))?;
+
+            let action = map_action(reply.action());
+            let lifespan = map_lifespan(reply.lifespan());
+            let duration = map_duration(lifespan, reply.duration.take())?;
+
+            let ctx = ReplyContext {
+                prompt_id: &reply.prompt_id,
+                lifespan,
+                active_prompt,
+            };
+
+            let reply = match prompt_type {
+                $(
+                    prompt_reply::PromptReply::$proto(r) => SnapPromptReply::<$iface> {
+                        action,
+                        lifespan,
+                        duration,
+                        constraints: <$iface>::map_reply_constraints(r, &ctx)?,
+                    }
+                    .into(),
+                )+
+
+                prompt_reply::PromptReply::$fallback_proto(r) => {
+                    SnapPromptReply::<$fallback_iface> {
+                        action,
+                        lifespan,
+                        duration,
+                        constraints: <$fallback_iface>::map_reply_constraints(r, &ctx)?,
+                    }
+                    .into()
+                }
+            };
+
+            Ok(reply)
+        }
+
+        fn map_ui_input(input: TypedUiInput, now: DateTime<Utc>) -> Prompt {
+            match input {
+                $(TypedUiInput::$variant(input) => <$iface>::map_response(input, now),)+
+                TypedUiInput::$fallback(input) => <$fallback_iface>::map_response(input, now),
+            }
+        }
+    };
+}
+
+with_interfaces!(proto_mapping);
+
+/// The information available when mapping the reply constraints for a reply from the UI.
+struct ReplyContext<'a> {
+    prompt_id: &'a str,
+    lifespan: snapd_client::Lifespan,
+    active_prompt: &'a ReadOnlyActivePrompt,
+}
+
+/// The mapping between the typed snapd representation of an interface and its protobuf messages.
+trait ProtoInterface: SnapInterface {
+    /// The message used by the UI to reply to prompts for this interface.
+    type ProtoReply;
+
+    #[allow(clippy::result_large_err)]
+    fn map_reply_constraints(
+        reply: Self::ProtoReply,
+        ctx: &ReplyContext<'_>,
+    ) -> Result<Self::ReplyConstraints, Status>;
+
+    /// Map the input for the UI into its protobuf message, with the age of the prompt reported
+    /// relative to `now`.
+    fn map_response(input: UiInput<Self>, now: DateTime<Utc>) -> Prompt;
+}
+
+impl ProtoInterface for HomeInterface {
+    type ProtoReply = apparmor_prompting::HomePromptReply;
+
+    // Custom path patterns are validated against the active prompt so that the user gets
+    // immediate feedback if they have entered an invalid pattern. Replies for anything other than
+    // the active prompt can not be validated and are rejected.
+    fn map_reply_constraints(
+        reply: Self::ProtoReply,
+        ctx: &ReplyContext<'_>,
+    ) -> Result<Self::ReplyConstraints, Status> {
+        let requested_path = match ctx.active_prompt.get() {
+            Some(TypedUiInput::Home(input)) if input.id.0 == ctx.prompt_id => {
+                validate_path_pattern(&reply.path_pattern, &input.data.requested_path)
+                    .map_err(|e| Status::new(Code::InvalidArgument, e.to_string()))?;
+                input.data.requested_path
+            }
+            _ => {
+                return Err(Status::new(
+                    Code::FailedPrecondition,
+                    "home prompt reply does not match the active prompt",
+                ))
+            }
+        };
+
+        Ok(HomeReplyConstraints {
+            path_pattern: reply.path_pattern,
+            permissions: reply.permissions.into_iter().collect(),
+            available_permissions: PermissionSet::new(),
+            requested_path,
+        })
+    }
+
+    fn map_response(input: UiInput<Self>, now: DateTime<Utc>) -> Prompt {
+        map_home_response(input, now)
+    }
 }
 
-fn map_prompt_reply(mut reply: PromptReply) -> Result<TypedPromptReply, Status> {
-    let prompt_type = reply.prompt_reply.take().ok_or(Status::new(
-        Code::InvalidArgument,
-        "recieved empty prompt_reply",
-    ))?;
-
-    Ok(TypedPromptReply::Home(SnapPromptReply::<HomeInterface> {
-        action: map_enum!(
-            apparmor_prompting::Action => snapd_client::Action;
-            [Allow, Deny];
-            reply.action();
-        ),
-        lifespan: map_enum!(
-            apparmor_prompting::Lifespan => snapd_client::Lifespan;
-            [Single, Session, Forever];
-            reply.lifespan();
-        ),
-        duration: None, // we never use the Timespan variant
-        constraints: match prompt_type {
-            HomePromptReply(home_prompt_reply) => HomeReplyConstraints {
-                path_pattern: home_prompt_reply.path_pattern,
-                permissions: home_prompt_reply.permissions,
-                available_permissions: Vec::new(),
-            },
-        },
-    }))
+impl ProtoInterface for CameraInterface {
+    type ProtoReply = apparmor_prompting::CameraPromptReply;
+
+    fn map_reply_constraints(
+        _: Self::ProtoReply,
+        _: &ReplyContext<'_>,
+    ) -> Result<Self::ReplyConstraints, Status> {
+        Ok(DeviceReplyConstraints::default())
+    }
+
+    fn map_response(input: UiInput<Self>, now: DateTime<Utc>) -> Prompt {
+        Prompt::CameraPrompt(CameraPrompt {
+            meta_data: Some(map_meta_data(&input, now)),
+        })
+    }
+}
+
+impl ProtoInterface for AudioRecordInterface {
+    type ProtoReply = apparmor_prompting::AudioRecordPromptReply;
+
+    fn map_reply_constraints(
+        _: Self::ProtoReply,
+        _: &ReplyContext<'_>,
+    ) -> Result<Self::ReplyConstraints, Status> {
+        Ok(DeviceReplyConstraints::default())
+    }
+
+    fn map_response(input: UiInput<Self>, now: DateTime<Utc>) -> Prompt {
+        Prompt::AudioRecordPrompt(AudioRecordPrompt {
+            meta_data: Some(map_meta_data(&input, now)),
+        })
+    }
+}
+
+impl ProtoInterface for UnknownInterface {
+    type ProtoReply = apparmor_prompting::GenericPromptReply;
+
+    // We have no typed model for the reply constraints of a generic prompt so they are
+    // generated from the raw constraints of the prompt currently being shown in the UI.
+    fn map_reply_constraints(
+        _: Self::ProtoReply,
+        ctx: &ReplyContext<'_>,
+    ) -> Result<Self::ReplyConstraints, Status> {
+        if ctx.lifespan != snapd_client::Lifespan::Single {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                "only single replies are supported for generic prompts",
+            ));
+        }
+
+        match ctx.active_prompt.get() {
+            Some(TypedUiInput::Unknown(input)) if input.id.0 == ctx.prompt_id => {
+                Ok(input.data.reply_constraints())
+            }
+            _ => Err(Status::new(
+                Code::InvalidArgument,
+                "generic prompt reply does not match the active prompt",
+            )),
+        }
+    }
+
+    fn map_response(input: UiInput<Self>, now: DateTime<Utc>) -> Prompt {
+        let constraints = input
+            .data
+            .constraint_pairs()
+            .into_iter()
+            .map(|(key, value)| Constraint { key, value })
+            .collect();
+
+        Prompt::GenericPrompt(GenericPrompt {
+            meta_data: Some(map_meta_data(&input, now)),
+            interface: input.data.interface,
+            constraints,
+        })
+    }
 }
 
-fn map_home_response(input: UiInput<HomeInterface>) -> Prompt {
+fn map_meta_data<I: SnapInterface>(input: &UiInput<I>, now: DateTime<Utc>) -> MetaData {
     let SnapMeta {
         name,
         updated_at,
         store_url,
         publisher,
-    } = input.meta;
+        publisher_validation,
+        version,
+        revision,
+        channel,
+        confinement,
+        title,
+        summary,
+        icon_api_path,
+    } = input.meta.clone();
+
+    let publisher_validation = match publisher_validation {
+        snapd_client::PublisherValidation::Verified => ProtoPublisherValidation::Verified,
+        snapd_client::PublisherValidation::Starred => ProtoPublisherValidation::Starred,
+        snapd_client::PublisherValidation::Unproven => ProtoPublisherValidation::Unproven,
+        snapd_client::PublisherValidation::Unknown => ProtoPublisherValidation::Unknown,
+    };
+
+    MetaData {
+        prompt_id: input.id.0.clone(),
+        snap_name: name,
+        store_url,
+        publisher,
+        updated_at,
+        timestamp: map_timestamp(input.timestamp),
+        age_ms: input.age(now).num_milliseconds() as u64,
+        publisher_validation: publisher_validation as i32,
+        version,
+        revision,
+        channel,
+        confinement,
+        title,
+        summary,
+        icon_api_path,
+    }
+}
 
+fn map_home_response(input: UiInput<HomeInterface>, now: DateTime<Utc>) -> Prompt {
+    let meta_data = map_meta_data(&input, now);
     let HomeUiInputData {
         requested_path,
         home_dir,
@@ -272,18 +751,12 @@ fn map_home_response(input: UiInput<HomeInterface>) -> Prompt {
     } = input.data;
 
     Prompt::HomePrompt(HomePrompt {
-        meta_data: Some(MetaData {
-            prompt_id: input.id.0,
-            snap_name: name,
-            store_url,
-            publisher,
-            updated_at,
-        }),
+        meta_data: Some(meta_data),
         requested_path,
         home_dir,
-        requested_permissions,
-        suggested_permissions,
-        available_permissions,
+        requested_permissions: requested_permissions.to_vec(),
+        suggested_permissions: suggested_permissions.to_vec(),
+        available_permissions: available_permissions.to_vec(),
         initial_pattern_option: initial_pattern_option as i32,
         pattern_options: pattern_options
             .into_iter()
@@ -292,46 +765,309 @@ fn map_home_response(input: UiInput<HomeInterface>) -> Prompt {
     })
 }
 
-fn map_pattern_option(
-    TypedPathPattern {
-        pattern_type,
-        path_pattern,
-        show_initially,
-    }: TypedPathPattern,
-) -> PatternOption {
-    let home_pattern_type = map_enum!(
-        PatternType => HomePatternType;
-        [
-            RequestedDirectory, RequestedFile, TopLevelDirectory,
-            HomeDirectory, MatchingFileExtension, ContainingDirectory,
-            RequestedDirectoryContents
-        ];
-        pattern_type;
+fn map_action(action: apparmor_prompting::Action) -> snapd_client::Action {
+    map_enum!(
+        apparmor_prompting::Action => snapd_client::Action;
+        [Allow, Deny];
+        action;
+    )
+}
+
+fn map_lifespan(lifespan: apparmor_prompting::Lifespan) -> snapd_client::Lifespan {
+    map_enum!(
+        apparmor_prompting::Lifespan => snapd_client::Lifespan;
+        [Single, Session, Forever, Timespan];
+        lifespan;
+    )
+}
+
+fn map_rule(rule: &TypedRule) -> Rule {
+    let outcome = map_enum!(
+        snapd_client::Action => apparmor_prompting::Action;
+        [Allow, Deny];
+        rule.outcome();
+    );
+    let lifespan = map_enum!(
+        snapd_client::Lifespan => apparmor_prompting::Lifespan;
+        [Single, Session, Forever, Timespan];
+        rule.lifespan();
     );
 
-    PatternOption {
-        home_pattern_type: home_pattern_type as i32,
-        path_pattern,
-        show_initially,
+    Rule {
+        id: rule.id().0.clone(),
+        timestamp: map_timestamp(rule.timestamp()),
+        snap: rule.snap().to_string(),
+        interface: rule.interface().to_string(),
+        outcome: outcome as i32,
+        lifespan: lifespan as i32,
+        expiration: rule.expiration().map(map_timestamp).unwrap_or_default(),
+        permissions: rule.permissions(),
+        path_pattern: rule.path_pattern().unwrap_or_default().to_string(),
     }
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use crate::{
-        daemon::worker::ReadOnlyActivePrompt,
-        protos::apparmor_prompting::{
-            app_armor_prompting_client::AppArmorPromptingClient, prompt_reply, Action, Lifespan,
-        },
-        snapd_client::{interfaces::home::HomeUiInputData, PromptId, SnapMeta, TypedPromptReply},
-        Error,
-    };
-    use hyper_util::rt::TokioIo;
-    use simple_test_case::test_case;
-    use std::{
-        fs, io,
-        ops::{Deref, DerefMut},
+/// Timestamps are sent as RFC 3339 strings, with as much sub-second precision as is needed to
+/// represent them exactly.
+fn map_timestamp(ts: DateTime<Utc>) -> String {
+    ts.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+}
+
+fn map_active_prompt(
+    state: ActivePrompt,
+    queue: Vec<TypedPrompt>,
+    now: DateTime<Utc>,
+) -> WatchPromptsResponse {
+    let (status, prompt_id, current_prompt) = match state {
+        ActivePrompt::Empty => (PromptStatus::NoPrompt, String::new(), None),
+        ActivePrompt::Active { input, .. } => (
+            PromptStatus::Active,
+            input.id().0.clone(),
+            Some(GetCurrentPromptResponse {
+                prompt: Some(map_ui_input(input, now)),
+            }),
+        ),
+        ActivePrompt::Actioned(id) => (PromptStatus::Actioned, id.0, None),
+        ActivePrompt::Dropped(id) => (PromptStatus::Dropped, id.0, None),
+        ActivePrompt::TimedOut(id) => (PromptStatus::TimedOut, id.0, None),
+    };
+
+    WatchPromptsResponse {
+        status: status as i32,
+        prompt_id,
+        current_prompt,
+        queue: Some(map_prompt_queue(queue)),
+    }
+}
+
+fn map_prompt_queue(queue: Vec<TypedPrompt>) -> PromptQueue {
+    let prompts: Vec<QueuedPrompt> = queue
+        .iter()
+        .map(|p| QueuedPrompt {
+            prompt_id: p.id().0.clone(),
+            snap_name: p.snap().to_string(),
+            interface: p.interface().to_string(),
+            requested_path: p.requested_path().unwrap_or_default().to_string(),
+            requested_permissions: p.requested_permissions(),
+        })
+        .collect();
+
+    PromptQueue { prompts }
+}
+
+/// Filter the recorded history as requested, returning the most recent entries first.
+fn map_history(entries: Vec<HistoryEntry>, req: GetHistoryRequest) -> GetHistoryResponse {
+    let limit = match req.limit {
+        0 => usize::MAX,
+        n => n as usize,
+    };
+
+    let entries = entries
+        .into_iter()
+        .rev()
+        .filter(|e| req.snap.is_empty() || e.snap == req.snap)
+        .filter(|e| !req.replies_only || matches!(e.event, HistoryEvent::Replied { .. }))
+        .take(limit)
+        .map(map_history_entry)
+        .collect();
+
+    GetHistoryResponse { entries }
+}
+
+fn map_history_entry(entry: HistoryEntry) -> ProtoHistoryEntry {
+    let mut proto = ProtoHistoryEntry {
+        timestamp: map_timestamp(entry.timestamp),
+        prompt_id: entry.id.0,
+        snap: entry.snap,
+        interface: entry.interface,
+        ..Default::default()
+    };
+
+    let event = match entry.event {
+        HistoryEvent::Prompted { constraints } => {
+            proto.constraints = constraints.to_string();
+            history_entry::HistoryEvent::Prompted
+        }
+        HistoryEvent::Displayed => history_entry::HistoryEvent::Displayed,
+        HistoryEvent::Replied {
+            action,
+            lifespan,
+            path_pattern,
+            permissions,
+            latency_ms,
+            outcome,
+        } => {
+            let action = map_enum!(
+                snapd_client::Action => apparmor_prompting::Action;
+                [Allow, Deny];
+                action;
+            );
+            let lifespan = map_enum!(
+                snapd_client::Lifespan => apparmor_prompting::Lifespan;
+                [Single, Session, Forever, Timespan];
+                lifespan;
+            );
+            let outcome = map_enum!(
+                history::ReplyOutcome => history_entry::ReplyOutcome;
+                [Success, NotFound, TimeoutDeny, MappingFailureDeny, Failed];
+                outcome;
+            );
+            proto.action = action as i32;
+            proto.lifespan = lifespan as i32;
+            proto.path_pattern = path_pattern.unwrap_or_default();
+            proto.permissions = permissions;
+            proto.latency_ms = latency_ms;
+            proto.outcome = outcome as i32;
+            history_entry::HistoryEvent::Replied
+        }
+        HistoryEvent::ResolvedByRule { by } => {
+            proto.resolved_by = by.0;
+            history_entry::HistoryEvent::ResolvedByRule
+        }
+        HistoryEvent::Dropped => history_entry::HistoryEvent::Dropped,
+    };
+    proto.event = event as i32;
+
+    proto
+}
+
+fn map_rule_update(update: RuleUpdate) -> ProtoRuleUpdate {
+    let (id, update_type, rule) = match update {
+        RuleUpdate::Changed(rule) => (
+            rule.id().0.clone(),
+            RuleUpdateType::Changed,
+            Some(map_rule(&rule)),
+        ),
+        RuleUpdate::Removed(id) => (id.0, RuleUpdateType::Removed, None),
+        RuleUpdate::Expired(id) => (id.0, RuleUpdateType::Expired, None),
+    };
+
+    ProtoRuleUpdate {
+        id,
+        update_type: update_type as i32,
+        rule,
+    }
+}
+
+/// Timespan lifespans require a valid, non-zero duration which is not permitted for any other
+/// lifespan.
+#[allow(clippy::result_large_err)]
+fn map_duration(
+    lifespan: snapd_client::Lifespan,
+    duration: Option<String>,
+) -> Result<Option<GoDuration>, Status> {
+    let invalid = |msg: &str| Err(Status::new(Code::InvalidArgument, msg));
+
+    match (lifespan, duration) {
+        (snapd_client::Lifespan::Timespan, None) => {
+            invalid("a duration is required for a timespan lifespan")
+        }
+        (snapd_client::Lifespan::Timespan, Some(raw)) => match GoDuration::parse(raw) {
+            Ok(d) if d.as_duration().is_zero() => {
+                invalid("a timespan duration must be greater than zero")
+            }
+            Ok(d) => Ok(Some(d)),
+            Err(e) => invalid(&e.to_string()),
+        },
+        (_, Some(_)) => invalid("a duration is only valid for a timespan lifespan"),
+        (_, None) => Ok(None),
+    }
+}
+
+#[allow(clippy::result_large_err)]
+fn map_rule_edit(req: UpdateRuleRequest) -> Result<RuleEdit, Status> {
+    let outcome = req.outcome.map(|_| map_action(req.outcome()));
+    let lifespan = req.lifespan.map(|_| map_lifespan(req.lifespan()));
+
+    // A duration is only applied to the rule along with a new lifespan
+    let duration = match lifespan {
+        Some(snapd_client::Lifespan::Single) => {
+            return Err(Status::new(
+                Code::InvalidArgument,
+                "rules can not have a single lifespan",
+            ))
+        }
+        Some(lifespan) => map_duration(lifespan, req.duration)?,
+        None => None,
+    };
+
+    Ok(RuleEdit {
+        outcome,
+        lifespan,
+        duration,
+        path_pattern: req.path_pattern,
+        permissions: (!req.permissions.is_empty()).then_some(req.permissions),
+    })
+}
+
+fn map_rule_error(e: Error) -> Status {
+    match e {
+        Error::SnapdError { status, message } if status == StatusCode::NOT_FOUND => {
+            Status::new(Code::NotFound, message)
+        }
+        Error::SnapdError { status, message } if status == StatusCode::BAD_REQUEST => {
+            Status::new(Code::InvalidArgument, message)
+        }
+        e @ (Error::InvalidRuleEdit { .. } | Error::InvalidRuleFilter { .. }) => {
+            Status::new(Code::InvalidArgument, e.to_string())
+        }
+        e => Status::new(Code::Internal, e.to_string()),
+    }
+}
+
+fn map_pattern_type(pattern_type: PatternType) -> HomePatternType {
+    map_enum!(
+        PatternType => HomePatternType;
+        [
+            RequestedDirectory, RequestedFile, TopLevelDirectory,
+            HomeDirectory, MatchingFileExtension, ContainingDirectory,
+            RequestedDirectoryContents
+        ];
+        pattern_type;
+    )
+}
+
+fn map_pattern_option(
+    TypedPathPattern {
+        pattern_type,
+        path_pattern,
+        show_initially,
+    }: TypedPathPattern,
+) -> PatternOption {
+    PatternOption {
+        home_pattern_type: map_pattern_type(pattern_type) as i32,
+        path_pattern,
+        show_initially,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        daemon::worker::ReadOnlyActivePrompt,
+        protos::apparmor_prompting::{
+            app_armor_prompting_client::AppArmorPromptingClient,
+            prompt_reply::PromptReply::{
+                AudioRecordPromptReply, CameraPromptReply, GenericPromptReply, HomePromptReply,
+            },
+            Action, Lifespan,
+        },
+        snapd_client::{
+            interfaces::{
+                device::DeviceUiInputData,
+                home::{HomeConstraints, HomeUiInputData},
+                unknown::UnknownUiInputData,
+            },
+            PromptId, SnapMeta, TypedPromptReply,
+        },
+        Error,
+    };
+    use hyper_util::rt::TokioIo;
+    use simple_test_case::test_case;
+    use std::{
+        fs, io,
+        ops::{Deref, DerefMut},
+        sync::{Arc, Mutex},
     };
     use tokio::{
         net::UnixStream,
@@ -350,15 +1086,22 @@ mod tests {
     struct MockClient {
         want_err: bool,
         expected_reply: Option<TypedPromptReply>,
+        expected_edit: Option<RuleEdit>,
+        replied: Arc<Mutex<Vec<(PromptId, TypedPromptReply)>>>,
     }
 
     #[async_trait]
+    #[allow(clippy::io_other_error)]
     impl ReplyToPrompt for MockClient {
         async fn reply(
             &self,
-            _id: &PromptId,
+            id: &PromptId,
             reply: TypedPromptReply,
         ) -> crate::Result<Vec<PromptId>> {
+            self.replied
+                .lock()
+                .unwrap()
+                .push((id.clone(), reply.clone()));
             if self.want_err {
                 return Err(Error::Io(io::Error::new(
                     io::ErrorKind::Other,
@@ -370,6 +1113,24 @@ mod tests {
                     (TypedPromptReply::Home(reply), TypedPromptReply::Home(expected_reply)) => {
                         assert_eq!(reply, expected_reply, "Replies did not match");
                     }
+                    (TypedPromptReply::Camera(reply), TypedPromptReply::Camera(expected_reply)) => {
+                        assert_eq!(reply, expected_reply, "Replies did not match");
+                    }
+                    (
+                        TypedPromptReply::AudioRecord(reply),
+                        TypedPromptReply::AudioRecord(expected_reply),
+                    ) => {
+                        assert_eq!(reply, expected_reply, "Replies did not match");
+                    }
+                    (
+                        TypedPromptReply::Unknown(reply),
+                        TypedPromptReply::Unknown(expected_reply),
+                    ) => {
+                        assert_eq!(reply, expected_reply, "Replies did not match");
+                    }
+                    (reply, expected_reply) => {
+                        panic!("Replies did not match: {reply:?} != {expected_reply:?}")
+                    }
                 }
             }
 
@@ -377,11 +1138,53 @@ mod tests {
         }
     }
 
+    #[async_trait]
+    #[allow(clippy::io_other_error)]
+    impl ManageRules for MockClient {
+        async fn list_rules(
+            &self,
+            snap: Option<&str>,
+            interface: Option<&str>,
+        ) -> crate::Result<Vec<TypedRule>> {
+            if self.want_err {
+                return Err(Error::Io(io::Error::new(
+                    io::ErrorKind::Other,
+                    "error requested of mock snapd client",
+                )));
+            }
+
+            Ok(rules()
+                .into_iter()
+                .filter(|r| snap.is_none_or(|s| s == r.snap()))
+                .filter(|r| interface.is_none_or(|i| i == r.interface()))
+                .collect())
+        }
+
+        async fn remove_rule(&self, id: &RuleId) -> crate::Result<TypedRule> {
+            rules()
+                .into_iter()
+                .find(|r| r.id() == id)
+                .ok_or(Error::SnapdError {
+                    status: StatusCode::NOT_FOUND,
+                    message: "rule not found".to_string(),
+                })
+        }
+
+        async fn edit_rule(&self, id: &RuleId, edit: RuleEdit) -> crate::Result<TypedRule> {
+            if let Some(expected_edit) = self.expected_edit.clone() {
+                assert_eq!(edit, expected_edit, "Edits did not match");
+            }
+
+            self.remove_rule(id).await
+        }
+    }
+
     // Ensure that our test sockets get cleaned up when the client is dropped
     #[derive(Debug)]
     struct SelfCleaningClient {
         inner: AppArmorPromptingClient<Channel>,
         socket_path: String,
+        tx_rule_updates: broadcast::Sender<RuleUpdate>,
     }
 
     impl Drop for SelfCleaningClient {
@@ -419,12 +1222,15 @@ mod tests {
         let test_name = Uuid::new_v4().to_string();
         let socket_path = format!("/tmp/{test_name}_socket");
         let _ = fs::remove_file(&socket_path); // Remove the old socket file if it exists
+        let (tx_rule_updates, _) = broadcast::channel(16);
 
         let (server, listener) = new_server_and_listener(
             mock_client,
             MockReloadHandle,
             active_prompt,
+            History::default(),
             tx_actioned_prompts,
+            tx_rule_updates.clone(),
             socket_path.clone(),
         );
 
@@ -449,24 +1255,44 @@ mod tests {
         SelfCleaningClient {
             inner: AppArmorPromptingClient::new(channel),
             socket_path,
+            tx_rule_updates,
         }
     }
 
+    const TIMESTAMP: &str = "2024-08-14T07:28:22.694800024Z";
+
+    /// The age of a prompt depends on the current time when it is served over gRPC.
+    fn without_age(mut prompt: Prompt) -> Prompt {
+        let meta_data = match &mut prompt {
+            Prompt::HomePrompt(p) => &mut p.meta_data,
+            Prompt::CameraPrompt(p) => &mut p.meta_data,
+            Prompt::AudioRecordPrompt(p) => &mut p.meta_data,
+            Prompt::GenericPrompt(p) => &mut p.meta_data,
+        };
+        if let Some(meta_data) = meta_data {
+            meta_data.age_ms = 0;
+        }
+
+        prompt
+    }
+
     fn ui_input() -> TypedUiInput {
         TypedUiInput::Home(UiInput::<HomeInterface> {
             id: PromptId("1".to_string()),
+            timestamp: TIMESTAMP.parse().unwrap(),
             meta: SnapMeta {
                 name: "2".to_string(),
                 updated_at: "3".to_string(),
                 store_url: "4".to_string(),
                 publisher: "5".to_string(),
+                ..Default::default()
             },
             data: HomeUiInputData {
                 requested_path: "6".to_string(),
                 home_dir: "7".to_string(),
-                requested_permissions: Vec::new(),
-                available_permissions: Vec::new(),
-                suggested_permissions: Vec::new(),
+                requested_permissions: PermissionSet::new(),
+                available_permissions: PermissionSet::new(),
+                suggested_permissions: PermissionSet::new(),
                 pattern_options: Vec::new(),
                 initial_pattern_option: 0,
             },
@@ -481,6 +1307,9 @@ mod tests {
                 store_url: "4".to_string(),
                 publisher: "5".to_string(),
                 updated_at: "3".to_string(),
+                timestamp: TIMESTAMP.to_string(),
+                age_ms: 0,
+                ..Default::default()
             }),
             requested_path: "6".to_string(),
             home_dir: "7".to_string(),
@@ -492,18 +1321,75 @@ mod tests {
         })
     }
 
+    /// UI input for an arbitrary interface sharing the snap metadata used by [prompt_meta_data].
+    fn ui_input_with<I: SnapInterface>(data: I::UiInputData) -> UiInput<I> {
+        UiInput {
+            id: PromptId("1".to_string()),
+            timestamp: TIMESTAMP.parse().unwrap(),
+            meta: SnapMeta {
+                name: "2".to_string(),
+                updated_at: "3".to_string(),
+                store_url: "4".to_string(),
+                publisher: "5".to_string(),
+                ..Default::default()
+            },
+            data,
+        }
+    }
+
+    fn prompt_meta_data() -> Option<MetaData> {
+        Some(MetaData {
+            prompt_id: "1".to_string(),
+            snap_name: "2".to_string(),
+            store_url: "4".to_string(),
+            publisher: "5".to_string(),
+            updated_at: "3".to_string(),
+            timestamp: TIMESTAMP.to_string(),
+            age_ms: 0,
+            ..Default::default()
+        })
+    }
+
+    fn generic_ui_input() -> TypedUiInput {
+        TypedUiInput::Unknown(ui_input_with(UnknownUiInputData {
+            interface: "6".to_string(),
+            constraints: serde_json::json!({
+                "path": "/dev/foo",
+                "requested-permissions": ["read"],
+            }),
+        }))
+    }
+
+    fn generic_prompt() -> Prompt {
+        Prompt::GenericPrompt(GenericPrompt {
+            meta_data: prompt_meta_data(),
+            interface: "6".to_string(),
+            constraints: vec![
+                Constraint {
+                    key: "path".to_string(),
+                    value: "/dev/foo".to_string(),
+                },
+                Constraint {
+                    key: "requested-permissions".to_string(),
+                    value: "read".to_string(),
+                },
+            ],
+        })
+    }
+
     fn prompt_reply(prompt_reply_inner: Option<prompt_reply::PromptReply>) -> PromptReply {
         PromptReply {
             prompt_id: "1".to_string(),
             action: Action::Allow as i32,
             lifespan: Lifespan::Single as i32,
             prompt_reply: prompt_reply_inner,
+            duration: None,
         }
     }
 
     fn prompt_reply_inner() -> Option<prompt_reply::PromptReply> {
         Some(HomePromptReply(apparmor_prompting::HomePromptReply {
-            path_pattern: "6".to_string(),
+            path_pattern: "/home/user/Documents/**".to_string(),
             permissions: Vec::new(),
         }))
     }
@@ -514,9 +1400,10 @@ mod tests {
             lifespan: snapd_client::Lifespan::Single,
             duration: None,
             constraints: HomeReplyConstraints {
-                path_pattern: "6".to_string(),
-                permissions: Vec::new(),
-                available_permissions: Vec::new(),
+                path_pattern: "/home/user/Documents/**".to_string(),
+                permissions: PermissionSet::new(),
+                available_permissions: PermissionSet::new(),
+                requested_path: "/home/user/Documents/foo.txt".to_string(),
             },
         })
     }
@@ -529,11 +1416,24 @@ mod tests {
 
     #[test_case(None, None; "empty prompt")]
     #[test_case(Some(ui_input()), Some(prompt()); "non-empty prompt")]
+    #[test_case(
+        Some(TypedUiInput::Camera(ui_input_with(DeviceUiInputData {}))),
+        Some(Prompt::CameraPrompt(CameraPrompt { meta_data: prompt_meta_data() }));
+        "non-empty camera prompt"
+    )]
+    #[test_case(
+        Some(TypedUiInput::AudioRecord(ui_input_with(DeviceUiInputData {}))),
+        Some(Prompt::AudioRecordPrompt(AudioRecordPrompt { meta_data: prompt_meta_data() }));
+        "non-empty audio record prompt"
+    )]
+    #[test_case(Some(generic_ui_input()), Some(generic_prompt()); "non-empty generic prompt")]
     #[tokio::test]
     async fn test_get_current_prompt(ui_input: Option<TypedUiInput>, expected: Option<Prompt>) {
         let mock_client = MockClient {
             want_err: false,
+            replied: Default::default(),
             expected_reply: None,
+            expected_edit: None,
         };
         let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
         let active_prompt = ReadOnlyActivePrompt::new(ui_input);
@@ -545,7 +1445,8 @@ mod tests {
             .await
             .unwrap()
             .into_inner()
-            .prompt;
+            .prompt
+            .map(without_age);
 
         assert_eq!(resp, expected);
     }
@@ -558,14 +1459,17 @@ mod tests {
     async fn test_reply_to_prompt(prompt_reply: PromptReply, expected_errors: ExpectedErrors) {
         let mock_client = MockClient {
             want_err: expected_errors.snapd_err,
+            replied: Default::default(),
             expected_reply: Some(typed_prompt_reply()),
+            expected_edit: None,
         };
         let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
         let mut rx_actioned_prompts = Some(rx_actioned_prompts);
         if expected_errors.tx_err {
             rx_actioned_prompts = None;
         }
-        let active_prompt = ReadOnlyActivePrompt::new(None);
+        let active_prompt =
+            ReadOnlyActivePrompt::new(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")));
         let mut client =
             setup_server_and_client(mock_client, active_prompt, tx_actioned_prompts).await;
 
@@ -594,4 +1498,874 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn map_prompt_reply_for_camera_uses_access_permission() {
+        let reply = prompt_reply(Some(CameraPromptReply(
+            apparmor_prompting::CameraPromptReply {},
+        )));
+
+        let expected = TypedPromptReply::Camera(SnapPromptReply::<CameraInterface> {
+            action: snapd_client::Action::Allow,
+            lifespan: snapd_client::Lifespan::Single,
+            duration: None,
+            constraints: DeviceReplyConstraints {
+                permissions: vec!["access".to_string()],
+            },
+        });
+
+        assert_eq!(
+            map_prompt_reply(reply, &ReadOnlyActivePrompt::new(None)).unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn map_prompt_reply_for_audio_record_uses_access_permission() {
+        let reply = prompt_reply(Some(AudioRecordPromptReply(
+            apparmor_prompting::AudioRecordPromptReply {},
+        )));
+
+        let expected = TypedPromptReply::AudioRecord(SnapPromptReply::<AudioRecordInterface> {
+            action: snapd_client::Action::Allow,
+            lifespan: snapd_client::Lifespan::Single,
+            duration: None,
+            constraints: DeviceReplyConstraints {
+                permissions: vec!["access".to_string()],
+            },
+        });
+
+        assert_eq!(
+            map_prompt_reply(reply, &ReadOnlyActivePrompt::new(None)).unwrap(),
+            expected
+        );
+    }
+
+    #[test_case(Lifespan::Single, Some(generic_ui_input()), true; "single reply to active generic prompt")]
+    #[test_case(Lifespan::Forever, Some(generic_ui_input()), false; "forever reply to active generic prompt")]
+    #[test_case(Lifespan::Single, Some(ui_input()), false; "single reply to active home prompt")]
+    #[test_case(Lifespan::Single, None, false; "single reply without an active prompt")]
+    #[test]
+    fn map_prompt_reply_for_generic_prompt(
+        lifespan: Lifespan,
+        active_prompt: Option<TypedUiInput>,
+        expect_ok: bool,
+    ) {
+        let mut reply = prompt_reply(Some(GenericPromptReply(
+            apparmor_prompting::GenericPromptReply {},
+        )));
+        reply.lifespan = lifespan as i32;
+
+        let res = map_prompt_reply(reply, &ReadOnlyActivePrompt::new(active_prompt));
+
+        if !expect_ok {
+            assert!(res.is_err(), "expected an error, got {res:?}");
+            return;
+        }
+
+        let expected = TypedPromptReply::Unknown(SnapPromptReply::<UnknownInterface> {
+            action: snapd_client::Action::Allow,
+            lifespan: snapd_client::Lifespan::Single,
+            duration: None,
+            constraints: serde_json::json!({
+                "path-pattern": "/dev/foo",
+                "permissions": ["read"],
+            }),
+        });
+
+        assert_eq!(res.unwrap(), expected);
+    }
+
+    fn home_ui_input_for_path(path: &str) -> TypedUiInput {
+        TypedUiInput::Home(UiInput::<HomeInterface> {
+            id: PromptId("1".to_string()),
+            timestamp: Default::default(),
+            meta: SnapMeta::default(),
+            data: HomeUiInputData {
+                requested_path: path.to_string(),
+                home_dir: "/home/user".to_string(),
+                requested_permissions: PermissionSet::new(),
+                available_permissions: PermissionSet::new(),
+                suggested_permissions: PermissionSet::new(),
+                pattern_options: Vec::new(),
+                initial_pattern_option: 0,
+            },
+        })
+    }
+
+    #[test_case(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")), "/home/user/Documents/**", Ok(HomePatternType::TopLevelDirectory); "top level directory")]
+    #[test_case(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")), "/home/user/**/*.txt", Ok(HomePatternType::MatchingFileExtension); "matching extension")]
+    #[test_case(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")), "/home/user/Pictures/**", Err(Code::InvalidArgument); "pattern not matching path")]
+    #[test_case(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")), "/**", Err(Code::InvalidArgument); "pattern escaping home")]
+    #[test_case(Some(TypedUiInput::Camera(ui_input_with(DeviceUiInputData {}))), "/home/user/**", Err(Code::FailedPrecondition); "active camera prompt")]
+    #[test_case(None, "/home/user/**", Err(Code::FailedPrecondition); "no active prompt")]
+    #[tokio::test]
+    async fn test_resolve_home_pattern_type(
+        ui_input: Option<TypedUiInput>,
+        pattern: &str,
+        expected: Result<HomePatternType, Code>,
+    ) {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let active_prompt = ReadOnlyActivePrompt::new(ui_input);
+        let mut client =
+            setup_server_and_client(mock_client, active_prompt, tx_actioned_prompts).await;
+
+        let res = client
+            .resolve_home_pattern_type(Request::new(pattern.to_string()))
+            .await
+            .map(|resp| resp.into_inner().home_pattern_type())
+            .map_err(|status| status.code());
+
+        assert_eq!(res, expected);
+    }
+
+    #[test_case("/home/user/Documents/**", true; "valid pattern")]
+    #[test_case("/home/user/Pictures/**", false; "pattern not matching path")]
+    #[test_case("Documents/**", false; "relative pattern")]
+    #[test]
+    fn map_prompt_reply_validates_home_path_patterns(pattern: &str, expect_ok: bool) {
+        let reply = prompt_reply(Some(HomePromptReply(apparmor_prompting::HomePromptReply {
+            path_pattern: pattern.to_string(),
+            permissions: vec!["read".to_string()],
+        })));
+        let active_prompt =
+            ReadOnlyActivePrompt::new(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")));
+
+        match map_prompt_reply(reply, &active_prompt) {
+            Ok(TypedPromptReply::Home(reply)) if expect_ok => {
+                assert_eq!(reply.constraints.path_pattern, pattern);
+                assert_eq!(
+                    reply.constraints.requested_path,
+                    "/home/user/Documents/foo.txt"
+                );
+            }
+            Err(status) if !expect_ok => assert_eq!(status.code(), Code::InvalidArgument),
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
+
+    #[test_case(None; "no active prompt")]
+    #[test_case(Some(generic_ui_input()); "active prompt for another interface")]
+    #[test]
+    fn map_prompt_reply_requires_an_active_home_prompt(active_prompt: Option<TypedUiInput>) {
+        let res = map_prompt_reply(
+            prompt_reply(prompt_reply_inner()),
+            &ReadOnlyActivePrompt::new(active_prompt),
+        );
+
+        match res {
+            Err(status) => assert_eq!(status.code(), Code::FailedPrecondition),
+            res => panic!("expected an error, got {res:?}"),
+        }
+    }
+
+    fn rules() -> Vec<TypedRule> {
+        serde_json::from_value(serde_json::json!([
+            {
+                "id": "0000000000000002",
+                "timestamp": "2024-08-14T07:28:22.694800024Z",
+                "snap": "firefox",
+                "interface": "home",
+                "constraints": {
+                    "path-pattern": "/home/ubuntu/Downloads/**",
+                    "permissions": ["read", "write"]
+                },
+                "outcome": "allow",
+                "lifespan": "forever"
+            },
+            {
+                "id": "0000000000000003",
+                "timestamp": "2024-08-14T07:30:22.694800024Z",
+                "snap": "cheese",
+                "interface": "camera",
+                "constraints": { "permissions": ["access"] },
+                "outcome": "deny",
+                "lifespan": "timespan",
+                "expiration": "2024-08-14T08:30:22.694800024Z"
+            }
+        ]))
+        .unwrap()
+    }
+
+    fn home_rule() -> Rule {
+        Rule {
+            id: "0000000000000002".to_string(),
+            timestamp: "2024-08-14T07:28:22.694800024Z".to_string(),
+            snap: "firefox".to_string(),
+            interface: "home".to_string(),
+            outcome: Action::Allow as i32,
+            lifespan: Lifespan::Forever as i32,
+            expiration: String::new(),
+            permissions: vec!["read".to_string(), "write".to_string()],
+            path_pattern: "/home/ubuntu/Downloads/**".to_string(),
+        }
+    }
+
+    fn camera_rule() -> Rule {
+        Rule {
+            id: "0000000000000003".to_string(),
+            timestamp: "2024-08-14T07:30:22.694800024Z".to_string(),
+            snap: "cheese".to_string(),
+            interface: "camera".to_string(),
+            outcome: Action::Deny as i32,
+            lifespan: Lifespan::Timespan as i32,
+            expiration: "2024-08-14T08:30:22.694800024Z".to_string(),
+            permissions: vec!["access".to_string()],
+            path_pattern: String::new(),
+        }
+    }
+
+    #[test_case("", "", false, Ok(vec![home_rule(), camera_rule()]); "no filter")]
+    #[test_case("cheese", "", false, Ok(vec![camera_rule()]); "filter by snap")]
+    #[test_case("", "home", false, Ok(vec![home_rule()]); "filter by interface")]
+    #[test_case("", "", true, Err(Code::Internal); "snapd error")]
+    #[tokio::test]
+    async fn test_list_rules(
+        snap: &str,
+        interface: &str,
+        want_err: bool,
+        expected: Result<Vec<Rule>, Code>,
+    ) {
+        let mock_client = MockClient {
+            want_err,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let mut client = setup_server_and_client(
+            mock_client,
+            ReadOnlyActivePrompt::new(None),
+            tx_actioned_prompts,
+        )
+        .await;
+
+        let req = ListRulesRequest {
+            snap: snap.to_string(),
+            interface: interface.to_string(),
+        };
+        let res = client
+            .list_rules(Request::new(req))
+            .await
+            .map(|resp| resp.into_inner().rules)
+            .map_err(|status| status.code());
+
+        assert_eq!(res, expected);
+    }
+
+    #[test_case("0000000000000002", Ok(home_rule()); "known rule")]
+    #[test_case("0000000000000009", Err(Code::NotFound); "unknown rule")]
+    #[tokio::test]
+    async fn test_remove_rule(id: &str, expected: Result<Rule, Code>) {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let mut client = setup_server_and_client(
+            mock_client,
+            ReadOnlyActivePrompt::new(None),
+            tx_actioned_prompts,
+        )
+        .await;
+
+        let res = client
+            .remove_rule(Request::new(id.to_string()))
+            .await
+            .map(|resp| resp.into_inner())
+            .map_err(|status| status.code());
+
+        assert_eq!(res, expected);
+    }
+
+    fn update_rule_request() -> UpdateRuleRequest {
+        UpdateRuleRequest {
+            id: "0000000000000002".to_string(),
+            outcome: Some(Action::Deny as i32),
+            lifespan: None,
+            duration: None,
+            path_pattern: Some("/home/ubuntu/**".to_string()),
+            permissions: Vec::new(),
+        }
+    }
+
+    #[test_case(update_rule_request(), Ok(home_rule()); "valid update")]
+    #[test_case(UpdateRuleRequest { id: "0000000000000009".to_string(), ..update_rule_request() }, Err(Code::NotFound); "unknown rule")]
+    #[test_case(UpdateRuleRequest { lifespan: Some(Lifespan::Single as i32), ..update_rule_request() }, Err(Code::InvalidArgument); "single lifespan")]
+    #[test_case(UpdateRuleRequest { lifespan: Some(Lifespan::Timespan as i32), ..update_rule_request() }, Err(Code::InvalidArgument); "timespan without duration")]
+    #[test_case(UpdateRuleRequest { lifespan: Some(Lifespan::Timespan as i32), duration: Some("10 days".to_string()), ..update_rule_request() }, Err(Code::InvalidArgument); "timespan with invalid duration")]
+    #[tokio::test]
+    async fn test_update_rule(req: UpdateRuleRequest, expected: Result<Rule, Code>) {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: Some(RuleEdit {
+                outcome: Some(snapd_client::Action::Deny),
+                path_pattern: Some("/home/ubuntu/**".to_string()),
+                ..Default::default()
+            }),
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let mut client = setup_server_and_client(
+            mock_client,
+            ReadOnlyActivePrompt::new(None),
+            tx_actioned_prompts,
+        )
+        .await;
+
+        let res = client
+            .update_rule(Request::new(req))
+            .await
+            .map(|resp| resp.into_inner())
+            .map_err(|status| status.code());
+
+        assert_eq!(res, expected);
+    }
+
+    #[test]
+    fn map_prompt_reply_supports_timespan_replies() {
+        let mut reply = prompt_reply(prompt_reply_inner());
+        reply.lifespan = Lifespan::Timespan as i32;
+        reply.duration = Some("1h".to_string());
+
+        let active_prompt =
+            ReadOnlyActivePrompt::new(Some(home_ui_input_for_path("/home/user/Documents/foo.txt")));
+        let res = map_prompt_reply(reply, &active_prompt).unwrap();
+
+        match res {
+            TypedPromptReply::Home(reply) => {
+                assert_eq!(reply.lifespan, snapd_client::Lifespan::Timespan);
+                assert_eq!(reply.duration.map(|d| d.to_string()).as_deref(), Some("1h"));
+            }
+            reply => panic!("expected a home reply, got {reply:?}"),
+        }
+    }
+
+    #[test_case(snapd_client::Lifespan::Timespan, Some("1h30m"), Ok(Some("1h30m")); "timespan with duration")]
+    #[test_case(snapd_client::Lifespan::Single, None, Ok(None); "single without duration")]
+    #[test_case(snapd_client::Lifespan::Forever, None, Ok(None); "forever without duration")]
+    #[test_case(snapd_client::Lifespan::Timespan, None, Err(()); "timespan without duration")]
+    #[test_case(snapd_client::Lifespan::Timespan, Some("1 hour"), Err(()); "timespan with invalid duration")]
+    #[test_case(snapd_client::Lifespan::Timespan, Some("0s"), Err(()); "timespan with zero duration")]
+    #[test_case(snapd_client::Lifespan::Session, Some("1h"), Err(()); "session with duration")]
+    #[test]
+    fn map_duration_works(
+        lifespan: snapd_client::Lifespan,
+        duration: Option<&str>,
+        expected: Result<Option<&str>, ()>,
+    ) {
+        let res = map_duration(lifespan, duration.map(String::from));
+
+        match (res, expected) {
+            (Ok(d), Ok(expected)) => {
+                assert_eq!(d.map(|d| d.to_string()).as_deref(), expected)
+            }
+            (Err(status), Err(())) => assert_eq!(status.code(), Code::InvalidArgument),
+            (res, expected) => panic!("expected {expected:?}, got {res:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_watch_rules() {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let mut client = setup_server_and_client(
+            mock_client,
+            ReadOnlyActivePrompt::new(None),
+            tx_actioned_prompts,
+        )
+        .await;
+
+        let mut stream = client
+            .watch_rules(Request::new(()))
+            .await
+            .unwrap()
+            .into_inner();
+
+        let updates = vec![
+            RuleUpdate::Changed(Box::new(rules().remove(0))),
+            RuleUpdate::Removed(RuleId("0000000000000003".to_string())),
+            RuleUpdate::Expired(RuleId("0000000000000004".to_string())),
+        ];
+        for update in updates {
+            client.tx_rule_updates.send(update).unwrap();
+        }
+
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            let update = stream.message().await.unwrap().unwrap();
+            received.push((update.id.clone(), update.update_type(), update.rule));
+        }
+
+        assert_eq!(
+            received,
+            vec![
+                (
+                    "0000000000000002".to_string(),
+                    RuleUpdateType::Changed,
+                    Some(home_rule())
+                ),
+                (
+                    "0000000000000003".to_string(),
+                    RuleUpdateType::Removed,
+                    None
+                ),
+                (
+                    "0000000000000004".to_string(),
+                    RuleUpdateType::Expired,
+                    None
+                ),
+            ]
+        );
+    }
+
+    #[test_case(ActivePrompt::Empty, PromptStatus::NoPrompt, "", None; "empty")]
+    #[test_case(ActivePrompt::Active { input: ui_input(), grouped: Vec::new() }, PromptStatus::Active, "1", Some(prompt()); "active")]
+    #[test_case(ActivePrompt::Actioned(PromptId("1".to_string())), PromptStatus::Actioned, "1", None; "actioned")]
+    #[test_case(ActivePrompt::Dropped(PromptId("1".to_string())), PromptStatus::Dropped, "1", None; "dropped")]
+    #[test_case(ActivePrompt::TimedOut(PromptId("1".to_string())), PromptStatus::TimedOut, "1", None; "timed out")]
+    #[test]
+    fn map_active_prompt_works(
+        state: ActivePrompt,
+        status: PromptStatus,
+        prompt_id: &str,
+        expected: Option<Prompt>,
+    ) {
+        let resp = map_active_prompt(
+            state,
+            vec![queued_home_prompt("5", "2", "/home/user/a")],
+            TIMESTAMP.parse().unwrap(),
+        );
+
+        assert_eq!(resp.status(), status);
+        assert_eq!(resp.prompt_id, prompt_id);
+        assert_eq!(resp.current_prompt.and_then(|p| p.prompt), expected);
+        assert_eq!(resp.queue.map(|q| q.prompts.len()), Some(1));
+    }
+
+    #[test_case("2024-08-14T07:28:22.694800024Z", 0; "just created")]
+    #[test_case("2024-08-14T07:28:25.194800024Z", 2500; "elapsed")]
+    #[test_case("2024-08-14T07:28:20Z", 0; "clock skew")]
+    #[test]
+    fn map_meta_data_reports_prompt_age(now: &str, expected_age_ms: u64) {
+        let TypedUiInput::Home(input) = ui_input() else {
+            panic!("expected a home ui input");
+        };
+
+        let meta_data = map_meta_data(&input, now.parse().unwrap());
+
+        assert_eq!(meta_data.timestamp, TIMESTAMP);
+        assert_eq!(meta_data.age_ms, expected_age_ms);
+    }
+
+    #[test]
+    fn map_meta_data_includes_snap_details() {
+        let input = UiInput::<CameraInterface> {
+            id: PromptId("1".to_string()),
+            timestamp: TIMESTAMP.parse().unwrap(),
+            meta: SnapMeta {
+                name: "firefox".to_string(),
+                updated_at: "2024-08-12".to_string(),
+                store_url: "https://snapcraft.io/firefox".to_string(),
+                publisher: "Mozilla".to_string(),
+                publisher_validation: snapd_client::PublisherValidation::Verified,
+                version: "129.0-2".to_string(),
+                revision: "4757".to_string(),
+                channel: "stable".to_string(),
+                confinement: "strict".to_string(),
+                title: "Firefox".to_string(),
+                summary: "Mozilla Firefox web browser".to_string(),
+                icon_api_path: "/v2/icons/firefox/icon".to_string(),
+            },
+            data: DeviceUiInputData {},
+        };
+
+        let meta_data = map_meta_data(&input, TIMESTAMP.parse().unwrap());
+        let expected = MetaData {
+            prompt_id: "1".to_string(),
+            snap_name: "firefox".to_string(),
+            store_url: "https://snapcraft.io/firefox".to_string(),
+            publisher: "Mozilla".to_string(),
+            updated_at: "2024-08-12".to_string(),
+            timestamp: TIMESTAMP.to_string(),
+            age_ms: 0,
+            publisher_validation: ProtoPublisherValidation::Verified as i32,
+            version: "129.0-2".to_string(),
+            revision: "4757".to_string(),
+            channel: "stable".to_string(),
+            confinement: "strict".to_string(),
+            title: "Firefox".to_string(),
+            summary: "Mozilla Firefox web browser".to_string(),
+            icon_api_path: "/v2/icons/firefox/icon".to_string(),
+        };
+
+        assert_eq!(meta_data, expected);
+    }
+
+    fn queued_home_prompt(id: &str, snap: &str, path: &str) -> TypedPrompt {
+        TypedPrompt::Home(snapd_client::Prompt {
+            id: PromptId(id.to_string()),
+            timestamp: Default::default(),
+            snap: snap.to_string(),
+            interface: "home".to_string(),
+            constraints: HomeConstraints {
+                path: path.to_string(),
+                requested_permissions: PermissionSet::from(["read"]),
+                available_permissions: PermissionSet::from(["read", "write"]),
+            },
+        })
+    }
+
+    #[test]
+    fn map_prompt_queue_works() {
+        let queue = map_prompt_queue(vec![
+            queued_home_prompt("5", "firefox", "/home/user/a.txt"),
+            queued_home_prompt("6", "cheese", "/home/user/b.txt"),
+        ]);
+
+        assert_eq!(queue.prompts.len(), 2);
+        assert_eq!(
+            queue.prompts[0],
+            QueuedPrompt {
+                prompt_id: "5".to_string(),
+                snap_name: "firefox".to_string(),
+                interface: "home".to_string(),
+                requested_path: "/home/user/a.txt".to_string(),
+                requested_permissions: vec!["read".to_string()],
+            }
+        );
+        assert_eq!(queue.prompts[1].prompt_id, "6");
+    }
+
+    #[tokio::test]
+    async fn test_get_prompt_queue() {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let active_prompt = ReadOnlyActivePrompt::new_with_queue(
+            Some(ui_input()),
+            vec![queued_home_prompt("5", "2", "/home/user/a.txt")],
+        );
+        let mut client =
+            setup_server_and_client(mock_client, active_prompt, tx_actioned_prompts).await;
+
+        let queue = client
+            .get_prompt_queue(Request::new(()))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(queue.prompts.len(), 1);
+        assert_eq!(queue.prompts[0].prompt_id, "5");
+    }
+
+    fn firefox_ui_input() -> TypedUiInput {
+        let mut input = home_ui_input_for_path("/home/user/Documents/foo.txt");
+        if let TypedUiInput::Home(ref mut input) = input {
+            input.meta.name = "firefox".to_string();
+        }
+
+        input
+    }
+
+    #[tokio::test]
+    async fn test_reply_to_prompts_replies_to_queued_prompts_from_the_same_snap() {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, mut rx_actioned_prompts) = unbounded_channel();
+        let active_prompt = ReadOnlyActivePrompt::new_with_queue(
+            Some(firefox_ui_input()),
+            vec![
+                queued_home_prompt("5", "firefox", "/home/user/Documents/bar.txt"),
+                queued_home_prompt("6", "cheese", "/home/user/Pictures/baz.png"),
+            ],
+        );
+        let mut client =
+            setup_server_and_client(mock_client, active_prompt, tx_actioned_prompts).await;
+
+        let reply = prompt_reply(Some(HomePromptReply(apparmor_prompting::HomePromptReply {
+            path_pattern: "/home/user/Documents/**".to_string(),
+            permissions: vec!["read".to_string()],
+        })));
+        let resp = client
+            .reply_to_prompts(Request::new(reply))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(
+            resp.reply.map(|r| r.prompt_reply_type()),
+            Some(PromptReplyType::Success)
+        );
+        assert_eq!(resp.actioned_prompt_ids, vec!["5".to_string()]);
+
+        match rx_actioned_prompts.recv().await {
+            Some(ActionedPrompt::Actioned { id, others }) => {
+                assert_eq!(id.0, "1");
+                assert_eq!(others, vec![PromptId("5".to_string())]);
+            }
+            res => panic!("expected actioned prompt, got {res:?}"),
+        }
+    }
+
+    #[test_case(Lifespan::Forever; "forever")]
+    #[test_case(Lifespan::Session; "session")]
+    #[tokio::test]
+    async fn test_reply_to_prompts_keeps_the_reply_lifespan_for_queued_prompts(lifespan: Lifespan) {
+        let replied: Arc<Mutex<Vec<(PromptId, TypedPromptReply)>>> = Default::default();
+        let mock_client = MockClient {
+            want_err: false,
+            replied: replied.clone(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let active_prompt = ReadOnlyActivePrompt::new_with_queue(
+            Some(firefox_ui_input()),
+            vec![
+                queued_home_prompt("5", "firefox", "/home/user/Documents/bar.txt"),
+                queued_home_prompt("6", "firefox", "/home/user/Pictures/baz.png"),
+            ],
+        );
+        let mut client =
+            setup_server_and_client(mock_client, active_prompt, tx_actioned_prompts).await;
+
+        let mut reply = prompt_reply(Some(HomePromptReply(apparmor_prompting::HomePromptReply {
+            path_pattern: "/home/user/Documents/**".to_string(),
+            permissions: vec!["read".to_string()],
+        })));
+        reply.lifespan = lifespan as i32;
+        client.reply_to_prompts(Request::new(reply)).await.unwrap();
+
+        let lifespan = map_lifespan(lifespan);
+        let replied = replied.lock().unwrap();
+        let seen: Vec<(&str, snapd_client::Lifespan, Option<&str>)> = replied
+            .iter()
+            .map(|(id, r)| (id.0.as_str(), r.lifespan(), r.path_pattern()))
+            .collect();
+
+        // The path pattern chosen by the user only covers the first queued prompt
+        assert_eq!(
+            seen,
+            vec![
+                ("1", lifespan, Some("/home/user/Documents/**")),
+                ("5", lifespan, Some("/home/user/Documents/**")),
+                ("6", lifespan, Some("/home/user/Pictures/baz.png")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_reply_to_prompt_replies_to_grouped_prompts() {
+        let replied: Arc<Mutex<Vec<(PromptId, TypedPromptReply)>>> = Default::default();
+        let mock_client = MockClient {
+            want_err: false,
+            replied: replied.clone(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, mut rx_actioned_prompts) = unbounded_channel();
+        let active_prompt = ReadOnlyActivePrompt::new_with_grouped(
+            firefox_ui_input(),
+            vec![queued_home_prompt(
+                "5",
+                "firefox",
+                "/home/user/Documents/bar.txt",
+            )],
+        );
+        let mut client =
+            setup_server_and_client(mock_client, active_prompt, tx_actioned_prompts).await;
+
+        let mut reply = prompt_reply(Some(HomePromptReply(apparmor_prompting::HomePromptReply {
+            path_pattern: "/home/user/Documents/**".to_string(),
+            permissions: vec!["read".to_string(), "write".to_string()],
+        })));
+        reply.lifespan = Lifespan::Forever as i32;
+        let resp = client
+            .reply_to_prompt(Request::new(reply))
+            .await
+            .unwrap()
+            .into_inner();
+
+        assert_eq!(resp.prompt_reply_type(), PromptReplyType::Success);
+        match rx_actioned_prompts.recv().await {
+            Some(ActionedPrompt::Actioned { id, others }) => {
+                assert_eq!(id.0, "1");
+                assert_eq!(others, vec![PromptId("5".to_string())]);
+            }
+            res => panic!("expected actioned prompt, got {res:?}"),
+        }
+
+        // The grouped prompt gets the same reply as the one given by the user
+        let replied = replied.lock().unwrap();
+        let (id, grouped_reply) = &replied[1];
+        assert_eq!(id.0, "5");
+        assert_eq!(grouped_reply.lifespan(), snapd_client::Lifespan::Forever);
+        assert_eq!(
+            grouped_reply.path_pattern(),
+            Some("/home/user/Documents/**")
+        );
+        assert_eq!(grouped_reply.permissions(), vec!["read", "write"]);
+    }
+
+    #[tokio::test]
+    async fn test_reply_to_prompts_requires_the_active_prompt() {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let mut client = setup_server_and_client(
+            mock_client,
+            ReadOnlyActivePrompt::new(None),
+            tx_actioned_prompts,
+        )
+        .await;
+
+        let res = client
+            .reply_to_prompts(Request::new(prompt_reply(prompt_reply_inner())))
+            .await;
+
+        assert_eq!(res.unwrap_err().code(), Code::FailedPrecondition);
+    }
+
+    #[tokio::test]
+    async fn test_watch_prompts_sends_current_state() {
+        let mock_client = MockClient {
+            want_err: false,
+            replied: Default::default(),
+            expected_reply: None,
+            expected_edit: None,
+        };
+        let (tx_actioned_prompts, _rx_actioned_prompts) = unbounded_channel();
+        let mut client = setup_server_and_client(
+            mock_client,
+            ReadOnlyActivePrompt::new(Some(ui_input())),
+            tx_actioned_prompts,
+        )
+        .await;
+
+        let mut stream = client
+            .watch_prompts(Request::new(()))
+            .await
+            .unwrap()
+            .into_inner();
+        let update = stream.message().await.unwrap().unwrap();
+
+        assert_eq!(update.status(), PromptStatus::Active);
+        assert_eq!(
+            update
+                .current_prompt
+                .and_then(|p| p.prompt)
+                .map(without_age),
+            Some(prompt())
+        );
+    }
+
+    fn history_entry(id: &str, snap: &str, event: HistoryEvent) -> HistoryEntry {
+        HistoryEntry {
+            timestamp: format!("2024-08-14T07:28:2{id}Z").parse().unwrap(),
+            id: PromptId(id.to_string()),
+            snap: snap.to_string(),
+            interface: "home".to_string(),
+            event,
+        }
+    }
+
+    fn history() -> Vec<HistoryEntry> {
+        let replied = HistoryEvent::Replied {
+            action: snapd_client::Action::Allow,
+            lifespan: snapd_client::Lifespan::Forever,
+            path_pattern: Some("/home/user/**".to_string()),
+            permissions: vec!["read".to_string()],
+            latency_ms: Some(1500),
+            outcome: ReplyOutcome::Success,
+        };
+        let prompted = HistoryEvent::Prompted {
+            constraints: serde_json::json!({ "path": "/home/user/foo.txt" }),
+        };
+
+        vec![
+            history_entry("1", "firefox", prompted),
+            history_entry("1", "firefox", HistoryEvent::Displayed),
+            history_entry("1", "firefox", replied.clone()),
+            history_entry("2", "cheese", HistoryEvent::Dropped),
+            history_entry("3", "firefox", replied),
+        ]
+    }
+
+    #[test_case(0, "", false, &[("3", 2), ("2", 3), ("1", 2), ("1", 1), ("1", 0)]; "everything")]
+    #[test_case(2, "", false, &[("3", 2), ("2", 3)]; "limit")]
+    #[test_case(0, "firefox", false, &[("3", 2), ("1", 2), ("1", 1), ("1", 0)]; "snap")]
+    #[test_case(0, "", true, &[("3", 2), ("1", 2)]; "replies only")]
+    #[test_case(1, "firefox", true, &[("3", 2)]; "all filters")]
+    #[test]
+    fn map_history_works(limit: u32, snap: &str, replies_only: bool, expected: &[(&str, i32)]) {
+        let req = GetHistoryRequest {
+            limit,
+            snap: snap.to_string(),
+            replies_only,
+        };
+
+        let resp = map_history(history(), req);
+        let seen: Vec<(&str, i32)> = resp
+            .entries
+            .iter()
+            .map(|e| (e.prompt_id.as_str(), e.event))
+            .collect();
+
+        assert_eq!(seen, expected);
+    }
+
+    #[test]
+    fn map_history_entry_works() {
+        let mut entries = history().into_iter();
+        let prompted = map_history_entry(entries.next().unwrap());
+        let replied = map_history_entry(entries.nth(1).unwrap());
+
+        assert_eq!(prompted.event(), history_entry::HistoryEvent::Prompted);
+        assert_eq!(prompted.constraints, r#"{"path":"/home/user/foo.txt"}"#);
+        assert_eq!(
+            replied,
+            ProtoHistoryEntry {
+                timestamp: "2024-08-14T07:28:21Z".to_string(),
+                prompt_id: "1".to_string(),
+                snap: "firefox".to_string(),
+                interface: "home".to_string(),
+                event: history_entry::HistoryEvent::Replied as i32,
+                constraints: String::new(),
+                action: Action::Allow as i32,
+                lifespan: Lifespan::Forever as i32,
+                path_pattern: "/home/user/**".to_string(),
+                permissions: vec!["read".to_string()],
+                latency_ms: Some(1500),
+                outcome: history_entry::ReplyOutcome::Success as i32,
+                resolved_by: String::new(),
+            }
+        );
+    }
 }
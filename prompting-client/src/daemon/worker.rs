@@ -1,23 +1,35 @@
 //! This is our main worker task for processing prompts from snapd and driving the UI.
 use crate::{
-    daemon::{ActionedPrompt, EnrichedPrompt, PromptUpdate, ReplyToPrompt},
-    snapd_client::{PromptId, SnapdSocketClient, TypedUiInput},
+    daemon::{
+        ActionedPrompt, ConfigHandle, DaemonConfig, EnrichedPrompt, History, PromptUpdate,
+        ReplyOutcome, ReplyToPrompt,
+    },
+    snapd_client::{PromptId, SnapMeta, TypedPrompt, TypedPromptReply, TypedUiInput},
     Result,
 };
+use serde::{Deserialize, Serialize};
 use std::{
     collections::VecDeque,
-    env,
-    sync::{Arc, Mutex},
-    time::Duration,
+    future::{pending, Future},
+    time::{Duration, Instant},
 };
+use strum::{Display, EnumString};
 use tokio::{
-    process::Command,
-    sync::mpsc::{error::TryRecvError, UnboundedReceiver},
-    time::timeout,
+    process::{Child, Command},
+    select,
+    sync::{
+        mpsc::{error::TryRecvError, UnboundedReceiver},
+        watch,
+    },
+    time::{self, sleep_until, timeout, timeout_at},
 };
+use tokio_util::sync::CancellationToken;
 use tracing::{debug, error, info, warn};
 
-const RECV_TIMEOUT: Duration = Duration::from_millis(200);
+const UI_RESTART_BACKOFF_BASE: Duration = Duration::from_millis(500);
+const UI_RESTART_BACKOFF_MAX: Duration = Duration::from_secs(30);
+// A UI process that stays up for at least this long is considered to have started cleanly
+const UI_STABLE_UPTIME: Duration = Duration::from_secs(60);
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 enum Recv {
@@ -26,45 +38,357 @@ enum Recv {
     Gone,
     DeadPrompt,
     Unexpected,
+    Updated,
+    UiExited,
     ChannelClosed,
 }
 
-#[derive(Debug)]
+/// The state of the prompt most recently presented to the UI.
+#[allow(clippy::large_enum_variant)]
+#[derive(Debug, Default, Clone)]
+pub enum ActivePrompt {
+    /// No prompt has been presented to the UI yet.
+    #[default]
+    Empty,
+    /// The prompt is currently awaiting a reply, along with any related prompts that have been
+    /// grouped with it.
+    Active {
+        input: TypedUiInput,
+        grouped: Vec<TypedPrompt>,
+    },
+    /// A reply was sent for the prompt.
+    Actioned(PromptId),
+    /// The prompt is no longer present in snapd without having been replied to by us.
+    Dropped(PromptId),
+    /// The user did not reply before the prompt timeout and the [TimeoutPolicy] was applied.
+    TimedOut(PromptId),
+}
+
+/// What to do with a prompt that the user has not replied to before the prompt timeout.
+#[derive(
+    Debug, Default, Clone, Copy, PartialEq, Eq, Display, EnumString, Serialize, Deserialize,
+)]
+#[strum(serialize_all = "kebab-case")]
+#[serde(rename_all = "kebab-case")]
+pub enum TimeoutPolicy {
+    /// Deny the request and close the UI.
+    #[default]
+    DenyOnce,
+    /// Deny the request along with any identical requests for the rest of the user's session.
+    DenyForSession,
+    /// Close the UI without replying so that the prompt remains pending within snapd. The prompt
+    /// is shown to the user again once another prompt arrives.
+    LeavePending,
+}
+
+impl TimeoutPolicy {
+    fn reply_fn(self) -> Option<fn(TypedPrompt) -> TypedPromptReply> {
+        match self {
+            Self::DenyOnce => Some(TypedPrompt::into_deny_once),
+            Self::DenyForSession => Some(TypedPrompt::into_deny_for_session),
+            Self::LeavePending => None,
+        }
+    }
+}
+
+/// An overall deadline for the user to reply to a prompt once it has been shown in the UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PromptTimeout {
+    pub timeout: Duration,
+    pub policy: TimeoutPolicy,
+}
+
+#[derive(Debug, Clone)]
 pub struct ReadOnlyActivePrompt {
-    active_prompt: Arc<Mutex<Option<TypedUiInput>>>,
+    rx: watch::Receiver<ActivePrompt>,
+    rx_queue: watch::Receiver<Vec<TypedPrompt>>,
 }
 
 impl ReadOnlyActivePrompt {
     #[cfg(test)]
     pub fn new(ui_input: Option<TypedUiInput>) -> Self {
-        Self {
-            active_prompt: Arc::new(Mutex::new(ui_input)),
-        }
+        Self::new_with_queue(ui_input, Vec::new())
+    }
+
+    #[cfg(test)]
+    pub fn new_with_queue(ui_input: Option<TypedUiInput>, queue: Vec<TypedPrompt>) -> Self {
+        let state = ui_input
+            .map(|input| ActivePrompt::Active {
+                input,
+                grouped: Vec::new(),
+            })
+            .unwrap_or_default();
+        let (_, rx) = watch::channel(state);
+        let (_, rx_queue) = watch::channel(queue);
+
+        Self { rx, rx_queue }
+    }
+
+    #[cfg(test)]
+    pub fn new_with_grouped(input: TypedUiInput, grouped: Vec<TypedPrompt>) -> Self {
+        let (_, rx) = watch::channel(ActivePrompt::Active { input, grouped });
+        let (_, rx_queue) = watch::channel(Vec::new());
+
+        Self { rx, rx_queue }
     }
 
+    /// The prompt currently awaiting a reply, if there is one.
     pub fn get(&self) -> Option<TypedUiInput> {
-        let guard = match self.active_prompt.lock() {
-            Ok(guard) => guard,
-            Err(err) => err.into_inner(),
-        };
-        guard.clone()
+        match &*self.rx.borrow() {
+            ActivePrompt::Active { input, .. } => Some(input.clone()),
+            _ => None,
+        }
+    }
+
+    /// The prompts that have been grouped with the active prompt with the given ID. Replies to
+    /// the active prompt should also be applied to these.
+    pub fn grouped(&self, id: &PromptId) -> Vec<TypedPrompt> {
+        match &*self.rx.borrow() {
+            ActivePrompt::Active { input, grouped } if input.id() == id => grouped.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// A receiver that will be notified of all future changes to the active prompt.
+    pub fn watch(&self) -> watch::Receiver<ActivePrompt> {
+        self.rx.clone()
+    }
+
+    /// The prompts waiting to be presented to the UI once the active prompt has been actioned.
+    pub fn queued(&self) -> Vec<TypedPrompt> {
+        self.rx_queue.borrow().clone()
+    }
+
+    /// A receiver that will be notified of all future changes to the queued prompts.
+    pub fn watch_queue(&self) -> watch::Receiver<Vec<TypedPrompt>> {
+        self.rx_queue.clone()
     }
 }
 
 pub trait SpawnUi {
     async fn spawn(&mut self) -> Result<()>;
+
+    /// Resolves when a UI that was spawned to handle the active prompt exits before the prompt
+    /// has been actioned, or when a restart of the UI that was scheduled by [SpawnUi::spawn] is
+    /// due. [SpawnUi::spawn] is called again each time this resolves. UIs that block in
+    /// [SpawnUi::spawn] until they exit never resolve.
+    async fn exited(&mut self) {
+        pending().await
+    }
+
+    /// How long to wait for a reply to the active prompt before denying it.
+    fn recv_timeout(&self, config: &DaemonConfig) -> Duration {
+        config.recv_timeout()
+    }
+
+    /// Close any UI that is still running when the daemon shuts down. UIs that are spawned in
+    /// [SpawnUi::spawn] are killed when the future driving them is dropped.
+    async fn close(&mut self) {}
 }
 
+/// Spawn a new instance of the Flutter UI for each prompt, waiting for it to exit.
 pub struct FlutterUi {
     cmd: String,
 }
 
 impl SpawnUi for FlutterUi {
     async fn spawn(&mut self) -> Result<()> {
-        Command::new(&self.cmd).spawn()?.wait().await?;
+        // The UI is killed when the child is dropped, which happens if the prompt times out while
+        // we are still waiting for the UI to exit
+        Command::new(&self.cmd)
+            .kill_on_drop(true)
+            .spawn()?
+            .wait()
+            .await?;
+
+        Ok(())
+    }
+}
+
+/// Keep a single instance of the UI running which is notified of new prompts over the gRPC socket
+/// (via WatchPrompts) rather than being spawned for each prompt.
+///
+/// If the UI process exits it is restarted with an exponential backoff the next time that it is
+/// needed. Rather than blocking the worker while backing off, the restart is scheduled and
+/// performed once [SpawnUi::exited] resolves at the restart deadline.
+pub struct PersistentFlutterUi {
+    cmd: String,
+    child: Option<Child>,
+    started_at: Instant,
+    restart_at: Option<time::Instant>,
+    restarts: u32,
+    backoff_base: Duration,
+    backoff_max: Duration,
+}
+
+impl PersistentFlutterUi {
+    fn new(cmd: String) -> Self {
+        Self {
+            cmd,
+            child: None,
+            started_at: Instant::now(),
+            restart_at: None,
+            restarts: 0,
+            backoff_base: UI_RESTART_BACKOFF_BASE,
+            backoff_max: UI_RESTART_BACKOFF_MAX,
+        }
+    }
+
+    fn restart_delay(&self) -> Duration {
+        match self.restarts {
+            0 => Duration::ZERO,
+            n => self
+                .backoff_base
+                .saturating_mul(2u32.saturating_pow(n - 1))
+                .min(self.backoff_max),
+        }
+    }
+}
+
+impl SpawnUi for PersistentFlutterUi {
+    async fn spawn(&mut self) -> Result<()> {
+        if let Some(child) = self.child.as_mut() {
+            match child.try_wait()? {
+                None => return Ok(()), // still running
+                Some(status) => {
+                    warn!(%status, "UI process exited: restarting");
+                    self.child = None;
+                    if self.started_at.elapsed() >= UI_STABLE_UPTIME {
+                        self.restarts = 0;
+                    }
+                }
+            }
+        }
+
+        if self.restart_at.is_none() {
+            let delay = self.restart_delay();
+            if !delay.is_zero() {
+                info!(?delay, restarts=%self.restarts, "scheduling restart of UI");
+                self.restart_at = Some(time::Instant::now() + delay);
+            }
+        }
+
+        match self.restart_at {
+            Some(restart_at) if time::Instant::now() < restart_at => return Ok(()),
+            _ => self.restart_at = None,
+        }
+
+        debug!(cmd=%self.cmd, "starting UI process");
+        let child = Command::new(&self.cmd).kill_on_drop(true).spawn()?;
+        self.child = Some(child);
+        self.started_at = Instant::now();
+        self.restarts = self.restarts.saturating_add(1);
 
         Ok(())
     }
+
+    async fn exited(&mut self) {
+        match (self.child.as_mut(), self.restart_at) {
+            (Some(child), _) => {
+                let _ = child.wait().await;
+            }
+            (None, Some(restart_at)) => sleep_until(restart_at).await,
+            (None, None) => pending().await,
+        }
+    }
+
+    // The persistent UI is not expected to exit after replying so we wait for as long as the user
+    // reasonably needs to respond before denying the prompt.
+    fn recv_timeout(&self, config: &DaemonConfig) -> Duration {
+        config.persistent_ui_recv_timeout()
+    }
+
+    async fn close(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            debug!("stopping UI process");
+            if let Err(error) = child.kill().await {
+                warn!(%error, "unable to stop UI process");
+            }
+        }
+    }
+}
+
+/// The UI used by the daemon, selected by the `persistent-ui` config option.
+pub enum DaemonUi {
+    PerPrompt(FlutterUi),
+    Persistent(Box<PersistentFlutterUi>),
+}
+
+impl DaemonUi {
+    pub fn from_config(config: &DaemonConfig) -> Self {
+        let cmd = config.ui_command();
+        if config.persistent_ui {
+            Self::Persistent(Box::new(PersistentFlutterUi::new(cmd)))
+        } else {
+            Self::PerPrompt(FlutterUi { cmd })
+        }
+    }
+}
+
+impl SpawnUi for DaemonUi {
+    async fn spawn(&mut self) -> Result<()> {
+        match self {
+            Self::PerPrompt(ui) => ui.spawn().await,
+            Self::Persistent(ui) => ui.spawn().await,
+        }
+    }
+
+    async fn exited(&mut self) {
+        match self {
+            Self::PerPrompt(ui) => ui.exited().await,
+            Self::Persistent(ui) => ui.exited().await,
+        }
+    }
+
+    fn recv_timeout(&self, config: &DaemonConfig) -> Duration {
+        match self {
+            Self::PerPrompt(ui) => ui.recv_timeout(config),
+            Self::Persistent(ui) => ui.recv_timeout(config),
+        }
+    }
+
+    async fn close(&mut self) {
+        match self {
+            Self::PerPrompt(ui) => ui.close().await,
+            Self::Persistent(ui) => ui.close().await,
+        }
+    }
+}
+
+/// Remove the prompt with the given ID from `prompts`, returning false if it was not present.
+fn remove_prompt(prompts: &mut VecDeque<EnrichedPrompt>, id: &PromptId) -> bool {
+    if let Some(ix) = prompts.iter().position(|ep| ep.prompt.id() == id) {
+        let ep = &mut prompts[ix];
+        if ep.grouped.is_empty() {
+            prompts.remove(ix);
+        } else {
+            // The first of the grouped prompts takes the place of the one being removed
+            ep.prompt = ep.grouped.remove(0);
+        }
+
+        return true;
+    }
+
+    for ep in prompts.iter_mut() {
+        let len = ep.grouped.len();
+        ep.grouped.retain(|p| p.id() != id);
+        if ep.grouped.len() < len {
+            return true;
+        }
+    }
+
+    false
+}
+
+/// Run `fut` to completion unless the deadline (if there is one) passes first.
+async fn before_deadline<T>(
+    deadline: Option<time::Instant>,
+    fut: impl Future<Output = T>,
+) -> Option<T> {
+    match deadline {
+        Some(deadline) => timeout_at(deadline, fut).await.ok(),
+        None => Some(fut.await),
+    }
 }
 
 #[derive(Debug)]
@@ -75,61 +399,97 @@ where
 {
     rx_prompts: UnboundedReceiver<PromptUpdate>,
     rx_actioned_prompts: UnboundedReceiver<ActionedPrompt>,
-    active_prompt: Arc<Mutex<Option<TypedUiInput>>>,
+    active_prompt: watch::Sender<ActivePrompt>,
+    queue: watch::Sender<Vec<TypedPrompt>>,
     pending_prompts: VecDeque<EnrichedPrompt>,
+    /// Prompts left pending after timing out, which are queued again when a new prompt arrives.
+    left_pending: VecDeque<EnrichedPrompt>,
     prompts_to_drop: Vec<PromptId>,
     dead_prompts: Vec<PromptId>,
     recv_timeout: Duration,
+    prompt_timeout: Option<PromptTimeout>,
+    config: ConfigHandle,
+    history: History,
     ui: S,
     client: R,
     running: bool,
 }
 
-impl Worker<FlutterUi, SnapdSocketClient> {
+impl<S, R> Worker<S, R>
+where
+    S: SpawnUi,
+    R: ReplyToPrompt,
+{
     pub fn new(
         rx_prompts: UnboundedReceiver<PromptUpdate>,
         rx_actioned_prompts: UnboundedReceiver<ActionedPrompt>,
-        client: SnapdSocketClient,
+        client: R,
+        mut config: ConfigHandle,
+        ui: S,
     ) -> Self {
-        let snap = env::var("SNAP").expect("SNAP env var to be set");
-        let cmd = format!("{snap}/bin/prompting_client_ui");
+        let current = config.borrow_and_update().clone();
 
         Self {
             rx_prompts,
             rx_actioned_prompts,
-            active_prompt: Arc::new(Mutex::new(None)),
+            active_prompt: watch::Sender::new(ActivePrompt::Empty),
+            queue: watch::Sender::new(Vec::new()),
             pending_prompts: VecDeque::new(),
+            left_pending: VecDeque::new(),
             prompts_to_drop: Vec::new(),
             dead_prompts: Vec::new(),
-            recv_timeout: RECV_TIMEOUT,
-            ui: FlutterUi { cmd },
+            recv_timeout: ui.recv_timeout(&current),
+            prompt_timeout: current.prompt_timeout(),
+            config,
+            history: History::default(),
+            ui,
             client,
             running: false,
         }
     }
-}
 
-impl<S, R> Worker<S, R>
-where
-    S: SpawnUi,
-    R: ReplyToPrompt,
-{
+    /// Record the prompts we see and how they are replied to in the given [History].
+    pub fn with_history(mut self, history: History) -> Self {
+        self.history = history;
+        self
+    }
+
     pub fn read_only_active_prompt(&self) -> ReadOnlyActivePrompt {
         ReadOnlyActivePrompt {
-            active_prompt: self.active_prompt.clone(),
+            rx: self.active_prompt.subscribe(),
+            rx_queue: self.queue.subscribe(),
         }
     }
 
-    pub async fn run(&mut self) -> Result<()> {
+    /// Process prompts until either the prompt channel is closed or `shutdown` is cancelled.
+    pub async fn run(&mut self, shutdown: CancellationToken) -> Result<()> {
         self.running = true;
 
         while self.running {
-            self.step().await?;
+            select! {
+                res = self.step() => res?,
+                _ = shutdown.cancelled() => self.shutdown().await,
+            }
         }
 
         Ok(())
     }
 
+    /// Close the UI without replying to the active prompt so that snapd keeps it pending and it
+    /// is re-presented by the [PollLoop][crate::daemon::PollLoop] when the daemon restarts.
+    async fn shutdown(&mut self) {
+        self.running = false;
+        if let ActivePrompt::Active { input, grouped } = &*self.active_prompt.borrow() {
+            let grouped: Vec<&str> = grouped.iter().map(|p| p.id().0.as_str()).collect();
+            info!(
+                id=%input.id().0, ?grouped, pending=%self.pending_prompts.len(),
+                "shutting down with an unactioned prompt: it will be re-presented on restart"
+            );
+        }
+
+        self.ui.close().await;
+    }
+
     async fn pull_updates(&mut self) {
         // If there are currently no prompts pending in the channel and nothing in our internal
         // pending_prompts buffer then we block until at least one prompt arrives rather than busy
@@ -159,6 +519,105 @@ where
         }
     }
 
+    fn publish_queue(&self) {
+        let queue = self
+            .pending_prompts
+            .iter()
+            .chain(self.left_pending.iter())
+            .flat_map(|ep| std::iter::once(&ep.prompt).chain(ep.grouped.iter()))
+            .cloned();
+        self.queue.send_replace(queue.collect());
+    }
+
+    /// Drop any prompts that have been actioned as a side effect of replying to another prompt.
+    fn drop_actioned(&mut self, ids: Vec<PromptId>) {
+        for id in ids {
+            // Prompts grouped with the active prompt have already been removed from the queue
+            if self.is_grouped_with_active_prompt(&id) {
+                continue;
+            }
+            if !self.remove_pending(&id) {
+                self.prompts_to_drop.push(id);
+            }
+        }
+        self.publish_queue();
+    }
+
+    /// Remove a pending prompt, including those that have been grouped with another pending
+    /// prompt and those left pending after timing out. Returns false if there was no pending
+    /// prompt with the given ID.
+    fn remove_pending(&mut self, id: &PromptId) -> bool {
+        remove_prompt(&mut self.pending_prompts, id) || remove_prompt(&mut self.left_pending, id)
+    }
+
+    fn is_grouped_with_active_prompt(&self, id: &PromptId) -> bool {
+        match &*self.active_prompt.borrow() {
+            ActivePrompt::Active { grouped, .. } => grouped.iter().any(|p| p.id() == id),
+            _ => false,
+        }
+    }
+
+    fn active_grouped_prompts(&self) -> Vec<TypedPrompt> {
+        match &*self.active_prompt.borrow() {
+            ActivePrompt::Active { grouped, .. } => grouped.clone(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Reply to each of the prompts that were grouped with a prompt we were unable to get a reply
+    /// for from the user. Errors are logged rather than returned as snapd may have already
+    /// actioned them.
+    async fn reply_to_grouped(
+        &self,
+        grouped: Vec<TypedPrompt>,
+        into_reply: fn(TypedPrompt) -> TypedPromptReply,
+        outcome: ReplyOutcome,
+    ) {
+        for prompt in grouped {
+            let id = prompt.id().clone();
+            if let Err(error) = self.reply_for_user(&id, into_reply(prompt), outcome).await {
+                warn!(id=%id.0, %error, "unable to reply to grouped prompt");
+            }
+        }
+    }
+
+    /// Reply to a prompt on behalf of the user, recording the reply in our history.
+    async fn reply_for_user(
+        &self,
+        id: &PromptId,
+        reply: TypedPromptReply,
+        outcome: ReplyOutcome,
+    ) -> Result<Vec<PromptId>> {
+        match self.client.reply(id, reply.clone()).await {
+            Ok(others) => {
+                self.history.replied(id, &reply, outcome);
+                self.history.resolved_by_rule(id, &others);
+                Ok(others)
+            }
+            Err(e) => {
+                self.history.reply_failed(id, &reply, &e);
+                Err(e)
+            }
+        }
+    }
+
+    /// Whether or not the given prompt is currently pending or being presented to the user.
+    fn is_known(&self, id: &PromptId) -> bool {
+        let is_pending = self
+            .pending_prompts
+            .iter()
+            .chain(self.left_pending.iter())
+            .any(|ep| ep.prompt.id() == id || ep.grouped.iter().any(|p| p.id() == id));
+
+        is_pending
+            || match &*self.active_prompt.borrow() {
+                ActivePrompt::Active { input, grouped } => {
+                    input.id() == id || grouped.iter().any(|p| p.id() == id)
+                }
+                _ => false,
+            }
+    }
+
     fn process_update(&mut self, update: PromptUpdate) {
         match update {
             PromptUpdate::Add(ep) if self.prompts_to_drop.contains(ep.prompt.id()) => {
@@ -166,14 +625,41 @@ where
                 self.prompts_to_drop.retain(|id| id != ep.prompt.id());
             }
 
-            PromptUpdate::Add(ep) => self.pending_prompts.push_back(ep),
+            // The poll loop re-syncs outstanding prompts after losing its connection to snapd
+            PromptUpdate::Add(ep) if self.is_known(ep.prompt.id()) => {
+                debug!(id=%ep.prompt.id().0, "ignoring prompt that has already been seen");
+            }
+
+            PromptUpdate::Add(ep) => {
+                self.history.prompted(&ep.prompt);
+
+                // Related prompts are grouped together so that the user only needs to reply once
+                let related = self
+                    .pending_prompts
+                    .iter_mut()
+                    .find(|pending| pending.prompt.can_coalesce_with(&ep.prompt));
+
+                match related {
+                    Some(pending) => {
+                        info!(id=%ep.prompt.id().0, group=%pending.prompt.id().0, "grouping prompt with pending prompt");
+                        pending.grouped.push(ep.prompt);
+                    }
+                    None => self.pending_prompts.push_back(ep),
+                }
+
+                // Prompts that timed out are shown again behind the new prompt
+                if !self.left_pending.is_empty() {
+                    debug!(n=%self.left_pending.len(), "re-queueing prompts left pending");
+                    self.pending_prompts.extend(self.left_pending.drain(..));
+                }
+            }
 
             PromptUpdate::Drop(id) => {
+                self.history.dropped(&id);
+
                 // If this prompt was already pending then remove it now, otherwise keep track of
                 // it as one to drop as and when it comes in
-                let len = self.pending_prompts.len();
-                self.pending_prompts.retain(|ep| ep.prompt.id() != &id);
-                if self.pending_prompts.len() < len {
+                if self.remove_pending(&id) {
                     info!(id=%id.0, "dropping prompt as it has already been actioned");
                 } else {
                     // TODO: do we need to worry about this growing unchecked if we get bogus
@@ -184,10 +670,26 @@ where
         }
     }
 
+    /// Pick up any changes to the timeouts we are using if the config has been reloaded.
+    fn refresh_config(&mut self) {
+        if !self.config.has_changed().unwrap_or(false) {
+            return;
+        }
+
+        let config = self.config.borrow_and_update();
+        self.recv_timeout = self.ui.recv_timeout(&config);
+        self.prompt_timeout = config.prompt_timeout();
+        debug!(recv_timeout=?self.recv_timeout, prompt_timeout=?self.prompt_timeout, "config updated");
+    }
+
     async fn step(&mut self) -> Result<()> {
+        self.refresh_config();
         self.pull_updates().await;
 
-        let ep = match self.pending_prompts.pop_front() {
+        let next = self.pending_prompts.pop_front();
+        self.publish_queue();
+
+        let ep = match next {
             Some(ep) if self.running => ep,
             _ => return Ok(()),
         };
@@ -196,64 +698,217 @@ where
 
         let expected_id = ep.prompt.id().clone();
         let prompt = ep.prompt.clone();
+        let meta = ep.meta.clone();
+        let grouped = ep.grouped.clone();
 
         debug!("updating active prompt");
         if let Err(error) = self.update_active_prompt(ep) {
             error!(%error, "failed to map prompt to UI input: replying with deny once");
-            let reply = prompt.into_deny_once();
-            self.client.reply(&expected_id, reply).await?;
+            let outcome = ReplyOutcome::MappingFailureDeny;
+            self.reply_for_user(&expected_id, prompt.into_deny_once(), outcome)
+                .await?;
+            self.reply_to_grouped(grouped, TypedPrompt::into_deny_once, outcome)
+                .await;
             return Ok(());
         }
 
-        // FIXME: the UI closing without replying or actioning multiple prompts gets tricky (when can we spawn the next UI?)
+        let state = match self.serve_prompt(&expected_id, &prompt, meta).await? {
+            Some(state) => state,
+            None => return Ok(()),
+        };
+
+        debug!(?state, "clearing active prompt");
+        self.active_prompt.send_replace(state);
+
+        Ok(())
+    }
+
+    /// Spawn the UI for the active prompt and wait for it to be actioned, returning the final
+    /// state of the prompt. Returns None if the worker should shut down.
+    ///
+    /// The prompt timeout (if there is one) applies while we are spawning the UI (which blocks
+    /// until the UI exits when spawning a UI per prompt) and waiting on it, but not to the replies
+    /// that we send to snapd so that they are never cancelled part way through.
+    async fn serve_prompt(
+        &mut self,
+        expected_id: &PromptId,
+        prompt: &TypedPrompt,
+        meta: Option<SnapMeta>,
+    ) -> Result<Option<ActivePrompt>> {
+        let deadline = self
+            .prompt_timeout
+            .map(|t| time::Instant::now() + t.timeout);
+
+        // FIXME: when spawning a UI per prompt, the UI closing without replying or actioning
+        // multiple prompts gets tricky (when can we spawn the next UI?)
         debug!("spawning UI");
-        self.ui.spawn().await?;
+        let mut spawned = before_deadline(deadline, self.ui.spawn())
+            .await
+            .transpose()?;
+
+        let state = loop {
+            let recv = match spawned {
+                Some(()) => {
+                    before_deadline(deadline, self.wait_for_expected_prompt(expected_id)).await
+                }
+                None => None,
+            };
 
-        loop {
-            match self.wait_for_expected_prompt(&expected_id).await {
-                Recv::DeadPrompt | Recv::Unexpected => continue,
-                Recv::Success | Recv::Gone => break,
+            let recv = match recv {
+                Some(recv) => recv,
+                None => {
+                    break self
+                        .apply_timeout_policy(expected_id.clone(), prompt.clone(), meta)
+                        .await
+                }
+            };
+
+            match recv {
+                Recv::DeadPrompt | Recv::Unexpected | Recv::Updated => continue,
+                Recv::UiExited => {
+                    debug!(id=%expected_id.0, "respawning UI for the active prompt");
+                    spawned = before_deadline(deadline, self.ui.spawn())
+                        .await
+                        .transpose()?;
+                    continue;
+                }
+                Recv::Success => break ActivePrompt::Actioned(expected_id.clone()),
+                Recv::Gone => {
+                    // Any prompts grouped with the one that was dropped still need a reply
+                    let mut grouped = self.active_grouped_prompts().into_iter();
+                    if let Some(prompt) = grouped.next() {
+                        self.pending_prompts.push_front(EnrichedPrompt {
+                            prompt,
+                            meta,
+                            grouped: grouped.collect(),
+                        });
+                        self.publish_queue();
+                    }
+                    break ActivePrompt::Dropped(expected_id.clone());
+                }
                 Recv::Timeout => {
-                    let reply = prompt.into_deny_once();
-                    self.client.reply(&expected_id, reply).await?;
-                    break;
+                    let outcome = ReplyOutcome::TimeoutDeny;
+                    let reply = prompt.clone().into_deny_once();
+                    self.reply_for_user(expected_id, reply, outcome).await?;
+                    self.reply_to_grouped(
+                        self.active_grouped_prompts(),
+                        TypedPrompt::into_deny_once,
+                        outcome,
+                    )
+                    .await;
+                    break ActivePrompt::Actioned(expected_id.clone());
                 }
                 Recv::ChannelClosed => {
                     self.running = false;
-                    return Ok(());
+                    return Ok(None);
                 }
             }
-        }
+        };
 
-        debug!("clearing active prompt");
-        self.active_prompt
-            .lock()
-            .expect("grpc server panicked")
-            .take();
+        Ok(Some(state))
+    }
 
-        Ok(())
+    /// Apply the configured [TimeoutPolicy] to a prompt that the user has not replied to within
+    /// the prompt timeout. Prompts that are left pending are held back until a new prompt arrives
+    /// so that a lone prompt is not immediately shown to the user again.
+    async fn apply_timeout_policy(
+        &mut self,
+        id: PromptId,
+        prompt: TypedPrompt,
+        meta: Option<SnapMeta>,
+    ) -> ActivePrompt {
+        let policy = self.prompt_timeout.map(|t| t.policy).unwrap_or_default();
+        warn!(id=%id.0, %policy, "timed out waiting for the user to reply to prompt");
+
+        match policy.reply_fn() {
+            Some(into_reply) => {
+                let outcome = ReplyOutcome::TimeoutDeny;
+                if let Err(error) = self.reply_for_user(&id, into_reply(prompt), outcome).await {
+                    warn!(id=%id.0, %error, "unable to apply timeout policy to prompt");
+                }
+                self.reply_to_grouped(self.active_grouped_prompts(), into_reply, outcome)
+                    .await;
+            }
+
+            None => {
+                self.left_pending.push_back(EnrichedPrompt {
+                    prompt,
+                    meta,
+                    grouped: self.active_grouped_prompts(),
+                });
+                self.publish_queue();
+            }
+        }
+
+        ActivePrompt::TimedOut(id)
     }
 
     fn update_active_prompt(
         &mut self,
-        EnrichedPrompt { prompt, meta }: EnrichedPrompt,
+        EnrichedPrompt {
+            prompt,
+            meta,
+            grouped,
+        }: EnrichedPrompt,
     ) -> Result<()> {
-        let input = TypedUiInput::try_from_prompt(prompt, meta)?;
-        let mut guard = match self.active_prompt.lock() {
-            Ok(guard) => guard,
-            Err(err) => err.into_inner(),
-        };
-        guard.replace(input);
+        let mut input = TypedUiInput::try_from_prompt(prompt, meta)?;
+        input.restrict_to_grouped(&grouped);
+        self.history.displayed(input.id());
+        for p in grouped.iter() {
+            self.history.displayed(p.id());
+        }
+        self.active_prompt
+            .send_replace(ActivePrompt::Active { input, grouped });
 
         Ok(())
     }
 
+    /// Wait for the expected prompt to be actioned, processing any prompt updates that arrive from
+    /// the poll loop in the meantime so that we notice if snapd drops the prompt.
     async fn wait_for_expected_prompt(&mut self, expected_id: &PromptId) -> Recv {
-        match timeout(self.recv_timeout, self.rx_actioned_prompts.recv()).await {
-            Ok(Some(ActionedPrompt::Actioned { id, others })) => {
+        let recv = async {
+            select! {
+                actioned = self.rx_actioned_prompts.recv() => Ok(Ok(actioned)),
+                // A closed prompts channel is handled by pull_updates once we return
+                Some(update) = self.rx_prompts.recv() => Ok(Err(update)),
+                _ = self.ui.exited() => Err(()),
+            }
+        };
+
+        let recv = match timeout(self.recv_timeout, recv).await {
+            Ok(Err(())) => return Recv::UiExited,
+            Ok(Ok(res)) => Ok(res),
+            Err(elapsed) => Err(elapsed),
+        };
+
+        match recv {
+            Ok(Err(PromptUpdate::Drop(id))) if &id == expected_id => {
+                info!(id=%id.0, "active prompt dropped by snapd");
+                self.history.dropped(&id);
+                Recv::Gone
+            }
+
+            Ok(Err(PromptUpdate::Drop(id))) if self.is_grouped_with_active_prompt(&id) => {
+                info!(id=%id.0, "grouped prompt dropped by snapd");
+                self.history.dropped(&id);
+                self.active_prompt.send_modify(|state| {
+                    if let ActivePrompt::Active { grouped, .. } = state {
+                        grouped.retain(|p| p.id() != &id);
+                    }
+                });
+                Recv::Updated
+            }
+
+            Ok(Err(update)) => {
+                self.process_update(update);
+                self.publish_queue();
+                Recv::Updated
+            }
+
+            Ok(Ok(Some(ActionedPrompt::Actioned { id, others }))) => {
                 debug!(recv_id=%id.0, "reply sent for prompt");
                 debug!(to_drop=?others, "updating prompts to drop");
-                self.prompts_to_drop.extend(others);
+                self.drop_actioned(others);
 
                 if self.dead_prompts.contains(&id) {
                     warn!(id=%id.0, "reply was for a dead prompt");
@@ -269,7 +924,7 @@ where
                 }
             }
 
-            Ok(Some(ActionedPrompt::NotFound { id })) => {
+            Ok(Ok(Some(ActionedPrompt::NotFound { id }))) => {
                 if self.dead_prompts.contains(&id) {
                     warn!(id=%id.0, "attempt to reply to dead prompt that is now gone");
                     self.dead_prompts.retain(|i| i != &id);
@@ -283,7 +938,7 @@ where
                 }
             }
 
-            Ok(None) => {
+            Ok(Ok(None)) => {
                 warn!("actioned prompts channel is now closed, exiting");
                 Recv::ChannelClosed
             }
@@ -301,7 +956,7 @@ where
 mod tests {
     use super::*;
     use crate::snapd_client::{
-        interfaces::home::{HomeConstraints, HomeReplyConstraints},
+        interfaces::home::{HomeConstraints, HomeReplyConstraints, PermissionSet},
         Action, Lifespan, Prompt, PromptReply, TypedPrompt, TypedPromptReply,
     };
     use simple_test_case::test_case;
@@ -332,12 +987,62 @@ mod tests {
         EnrichedPrompt {
             prompt: TypedPrompt::Home(Prompt {
                 id: PromptId(id.to_string()),
-                timestamp: String::new(),
+                timestamp: Default::default(),
                 snap: "test".to_string(),
                 interface: "home".to_string(),
                 constraints: HomeConstraints::default(),
             }),
             meta: None,
+            grouped: Vec::new(),
+        }
+    }
+
+    fn home_ep(id: &str, path: &str) -> EnrichedPrompt {
+        EnrichedPrompt {
+            prompt: TypedPrompt::Home(Prompt {
+                id: PromptId(id.to_string()),
+                timestamp: Default::default(),
+                snap: "test".to_string(),
+                interface: "home".to_string(),
+                constraints: HomeConstraints {
+                    path: path.to_string(),
+                    requested_permissions: PermissionSet::from(["read"]),
+                    available_permissions: PermissionSet::from(["read", "write"]),
+                },
+            }),
+            meta: None,
+            grouped: Vec::new(),
+        }
+    }
+
+    /// A running worker with nothing pending and no prompt timeout. Tests override the fields
+    /// that they need using struct update syntax.
+    fn test_worker<S, R>(
+        rx_prompts: UnboundedReceiver<PromptUpdate>,
+        rx_actioned_prompts: UnboundedReceiver<ActionedPrompt>,
+        ui: S,
+        client: R,
+    ) -> Worker<S, R>
+    where
+        S: SpawnUi,
+        R: ReplyToPrompt,
+    {
+        Worker {
+            rx_prompts,
+            rx_actioned_prompts,
+            active_prompt: watch::Sender::new(ActivePrompt::Empty),
+            queue: watch::Sender::new(Vec::new()),
+            pending_prompts: VecDeque::new(),
+            left_pending: VecDeque::new(),
+            prompts_to_drop: Vec::new(),
+            dead_prompts: Vec::new(),
+            recv_timeout: Duration::from_millis(100),
+            prompt_timeout: None,
+            config: DaemonConfig::default().fixed(),
+            history: History::default(),
+            ui,
+            client,
+            running: true,
         }
     }
 
@@ -357,18 +1062,8 @@ mod tests {
         let (_tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
-            active_prompt: Arc::new(Mutex::new(None)),
             pending_prompts: [ep("1")].into_iter().collect(),
-            prompts_to_drop: Vec::new(),
-            dead_prompts: Vec::new(),
-            recv_timeout: Duration::from_millis(100),
-            ui: FlutterUi {
-                cmd: "".to_string(),
-            },
-            client: StubClient,
-            running: true,
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
         };
 
         let res = timeout(Duration::from_millis(1000), w.pull_updates()).await;
@@ -382,6 +1077,7 @@ mod tests {
 
     #[test_case(add("1"), &[], &[], &["1"], &[]; "add new prompt")]
     #[test_case(add("1"), &[], &["1"], &[], &[]; "add prompt that we have been told to drop")]
+    #[test_case(add("1"), &["1"], &[], &["1"], &[]; "add prompt that is already pending")]
     #[test_case(drop_id("1"), &["1"], &[], &[], &[]; "drop for pending prompt")]
     #[test_case(drop_id("1"), &[], &[], &[], &["1"]; "drop prompt not seen yet")]
     #[test]
@@ -401,18 +1097,9 @@ mod tests {
             .collect();
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
-            active_prompt: Arc::new(Mutex::new(None)),
             pending_prompts,
             prompts_to_drop,
-            dead_prompts: Vec::new(),
-            recv_timeout: Duration::from_millis(100),
-            ui: FlutterUi {
-                cmd: "".to_string(),
-            },
-            client: StubClient,
-            running: true,
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
         };
 
         w.process_update(update);
@@ -428,12 +1115,41 @@ mod tests {
         assert_eq!(to_drop, expected_to_drop);
     }
 
-    #[test_case("1", "1", 10, Recv::Success, &["drop-me"], &["dead"]; "recv expected within timeout")]
-    #[test_case("2", "1", 10, Recv::Unexpected, &["drop-me"], &["dead"]; "recv unexpected within timeout")]
-    #[test_case("dead", "1", 10, Recv::DeadPrompt, &["drop-me"], &[]; "recv dead prompt")]
-    #[test_case("1", "1", 200, Recv::Timeout, &[], &["dead", "1"]; "recv expected after timeout")]
-    #[tokio::test]
-    async fn wait_for_expected_prompt(
+    #[test]
+    fn drop_actioned_updates_pending_prompts_and_published_queue() {
+        let (_, rx_prompts) = unbounded_channel();
+        let (_, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [ep("1"), ep("2"), ep("3")].into_iter().collect(),
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
+        };
+        let active_prompt = w.read_only_active_prompt();
+
+        w.drop_actioned(vec![PromptId("2".to_string()), PromptId("4".to_string())]);
+
+        let pending: Vec<&str> = w
+            .pending_prompts
+            .iter()
+            .map(|ep| ep.prompt.id().0.as_str())
+            .collect();
+        let queued: Vec<String> = active_prompt
+            .queued()
+            .iter()
+            .map(|p| p.id().0.clone())
+            .collect();
+
+        assert_eq!(pending, &["1", "3"]);
+        assert_eq!(queued, &["1", "3"]);
+        assert_eq!(w.prompts_to_drop, vec![PromptId("4".to_string())]);
+    }
+
+    #[test_case("1", "1", 10, Recv::Success, &["drop-me"], &["dead"]; "recv expected within timeout")]
+    #[test_case("2", "1", 10, Recv::Unexpected, &["drop-me"], &["dead"]; "recv unexpected within timeout")]
+    #[test_case("dead", "1", 10, Recv::DeadPrompt, &["drop-me"], &[]; "recv dead prompt")]
+    #[test_case("1", "1", 200, Recv::Timeout, &[], &["dead", "1"]; "recv expected after timeout")]
+    #[tokio::test]
+    async fn wait_for_expected_prompt(
         sent_id: &str,
         expected_id: &str,
         sleep_ms: u64,
@@ -445,18 +1161,8 @@ mod tests {
         let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
-            active_prompt: Arc::new(Mutex::new(None)),
-            pending_prompts: VecDeque::new(),
-            prompts_to_drop: Vec::new(),
             dead_prompts: vec![PromptId("dead".to_string())],
-            recv_timeout: Duration::from_millis(100),
-            ui: FlutterUi {
-                cmd: "".to_string(),
-            },
-            client: StubClient,
-            running: true,
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
         };
 
         tokio::spawn(async move {
@@ -505,18 +1211,9 @@ mod tests {
         let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
-            active_prompt: Arc::new(Mutex::new(None)),
-            pending_prompts: VecDeque::new(),
-            prompts_to_drop: Vec::new(),
             dead_prompts: vec![PromptId("dead".to_string())],
-            recv_timeout: Duration::from_millis(100),
-            ui: FlutterUi {
-                cmd: "".to_string(),
-            },
-            client: StubClient,
             running: false,
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
         };
 
         let _ = tx_actioned_prompts.send(ActionedPrompt::NotFound {
@@ -538,24 +1235,48 @@ mod tests {
         );
     }
 
+    #[test_case("1", Recv::Gone, &["3"], &[]; "drop for expected prompt")]
+    #[test_case("2", Recv::Updated, &["3"], &["2"]; "drop for prompt not seen yet")]
+    #[test_case("3", Recv::Updated, &[], &[]; "drop for pending prompt")]
+    #[tokio::test]
+    async fn wait_for_expected_prompt_sees_drops(
+        dropped_id: &str,
+        expected_recv: Recv,
+        expected_pending: &[&str],
+        expected_to_drop: &[&str],
+    ) {
+        let (tx_prompts, rx_prompts) = unbounded_channel();
+        let (_tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [ep("3")].into_iter().collect(),
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
+        };
+
+        let _ = tx_prompts.send(drop_id(dropped_id));
+        let recv = w.wait_for_expected_prompt(&PromptId("1".to_string())).await;
+
+        let pending: Vec<&str> = w
+            .pending_prompts
+            .iter()
+            .map(|ep| ep.prompt.id().0.as_str())
+            .collect();
+        let to_drop: Vec<&str> = w.prompts_to_drop.iter().map(|id| id.0.as_str()).collect();
+
+        assert_eq!(recv, expected_recv);
+        assert_eq!(pending, expected_pending);
+        assert_eq!(to_drop, expected_to_drop);
+    }
+
     #[tokio::test]
     async fn wait_for_expected_prompt_closed_channel() {
         let (_, rx_prompts) = unbounded_channel();
         let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
-            active_prompt: Arc::new(Mutex::new(None)),
-            pending_prompts: VecDeque::new(),
-            prompts_to_drop: Vec::new(),
             dead_prompts: vec![PromptId("dead".to_string())],
-            recv_timeout: Duration::from_millis(100),
-            ui: FlutterUi {
-                cmd: "".to_string(),
-            },
-            client: StubClient,
             running: false,
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
         };
 
         drop(tx_actioned_prompts);
@@ -645,26 +1366,19 @@ mod tests {
     async fn sequence(updates: Vec<PromptUpdate>, replies: &[Reply]) {
         let (tx_prompts, rx_prompts) = unbounded_channel();
         let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
-        let active_prompt = Arc::new(Mutex::new(None));
+        let active_prompt = watch::Sender::new(ActivePrompt::Empty);
         let ui = TestUi {
             replies: replies.to_vec(),
             tx: tx_actioned_prompts,
             active_prompt: ReadOnlyActivePrompt {
-                active_prompt: active_prompt.clone(),
+                rx: active_prompt.subscribe(),
+                rx_queue: watch::Sender::new(Vec::new()).subscribe(),
             },
         };
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
             active_prompt,
-            pending_prompts: VecDeque::new(),
-            prompts_to_drop: Vec::new(),
-            dead_prompts: vec![],
-            recv_timeout: Duration::from_millis(100),
-            ui,
-            client: StubClient,
-            running: true,
+            ..test_worker(rx_prompts, rx_actioned_prompts, ui, StubClient)
         };
 
         // We need this env var set to be able to generate the appropriate UI options
@@ -680,6 +1394,113 @@ mod tests {
         assert!(!w.running, "drop(tx_prompts) should shut down the worker");
     }
 
+    #[test_case(ActionedPrompt::Actioned { id: PromptId("1".to_string()), others: Vec::new() }, true; "actioned")]
+    #[test_case(ActionedPrompt::NotFound { id: PromptId("1".to_string()) }, false; "not found")]
+    #[tokio::test]
+    async fn step_publishes_final_prompt_state(actioned: ActionedPrompt, expect_actioned: bool) {
+        let (_tx_prompts, rx_prompts) = unbounded_channel();
+        let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [home_ep("1", "/home/ubuntu/foo.txt")].into_iter().collect(),
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
+        };
+        let active_prompt = w.read_only_active_prompt();
+
+        // We need this env var set to be able to generate the appropriate UI options
+        // for the home interface
+        env::set_var("SNAP_REAL_HOME", "/home/ubuntu");
+        let _ = tx_actioned_prompts.send(actioned);
+        w.step().await.unwrap();
+
+        assert!(
+            active_prompt.get().is_none(),
+            "prompt should no longer be active"
+        );
+        match &*active_prompt.watch().borrow() {
+            ActivePrompt::Actioned(id) if expect_actioned => assert_eq!(id.0, "1"),
+            ActivePrompt::Dropped(id) if !expect_actioned => assert_eq!(id.0, "1"),
+            state => panic!("unexpected final state: {state:?}"),
+        }
+    }
+
+    fn grouped_ep(ids: &[&str]) -> EnrichedPrompt {
+        let mut eps = ids
+            .iter()
+            .map(|id| home_ep(id, &format!("/home/ubuntu/Downloads/{id}.txt")));
+        let mut ep = eps.next().unwrap();
+        ep.grouped = eps.map(|ep| ep.prompt).collect();
+
+        ep
+    }
+
+    fn ids(prompts: &[TypedPrompt]) -> Vec<&str> {
+        prompts.iter().map(|p| p.id().0.as_str()).collect()
+    }
+
+    #[test]
+    fn process_update_groups_related_prompts() {
+        let (_, rx_prompts) = unbounded_channel();
+        let (_, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [home_ep("1", "/home/ubuntu/Downloads/1.txt")]
+                .into_iter()
+                .collect(),
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
+        };
+        let active_prompt = w.read_only_active_prompt();
+
+        w.process_update(PromptUpdate::Add(home_ep(
+            "2",
+            "/home/ubuntu/Downloads/2.txt",
+        )));
+        w.process_update(PromptUpdate::Add(home_ep(
+            "3",
+            "/home/ubuntu/Pictures/3.txt",
+        )));
+        w.publish_queue();
+
+        let pending: Vec<&str> = w
+            .pending_prompts
+            .iter()
+            .map(|ep| ep.prompt.id().0.as_str())
+            .collect();
+        assert_eq!(pending, &["1", "3"]);
+        assert_eq!(ids(&w.pending_prompts[0].grouped), &["2"]);
+        assert_eq!(ids(&active_prompt.queued()), &["1", "2", "3"]);
+
+        // Dropping the first prompt in a group promotes the next one in its place
+        w.process_update(drop_id("1"));
+        assert_eq!(w.pending_prompts[0].prompt.id().0, "2");
+        assert!(w.pending_prompts[0].grouped.is_empty());
+        assert!(w.prompts_to_drop.is_empty());
+    }
+
+    #[test_case(ActionedPrompt::Actioned { id: PromptId("1".to_string()), others: vec![PromptId("2".to_string())] }, &[]; "actioned")]
+    #[test_case(ActionedPrompt::NotFound { id: PromptId("1".to_string()) }, &["2", "3"]; "not found")]
+    #[tokio::test]
+    async fn step_handles_grouped_prompts(actioned: ActionedPrompt, expected_pending: &[&str]) {
+        let (_tx_prompts, rx_prompts) = unbounded_channel();
+        let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [grouped_ep(&["1", "2", "3"])].into_iter().collect(),
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
+        };
+        let active_prompt = w.read_only_active_prompt();
+
+        // We need this env var set to be able to generate the appropriate UI options
+        // for the home interface
+        env::set_var("SNAP_REAL_HOME", "/home/ubuntu");
+        let _ = tx_actioned_prompts.send(actioned);
+        w.step().await.unwrap();
+
+        // Grouped prompts are never expected to be re-sent by snapd so we must not track them
+        assert!(w.prompts_to_drop.is_empty());
+        assert_eq!(ids(&active_prompt.queued()), expected_pending);
+    }
+
     struct StubUi;
 
     impl SpawnUi for StubUi {
@@ -712,19 +1533,17 @@ mod tests {
         // remain open. Without this calls to recv() immediately returns None.
         let (_tx_prompts, rx_prompts) = unbounded_channel();
         let (_tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
-        let active_prompt = Arc::new(Mutex::new(None));
+        let active_prompt = watch::Sender::new(ActivePrompt::Empty);
 
         let mut w = Worker {
-            rx_prompts,
-            rx_actioned_prompts,
             active_prompt,
             pending_prompts: [ep("1")].into_iter().collect(),
-            prompts_to_drop: Vec::new(),
-            dead_prompts: vec![],
-            recv_timeout: Duration::from_millis(100),
-            ui: StubUi,
-            client: AckClient::default(),
-            running: true,
+            ..test_worker(
+                rx_prompts,
+                rx_actioned_prompts,
+                StubUi,
+                AckClient::default(),
+            )
         };
 
         // We need this env var set to be able to generate the appropriate UI options
@@ -751,4 +1570,324 @@ mod tests {
             )]
         );
     }
+
+    #[test_case(TimeoutPolicy::DenyOnce, Some(Lifespan::Single); "deny once")]
+    #[test_case(TimeoutPolicy::DenyForSession, Some(Lifespan::Session); "deny for session")]
+    #[test_case(TimeoutPolicy::LeavePending, None; "leave pending")]
+    #[tokio::test]
+    async fn prompt_timeout_applies_policy(policy: TimeoutPolicy, expected: Option<Lifespan>) {
+        let (_tx_prompts, rx_prompts) = unbounded_channel();
+        let (_tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [home_ep("1", "/home/ubuntu/foo.txt")].into_iter().collect(),
+            recv_timeout: Duration::from_secs(10),
+            prompt_timeout: Some(PromptTimeout {
+                timeout: Duration::from_millis(50),
+                policy,
+            }),
+            ..test_worker(
+                rx_prompts,
+                rx_actioned_prompts,
+                StubUi,
+                AckClient::default(),
+            )
+        };
+
+        // We need this env var set to be able to generate the appropriate UI options
+        // for the home interface
+        env::set_var("SNAP_REAL_HOME", "/home/ubuntu");
+        let res = timeout(Duration::from_secs(1), w.step()).await;
+        assert!(res.is_ok(), "prompt timeout was not applied");
+
+        let replies: Vec<(Action, Lifespan)> = w
+            .client
+            .seen
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|(id, reply)| match reply {
+                TypedPromptReply::Home(r) if id.0 == "1" => (r.action, r.lifespan),
+                _ => panic!("unexpected reply: {id:?} {reply:?}"),
+            })
+            .collect();
+
+        assert_eq!(
+            replies,
+            expected
+                .map(|lifespan| vec![(Action::Deny, lifespan)])
+                .unwrap_or_default()
+        );
+        assert!(matches!(
+            &*w.active_prompt.borrow(),
+            ActivePrompt::TimedOut(id) if id.0 == "1"
+        ));
+
+        // Prompts left pending are held back rather than being shown again straight away
+        let left_pending: Vec<&str> = w
+            .left_pending
+            .iter()
+            .map(|ep| ep.prompt.id().0.as_str())
+            .collect();
+        let expected_left_pending: &[&str] = if expected.is_none() { &["1"] } else { &[] };
+        assert!(w.pending_prompts.is_empty());
+        assert_eq!(left_pending, expected_left_pending);
+    }
+
+    #[test]
+    fn prompts_left_pending_are_queued_again_when_a_new_prompt_arrives() {
+        let (_, rx_prompts) = unbounded_channel();
+        let (_, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            left_pending: [ep("1")].into_iter().collect(),
+            ..test_worker(rx_prompts, rx_actioned_prompts, StubUi, StubClient)
+        };
+
+        w.process_update(drop_id("2"));
+        assert!(w.pending_prompts.is_empty(), "only new prompts re-queue");
+
+        w.process_update(add("3"));
+        let pending: Vec<&str> = w
+            .pending_prompts
+            .iter()
+            .map(|ep| ep.prompt.id().0.as_str())
+            .collect();
+
+        assert_eq!(pending, &["3", "1"]);
+        assert!(w.left_pending.is_empty());
+    }
+
+    /// A UI that blocks in spawn until it is dropped, as the per-prompt Flutter UI does while the
+    /// user has yet to reply.
+    #[derive(Default)]
+    struct BlockingUi {
+        dropped: Arc<Mutex<usize>>,
+    }
+
+    struct DropGuard(Arc<Mutex<usize>>);
+
+    impl Drop for DropGuard {
+        fn drop(&mut self) {
+            *self.0.lock().unwrap() += 1;
+        }
+    }
+
+    impl SpawnUi for BlockingUi {
+        async fn spawn(&mut self) -> Result<()> {
+            let _guard = DropGuard(self.dropped.clone());
+            pending().await
+        }
+    }
+
+    #[tokio::test]
+    async fn prompt_timeout_applies_while_the_ui_is_running() {
+        let (_tx_prompts, rx_prompts) = unbounded_channel();
+        let (_tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+        let ui = BlockingUi::default();
+        let dropped = ui.dropped.clone();
+
+        let mut w = Worker {
+            pending_prompts: [home_ep("1", "/home/ubuntu/foo.txt")].into_iter().collect(),
+            recv_timeout: Duration::from_secs(10),
+            prompt_timeout: Some(PromptTimeout {
+                timeout: Duration::from_millis(50),
+                policy: TimeoutPolicy::DenyOnce,
+            }),
+            ..test_worker(rx_prompts, rx_actioned_prompts, ui, AckClient::default())
+        };
+
+        // We need this env var set to be able to generate the appropriate UI options
+        // for the home interface
+        env::set_var("SNAP_REAL_HOME", "/home/ubuntu");
+        let res = timeout(Duration::from_secs(1), w.step()).await;
+        assert!(res.is_ok(), "prompt timeout was not applied");
+
+        assert_eq!(*dropped.lock().unwrap(), 1, "UI should have been stopped");
+        assert_eq!(w.client.seen.lock().unwrap().len(), 1);
+        assert!(matches!(
+            &*w.active_prompt.borrow(),
+            ActivePrompt::TimedOut(id) if id.0 == "1"
+        ));
+    }
+
+    #[test_case("deny-once", TimeoutPolicy::DenyOnce; "deny once")]
+    #[test_case("deny-for-session", TimeoutPolicy::DenyForSession; "deny for session")]
+    #[test_case("leave-pending", TimeoutPolicy::LeavePending; "leave pending")]
+    #[test]
+    fn timeout_policy_parsing_works(raw: &str, expected: TimeoutPolicy) {
+        assert_eq!(raw.parse::<TimeoutPolicy>().unwrap(), expected);
+    }
+
+    #[test_case(0, 0; "first start")]
+    #[test_case(1, 10; "first restart")]
+    #[test_case(3, 40; "third restart")]
+    #[test_case(10, 100; "capped")]
+    #[test]
+    fn persistent_ui_restart_delay(restarts: u32, expected_ms: u64) {
+        let mut ui = PersistentFlutterUi::new("true".to_string());
+        ui.backoff_base = Duration::from_millis(10);
+        ui.backoff_max = Duration::from_millis(100);
+        ui.restarts = restarts;
+
+        assert_eq!(ui.restart_delay(), Duration::from_millis(expected_ms));
+    }
+
+    #[tokio::test]
+    async fn persistent_ui_restarts_after_exit() {
+        let mut ui = PersistentFlutterUi::new("true".to_string());
+        ui.backoff_base = Duration::from_millis(1);
+
+        ui.spawn().await.unwrap();
+        timeout(Duration::from_millis(1000), ui.exited())
+            .await
+            .expect("UI process should have exited");
+
+        // The restart is scheduled rather than performed immediately
+        ui.spawn().await.unwrap();
+        assert!(ui.child.is_none());
+        assert!(ui.restart_at.is_some());
+
+        timeout(Duration::from_millis(1000), ui.exited())
+            .await
+            .expect("UI restart should have been due");
+        ui.spawn().await.unwrap();
+
+        assert_eq!(ui.restarts, 2);
+        assert!(ui.child.is_some());
+        assert!(ui.restart_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn persistent_ui_restart_does_not_block() {
+        let mut ui = PersistentFlutterUi::new("true".to_string());
+        ui.backoff_base = Duration::from_secs(60 * 60);
+        ui.restarts = 1;
+
+        timeout(Duration::from_millis(100), ui.spawn())
+            .await
+            .expect("spawn should not wait for the restart delay")
+            .unwrap();
+        assert!(ui.child.is_none());
+
+        let exited = timeout(Duration::from_millis(100), ui.exited()).await;
+        assert!(exited.is_err(), "restart should not be due yet");
+    }
+
+    #[derive(Default)]
+    struct CrashingUi {
+        spawns: usize,
+        crashed: bool,
+    }
+
+    impl SpawnUi for CrashingUi {
+        async fn spawn(&mut self) -> Result<()> {
+            self.spawns += 1;
+            Ok(())
+        }
+
+        async fn exited(&mut self) {
+            if self.crashed {
+                pending().await
+            }
+            self.crashed = true;
+        }
+    }
+
+    #[tokio::test]
+    async fn ui_exiting_before_reply_respawns_ui() {
+        let (_tx_prompts, rx_prompts) = unbounded_channel();
+        let (tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [home_ep("1", "/home/ubuntu/foo.txt")].into_iter().collect(),
+            recv_timeout: Duration::from_millis(500),
+            ..test_worker(
+                rx_prompts,
+                rx_actioned_prompts,
+                CrashingUi::default(),
+                StubClient,
+            )
+        };
+
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(50)).await;
+            let _ = tx_actioned_prompts.send(ActionedPrompt::Actioned {
+                id: PromptId("1".to_string()),
+                others: Vec::new(),
+            });
+        });
+
+        // We need this env var set to be able to generate the appropriate UI options
+        // for the home interface
+        env::set_var("SNAP_REAL_HOME", "/home/ubuntu");
+        w.step().await.unwrap();
+
+        assert_eq!(w.ui.spawns, 2);
+        assert!(matches!(
+            &*w.active_prompt.borrow(),
+            ActivePrompt::Actioned(id) if id.0 == "1"
+        ));
+    }
+
+    #[derive(Default)]
+    struct SlowUi {
+        closed: bool,
+    }
+
+    impl SpawnUi for SlowUi {
+        async fn spawn(&mut self) -> Result<()> {
+            Ok(())
+        }
+
+        async fn close(&mut self) {
+            self.closed = true;
+        }
+    }
+
+    #[tokio::test]
+    async fn cancelling_shutdown_closes_ui_and_leaves_prompt_pending() {
+        let (_tx_prompts, rx_prompts) = unbounded_channel();
+        let (_tx_actioned_prompts, rx_actioned_prompts) = unbounded_channel();
+
+        let mut w = Worker {
+            pending_prompts: [
+                home_ep("1", "/home/ubuntu/foo.txt"),
+                home_ep("2", "/home/ubuntu/bar.txt"),
+            ]
+            .into_iter()
+            .collect(),
+            recv_timeout: Duration::from_secs(60),
+            running: false,
+            ..test_worker(
+                rx_prompts,
+                rx_actioned_prompts,
+                SlowUi::default(),
+                StubClient,
+            )
+        };
+
+        let shutdown = CancellationToken::new();
+        let token = shutdown.clone();
+        tokio::spawn(async move {
+            sleep(Duration::from_millis(50)).await;
+            token.cancel();
+        });
+
+        // We need this env var set to be able to generate the appropriate UI options
+        // for the home interface
+        env::set_var("SNAP_REAL_HOME", "/home/ubuntu");
+        timeout(Duration::from_millis(1000), w.run(shutdown))
+            .await
+            .expect("worker should stop when cancelled")
+            .unwrap();
+
+        assert!(!w.running);
+        assert!(w.ui.closed, "UI should have been closed");
+        assert_eq!(w.pending_prompts.len(), 1);
+        assert!(matches!(
+            &*w.active_prompt.borrow(),
+            ActivePrompt::Active { input, .. } if input.id().0 == "1"
+        ));
+    }
 }
@@ -47,18 +47,45 @@ pub enum Error {
     #[error(transparent)]
     Regex(#[from] regex::Error),
 
+    #[error(transparent)]
+    Tonic(#[from] tonic::transport::Error),
+
     #[error("failed prompt sequence: {error}")]
     FailedPromptSequence { error: MatchError },
 
+    #[error("invalid daemon config: {reason}")]
+    InvalidConfig { reason: String },
+
     #[error("invalid custom permissions: requested={requested:?} but available={available:?}")]
     InvalidCustomPermissions {
         requested: Vec<String>,
         available: Vec<String>,
     },
 
+    #[error("invalid duration {duration:?}: {reason}")]
+    InvalidDuration {
+        duration: String,
+        reason: &'static str,
+    },
+
     #[error("snapd provided a home interface prompt for a path outside of home: path={path:?} but home={home:?}")]
     InvalidHomePromptPath { path: String, home: String },
 
+    #[error("invalid path pattern {pattern:?}: {reason}")]
+    InvalidPathPattern {
+        pattern: String,
+        reason: &'static str,
+    },
+
+    #[error("invalid rule edit: {reason}")]
+    InvalidRuleEdit { reason: String },
+
+    #[error("invalid {field} filter for rules: {value:?}")]
+    InvalidRuleFilter { field: &'static str, value: String },
+
+    #[error("invalid timestamp {raw:?}: expected an RFC 3339 timestamp or YYYY-MM-DD date")]
+    InvalidTimestamp { raw: String },
+
     #[error("{version} is not supported recording version.")]
     InvalidRecordingVersion { version: u8 },
 
@@ -80,8 +107,8 @@ pub enum Error {
     #[error("error message returned from snapd: {message}")]
     SnapdError { status: StatusCode, message: String },
 
-    #[error("{interface} is not currently supported for apparmor prompting")]
-    UnsupportedInterface { interface: String },
+    #[error("unable to determine the location of the history file")]
+    UnableToLocateHistory,
 
     #[error("unable to update log filter: {reason}")]
     UnableToUpdateLogFilter { reason: String },
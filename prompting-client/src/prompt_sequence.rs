@@ -1,23 +1,73 @@
 use crate::snapd_client::{
+    duration::GoDuration,
     interfaces::{
-        home::HomeInterface, ConstraintsFilter, ReplyConstraintsOverrides, SnapInterface,
+        home::HomeInterface, with_interfaces, ConstraintsFilter, ReplyConstraintsOverrides,
+        SnapInterface,
     },
     Action, Lifespan, Prompt, PromptReply, TypedPrompt, TypedPromptReply,
 };
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use std::{collections::VecDeque, fs};
 
 #[allow(dead_code)]
 #[derive(Debug, Default, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(try_from = "RawPromptSequence")]
 pub struct PromptSequence {
     version: u8,
     filter: Option<TypedPromptFilter>,
     prompts: VecDeque<TypedPromptCase>,
-    #[serde(skip, default)]
     index: usize,
 }
 
+/// The interface for each filter and prompt case is determined by its "interface" field if
+/// present, falling back to the interface of the top level filter and finally to the home
+/// interface if neither are specified.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawPromptSequence {
+    version: u8,
+    filter: Option<Value>,
+    prompts: VecDeque<Value>,
+}
+
+impl TryFrom<RawPromptSequence> for PromptSequence {
+    type Error = serde_json::Error;
+
+    fn try_from(
+        RawPromptSequence {
+            version,
+            filter,
+            prompts,
+        }: RawPromptSequence,
+    ) -> Result<Self, Self::Error> {
+        let default_interface = filter
+            .as_ref()
+            .and_then(interface_field)
+            .unwrap_or(HomeInterface::NAME)
+            .to_string();
+
+        let filter = filter
+            .map(|f| TypedPromptFilter::try_from_value(f, &default_interface))
+            .transpose()?;
+        let prompts = prompts
+            .into_iter()
+            .map(|p| TypedPromptCase::try_from_value(p, &default_interface))
+            .collect::<Result<_, _>>()?;
+
+        Ok(Self {
+            version,
+            filter,
+            prompts,
+            index: 0,
+        })
+    }
+}
+
+fn interface_field(filter: &Value) -> Option<&str> {
+    filter.get("interface").and_then(Value::as_str)
+}
+
 impl PromptSequence {
     pub fn try_new_from_file(path: &str, vars: &[(&str, &str)]) -> crate::Result<(Self, String)> {
         Self::try_new_from_string(fs::read_to_string(path)?, vars)
@@ -46,15 +96,10 @@ impl PromptSequence {
             None => return Err(MatchError::NoPromptsRemaining),
         };
 
-        match (case, p) {
-            (TypedPromptCase::Home(case), TypedPrompt::Home(p)) => {
-                let res = case
-                    .into_reply_or_error(p, self.index)
-                    .map(TypedPromptReply::Home);
-                self.index += 1;
-                res
-            }
-        }
+        let res = case.into_reply_or_error(p, self.index);
+        self.index += 1;
+
+        res
     }
 
     pub fn is_empty(&self) -> bool {
@@ -75,26 +120,114 @@ fn apply_vars(mut content: String, vars: &[(&str, &str)]) -> String {
     content
 }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-enum TypedPromptCase {
-    Home(PromptCase<HomeInterface>),
-}
+/// Generate the prompt case and filter enums over all supported interfaces.
+///
+/// See [with_interfaces] for how the list of interfaces is defined.
+macro_rules! typed_sequence_enums {
+    (
+        known: [$($variant:ident($iface:ty, $proto:ident)),+ $(,)?],
+        fallback: $fallback:ident($fallback_iface:ty, $fallback_proto:ident) $(,)?
+    ) => {
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        enum TypedPromptCase {
+            $($variant(PromptCase<$iface>),)+
+            $fallback(PromptCase<$fallback_iface>),
+        }
 
-#[derive(Debug, Serialize, Deserialize)]
-#[serde(untagged)]
-enum TypedPromptFilter {
-    Home(PromptFilter<HomeInterface>),
-}
+        impl TypedPromptCase {
+            fn try_from_value(
+                value: Value,
+                default_interface: &str,
+            ) -> Result<Self, serde_json::Error> {
+                let interface = value
+                    .get("prompt-filter")
+                    .and_then(interface_field)
+                    .unwrap_or(default_interface);
+
+                $(
+                    if interface == <$iface>::NAME {
+                        return serde_json::from_value(value).map(Self::$variant);
+                    }
+                )+
+
+                serde_json::from_value(value).map(Self::$fallback)
+            }
+
+            fn interface(&self) -> &str {
+                match self {
+                    $(Self::$variant(_) => <$iface>::NAME,)+
+                    Self::$fallback(case) => case
+                        .prompt_filter
+                        .interface
+                        .as_deref()
+                        .unwrap_or(<$fallback_iface>::NAME),
+                }
+            }
+
+            fn into_reply_or_error(
+                self,
+                p: TypedPrompt,
+                index: usize,
+            ) -> Result<TypedPromptReply, MatchError> {
+                match (self, p) {
+                    $(
+                        (Self::$variant(case), TypedPrompt::$variant(p)) => case
+                            .into_reply_or_error(p, index)
+                            .map(TypedPromptReply::$variant),
+                    )+
+                    (Self::$fallback(case), TypedPrompt::$fallback(p)) => case
+                        .into_reply_or_error(p, index)
+                        .map(TypedPromptReply::$fallback),
+
+                    (case, p) => Err(MatchError::WrongInterface {
+                        expected: case.interface().to_string(),
+                        seen: p.interface().to_string(),
+                    }),
+                }
+            }
+        }
 
-impl TypedPromptFilter {
-    pub fn matches(&self, prompt: &TypedPrompt) -> bool {
-        match (self, prompt) {
-            (Self::Home(f), TypedPrompt::Home(p)) => f.matches(p).is_success(),
+        #[derive(Debug, Serialize)]
+        #[serde(untagged)]
+        enum TypedPromptFilter {
+            $($variant(PromptFilter<$iface>),)+
+            $fallback(PromptFilter<$fallback_iface>),
         }
-    }
+
+        impl TypedPromptFilter {
+            fn try_from_value(
+                value: Value,
+                default_interface: &str,
+            ) -> Result<Self, serde_json::Error> {
+                let interface = interface_field(&value).unwrap_or(default_interface);
+
+                $(
+                    if interface == <$iface>::NAME {
+                        return serde_json::from_value(value).map(Self::$variant);
+                    }
+                )+
+
+                serde_json::from_value(value).map(Self::$fallback)
+            }
+
+            pub fn matches(&self, prompt: &TypedPrompt) -> bool {
+                match (self, prompt) {
+                    $(
+                        (Self::$variant(f), TypedPrompt::$variant(p)) => {
+                            f.matches(p).is_success()
+                        }
+                    )+
+                    (Self::$fallback(f), TypedPrompt::$fallback(p)) => f.matches(p).is_success(),
+                    _ => false,
+                }
+            }
+        }
+    };
 }
 
+with_interfaces!(typed_sequence_enums);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct PromptCase<I>
@@ -244,7 +377,7 @@ where
 {
     action: Action,
     lifespan: Lifespan,
-    duration: Option<String>,
+    duration: Option<GoDuration>,
     constraints: Option<I::ReplyConstraintsOverrides>,
 }
 
@@ -252,7 +385,10 @@ where
 mod tests {
     use super::*;
     use crate::snapd_client::{
-        interfaces::home::{HomeConstraints, HomeConstraintsFilter},
+        interfaces::{
+            device::DeviceConstraints,
+            home::{HomeConstraints, HomeConstraintsFilter, PermissionSet},
+        },
         PromptId,
     };
     use simple_test_case::{dir_cases, test_case};
@@ -289,14 +425,10 @@ mod tests {
                     path.map(|re| re.to_string()).as_deref(),
                     Some("/home/foo/bar")
                 );
-                assert_eq!(requested_permissions, Some(vec!["read".to_string()]));
+                assert_eq!(requested_permissions, Some(PermissionSet::from(["read"])));
                 assert_eq!(
                     available_permissions,
-                    Some(vec![
-                        "read".to_string(),
-                        "write".to_string(),
-                        "execute".to_string()
-                    ])
+                    Some(PermissionSet::from(["read", "write", "execute"]))
                 );
             }
             f => panic!("invalid filter: {f:?}"),
@@ -342,12 +474,12 @@ mod tests {
         let p = Prompt {
             id: PromptId("id".to_string()),
             interface: "home".to_string(),
-            timestamp: "".to_string(),
+            timestamp: Default::default(),
             snap: "test".to_string(),
             constraints: HomeConstraints {
                 path: "/home/foo/bar".to_string(),
-                requested_permissions: vec!["read".to_string()],
-                available_permissions: vec!["read".to_string(), "write".to_string()],
+                requested_permissions: PermissionSet::from(["read"]),
+                available_permissions: PermissionSet::from(["read", "write"]),
             },
         };
 
@@ -375,4 +507,66 @@ mod tests {
 
         assert!(res.is_ok(), "error parsing {path}: {:?}", res);
     }
+
+    #[test_case("10m", true; "valid duration")]
+    #[test_case("10 minutes", false; "invalid duration")]
+    #[test]
+    fn reply_durations_are_validated(duration: &str, expected_ok: bool) {
+        let data = format!(
+            r#"{{
+  "version": 1,
+  "prompts": [
+    {{
+      "prompt-filter": {{ "snap": "testSnap" }},
+      "reply": {{ "action": "allow", "lifespan": "timespan", "duration": "{duration}" }}
+    }}
+  ]
+}}"#
+        );
+
+        let res = PromptSequence::try_new_from_string(&data, &[]);
+
+        assert_eq!(res.is_ok(), expected_ok, "{res:?}");
+    }
+
+    fn camera_prompt() -> TypedPrompt {
+        TypedPrompt::Camera(Prompt {
+            id: PromptId("id".to_string()),
+            interface: "camera".to_string(),
+            timestamp: Default::default(),
+            snap: "cheese".to_string(),
+            constraints: DeviceConstraints {
+                requested_permissions: vec!["access".to_string()],
+                available_permissions: vec!["access".to_string()],
+            },
+        })
+    }
+
+    #[test]
+    fn cases_default_to_the_interface_of_the_top_level_filter() {
+        let data = include_str!("../resources/prompt-sequence-tests/camera_sequence.json");
+        let (mut seq, _) = PromptSequence::try_new_from_string(data, &[]).unwrap();
+        let p = camera_prompt();
+
+        assert!(seq.should_handle(&p));
+        match seq.try_match_next(p) {
+            Ok(TypedPromptReply::Camera(reply)) => assert_eq!(reply.lifespan, Lifespan::Session),
+            res => panic!("expected a camera reply, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn mismatched_interface_errors() {
+        let data = include_str!("../resources/prompt-sequence-tests/simple_sequence.json");
+        let (mut seq, _) =
+            PromptSequence::try_new_from_string(data, &[("BASE_PATH", "/home/foo")]).unwrap();
+
+        match seq.try_match_next(camera_prompt()) {
+            Err(MatchError::WrongInterface { expected, seen }) => {
+                assert_eq!(expected, "home");
+                assert_eq!(seen, "camera");
+            }
+            res => panic!("expected WrongInterface, got {res:?}"),
+        }
+    }
 }
@@ -1,6 +1,102 @@
 // This file is @generated by prost-build.
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchPromptsResponse {
+    #[prost(enumeration = "watch_prompts_response::PromptStatus", tag = "1")]
+    pub status: i32,
+    /// Not set when the status is NO_PROMPT
+    #[prost(string, tag = "2")]
+    pub prompt_id: ::prost::alloc::string::String,
+    /// Only set when the status is ACTIVE
+    #[prost(message, optional, tag = "3")]
+    pub current_prompt: ::core::option::Option<GetCurrentPromptResponse>,
+    #[prost(message, optional, tag = "4")]
+    pub queue: ::core::option::Option<PromptQueue>,
+}
+/// Nested message and enum types in `WatchPromptsResponse`.
+pub mod watch_prompts_response {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum PromptStatus {
+        NoPrompt = 0,
+        Active = 1,
+        Actioned = 2,
+        Dropped = 3,
+        /// The user did not reply before the prompt timeout and the configured timeout policy
+        /// has been applied
+        TimedOut = 4,
+    }
+    impl PromptStatus {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                PromptStatus::NoPrompt => "NO_PROMPT",
+                PromptStatus::Active => "ACTIVE",
+                PromptStatus::Actioned => "ACTIONED",
+                PromptStatus::Dropped => "DROPPED",
+                PromptStatus::TimedOut => "TIMED_OUT",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "NO_PROMPT" => Some(Self::NoPrompt),
+                "ACTIVE" => Some(Self::Active),
+                "ACTIONED" => Some(Self::Actioned),
+                "DROPPED" => Some(Self::Dropped),
+                "TIMED_OUT" => Some(Self::TimedOut),
+                _ => None,
+            }
+        }
+    }
+}
+/// The prompts waiting to be shown once the current prompt has been actioned
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PromptQueue {
+    #[prost(message, repeated, tag = "1")]
+    pub prompts: ::prost::alloc::vec::Vec<QueuedPrompt>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueuedPrompt {
+    #[prost(string, tag = "1")]
+    pub prompt_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub snap_name: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub interface: ::prost::alloc::string::String,
+    /// Only set for interfaces that request access to a path
+    #[prost(string, tag = "4")]
+    pub requested_path: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "5")]
+    pub requested_permissions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ReplyToPromptsResponse {
+    /// The result of replying to the prompt identified in the request
+    #[prost(message, optional, tag = "1")]
+    pub reply: ::core::option::Option<PromptReplyResponse>,
+    /// Queued prompts from the same snap that the same action was applied to
+    #[prost(string, repeated, tag = "2")]
+    pub actioned_prompt_ids: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PromptReply {
     #[prost(string, tag = "1")]
     pub prompt_id: ::prost::alloc::string::String,
@@ -8,7 +104,10 @@ pub struct PromptReply {
     pub action: i32,
     #[prost(enumeration = "Lifespan", tag = "3")]
     pub lifespan: i32,
-    #[prost(oneof = "prompt_reply::PromptReply", tags = "4")]
+    /// Required when the lifespan is TIMESPAN, in the format accepted by Go's time.ParseDuration
+    #[prost(string, optional, tag = "8")]
+    pub duration: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(oneof = "prompt_reply::PromptReply", tags = "4, 5, 6, 7")]
     pub prompt_reply: ::core::option::Option<prompt_reply::PromptReply>,
 }
 /// Nested message and enum types in `PromptReply`.
@@ -18,6 +117,12 @@ pub mod prompt_reply {
     pub enum PromptReply {
         #[prost(message, tag = "4")]
         HomePromptReply(super::HomePromptReply),
+        #[prost(message, tag = "5")]
+        CameraPromptReply(super::CameraPromptReply),
+        #[prost(message, tag = "6")]
+        AudioRecordPromptReply(super::AudioRecordPromptReply),
+        #[prost(message, tag = "7")]
+        GenericPromptReply(super::GenericPromptReply),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -73,7 +178,7 @@ pub mod prompt_reply_response {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct GetCurrentPromptResponse {
-    #[prost(oneof = "get_current_prompt_response::Prompt", tags = "1")]
+    #[prost(oneof = "get_current_prompt_response::Prompt", tags = "1, 2, 3, 4")]
     pub prompt: ::core::option::Option<get_current_prompt_response::Prompt>,
 }
 /// Nested message and enum types in `GetCurrentPromptResponse`.
@@ -83,6 +188,12 @@ pub mod get_current_prompt_response {
     pub enum Prompt {
         #[prost(message, tag = "1")]
         HomePrompt(super::HomePrompt),
+        #[prost(message, tag = "2")]
+        CameraPrompt(super::CameraPrompt),
+        #[prost(message, tag = "3")]
+        AudioRecordPrompt(super::AudioRecordPrompt),
+        #[prost(message, tag = "4")]
+        GenericPrompt(super::GenericPrompt),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -127,18 +238,133 @@ pub mod home_prompt {
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct CameraPromptReply {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CameraPrompt {
+    #[prost(message, optional, tag = "1")]
+    pub meta_data: ::core::option::Option<MetaData>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct AudioRecordPromptReply {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AudioRecordPrompt {
+    #[prost(message, optional, tag = "1")]
+    pub meta_data: ::core::option::Option<MetaData>,
+}
+/// Replies to prompts for interfaces without a dedicated message type are only supported with
+/// the SINGLE lifespan.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, Copy, PartialEq, ::prost::Message)]
+pub struct GenericPromptReply {}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GenericPrompt {
+    #[prost(message, optional, tag = "1")]
+    pub meta_data: ::core::option::Option<MetaData>,
+    #[prost(string, tag = "2")]
+    pub interface: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub constraints: ::prost::alloc::vec::Vec<generic_prompt::Constraint>,
+}
+/// Nested message and enum types in `GenericPrompt`.
+pub mod generic_prompt {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Message)]
+    pub struct Constraint {
+        #[prost(string, tag = "1")]
+        pub key: ::prost::alloc::string::String,
+        #[prost(string, tag = "2")]
+        pub value: ::prost::alloc::string::String,
+    }
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct MetaData {
     #[prost(string, tag = "1")]
     pub prompt_id: ::prost::alloc::string::String,
     #[prost(string, tag = "2")]
     pub snap_name: ::prost::alloc::string::String,
+    /// Only set for snaps installed from the store
     #[prost(string, tag = "3")]
     pub store_url: ::prost::alloc::string::String,
     #[prost(string, tag = "4")]
     pub publisher: ::prost::alloc::string::String,
     #[prost(string, tag = "5")]
     pub updated_at: ::prost::alloc::string::String,
+    /// When snapd created the prompt as an RFC 3339 timestamp
+    #[prost(string, tag = "6")]
+    pub timestamp: ::prost::alloc::string::String,
+    /// How long ago the prompt was created at the time it was sent to the UI
+    #[prost(uint64, tag = "7")]
+    pub age_ms: u64,
+    #[prost(enumeration = "meta_data::PublisherValidation", tag = "8")]
+    pub publisher_validation: i32,
+    #[prost(string, tag = "9")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub revision: ::prost::alloc::string::String,
+    #[prost(string, tag = "11")]
+    pub channel: ::prost::alloc::string::String,
+    #[prost(string, tag = "12")]
+    pub confinement: ::prost::alloc::string::String,
+    #[prost(string, tag = "13")]
+    pub title: ::prost::alloc::string::String,
+    #[prost(string, tag = "14")]
+    pub summary: ::prost::alloc::string::String,
+    /// The snapd REST API path (/v2/icons/<name>/icon) for fetching the snap icon if it has one.
+    /// This needs to be requested from snapd and is not a path on the filesystem.
+    #[prost(string, tag = "15")]
+    pub icon_api_path: ::prost::alloc::string::String,
+}
+/// Nested message and enum types in `MetaData`.
+pub mod meta_data {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum PublisherValidation {
+        /// Locally installed snaps have no publisher validation
+        Unknown = 0,
+        Unproven = 1,
+        Verified = 2,
+        Starred = 3,
+    }
+    impl PublisherValidation {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                PublisherValidation::Unknown => "UNKNOWN",
+                PublisherValidation::Unproven => "UNPROVEN",
+                PublisherValidation::Verified => "VERIFIED",
+                PublisherValidation::Starred => "STARRED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "UNKNOWN" => Some(Self::Unknown),
+                "UNPROVEN" => Some(Self::Unproven),
+                "VERIFIED" => Some(Self::Verified),
+                "STARRED" => Some(Self::Starred),
+                _ => None,
+            }
+        }
+    }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
@@ -152,6 +378,265 @@ pub struct SetLoggingFilterResponse {
     #[prost(string, tag = "1")]
     pub current: ::prost::alloc::string::String,
 }
+/// Empty fields are not used for filtering
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRulesRequest {
+    #[prost(string, tag = "1")]
+    pub snap: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub interface: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListRulesResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub rules: ::prost::alloc::vec::Vec<Rule>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Rule {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub timestamp: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub snap: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub interface: ::prost::alloc::string::String,
+    #[prost(enumeration = "Action", tag = "5")]
+    pub outcome: i32,
+    #[prost(enumeration = "Lifespan", tag = "6")]
+    pub lifespan: i32,
+    /// Only set for rules with a TIMESPAN lifespan
+    #[prost(string, tag = "7")]
+    pub expiration: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "8")]
+    pub permissions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// Only set for interfaces that have a path pattern
+    #[prost(string, tag = "9")]
+    pub path_pattern: ::prost::alloc::string::String,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RuleUpdate {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(enumeration = "rule_update::RuleUpdateType", tag = "2")]
+    pub update_type: i32,
+    /// Only set for CHANGED updates
+    #[prost(message, optional, tag = "3")]
+    pub rule: ::core::option::Option<Rule>,
+}
+/// Nested message and enum types in `RuleUpdate`.
+pub mod rule_update {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum RuleUpdateType {
+        Changed = 0,
+        Removed = 1,
+        Expired = 2,
+    }
+    impl RuleUpdateType {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                RuleUpdateType::Changed => "CHANGED",
+                RuleUpdateType::Removed => "REMOVED",
+                RuleUpdateType::Expired => "EXPIRED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "CHANGED" => Some(Self::Changed),
+                "REMOVED" => Some(Self::Removed),
+                "EXPIRED" => Some(Self::Expired),
+                _ => None,
+            }
+        }
+    }
+}
+/// Only the fields that are set are updated
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateRuleRequest {
+    #[prost(string, tag = "1")]
+    pub id: ::prost::alloc::string::String,
+    #[prost(enumeration = "Action", optional, tag = "2")]
+    pub outcome: ::core::option::Option<i32>,
+    #[prost(enumeration = "Lifespan", optional, tag = "3")]
+    pub lifespan: ::core::option::Option<i32>,
+    /// Required when setting the lifespan to TIMESPAN
+    #[prost(string, optional, tag = "4")]
+    pub duration: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub path_pattern: ::core::option::Option<::prost::alloc::string::String>,
+    /// Permissions are only updated if this is non-empty
+    #[prost(string, repeated, tag = "6")]
+    pub permissions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// Empty fields are not used for filtering
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetHistoryRequest {
+    /// The maximum number of entries to return, with 0 returning all entries
+    #[prost(uint32, tag = "1")]
+    pub limit: u32,
+    #[prost(string, tag = "2")]
+    pub snap: ::prost::alloc::string::String,
+    /// Only return REPLIED entries
+    #[prost(bool, tag = "3")]
+    pub replies_only: bool,
+}
+/// Entries are ordered from most to least recent
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetHistoryResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub entries: ::prost::alloc::vec::Vec<HistoryEntry>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct HistoryEntry {
+    #[prost(string, tag = "1")]
+    pub timestamp: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub prompt_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub snap: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub interface: ::prost::alloc::string::String,
+    #[prost(enumeration = "history_entry::HistoryEvent", tag = "5")]
+    pub event: i32,
+    /// Only set for PROMPTED entries: the JSON encoded constraints of the prompt
+    #[prost(string, tag = "6")]
+    pub constraints: ::prost::alloc::string::String,
+    /// The remaining fields are only set for REPLIED entries
+    #[prost(enumeration = "Action", tag = "7")]
+    pub action: i32,
+    #[prost(enumeration = "Lifespan", tag = "8")]
+    pub lifespan: i32,
+    /// Only set for interfaces that have a path pattern
+    #[prost(string, tag = "9")]
+    pub path_pattern: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "10")]
+    pub permissions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    /// The time between the prompt being shown to the user and the reply being sent
+    #[prost(uint64, optional, tag = "11")]
+    pub latency_ms: ::core::option::Option<u64>,
+    #[prost(enumeration = "history_entry::ReplyOutcome", tag = "12")]
+    pub outcome: i32,
+    /// Only set for RESOLVED_BY_RULE entries: the prompt whose reply created the rule
+    #[prost(string, tag = "13")]
+    pub resolved_by: ::prost::alloc::string::String,
+}
+/// Nested message and enum types in `HistoryEntry`.
+pub mod history_entry {
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum HistoryEvent {
+        Prompted = 0,
+        Displayed = 1,
+        Replied = 2,
+        Dropped = 3,
+        ResolvedByRule = 4,
+    }
+    impl HistoryEvent {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                HistoryEvent::Prompted => "PROMPTED",
+                HistoryEvent::Displayed => "DISPLAYED",
+                HistoryEvent::Replied => "REPLIED",
+                HistoryEvent::Dropped => "DROPPED",
+                HistoryEvent::ResolvedByRule => "RESOLVED_BY_RULE",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "PROMPTED" => Some(Self::Prompted),
+                "DISPLAYED" => Some(Self::Displayed),
+                "REPLIED" => Some(Self::Replied),
+                "DROPPED" => Some(Self::Dropped),
+                "RESOLVED_BY_RULE" => Some(Self::ResolvedByRule),
+                _ => None,
+            }
+        }
+    }
+    #[derive(
+        Clone,
+        Copy,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash,
+        PartialOrd,
+        Ord,
+        ::prost::Enumeration
+    )]
+    #[repr(i32)]
+    pub enum ReplyOutcome {
+        Success = 0,
+        NotFound = 1,
+        TimeoutDeny = 2,
+        MappingFailureDeny = 3,
+        Failed = 4,
+    }
+    impl ReplyOutcome {
+        /// String value of the enum field names used in the ProtoBuf definition.
+        ///
+        /// The values are not transformed in any way and thus are considered stable
+        /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+        pub fn as_str_name(&self) -> &'static str {
+            match self {
+                ReplyOutcome::Success => "SUCCESS",
+                ReplyOutcome::NotFound => "NOT_FOUND",
+                ReplyOutcome::TimeoutDeny => "TIMEOUT_DENY",
+                ReplyOutcome::MappingFailureDeny => "MAPPING_FAILURE_DENY",
+                ReplyOutcome::Failed => "FAILED",
+            }
+        }
+        /// Creates an enum from field names used in the ProtoBuf definition.
+        pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+            match value {
+                "SUCCESS" => Some(Self::Success),
+                "NOT_FOUND" => Some(Self::NotFound),
+                "TIMEOUT_DENY" => Some(Self::TimeoutDeny),
+                "MAPPING_FAILURE_DENY" => Some(Self::MappingFailureDeny),
+                "FAILED" => Some(Self::Failed),
+                _ => None,
+            }
+        }
+    }
+}
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
 pub enum Action {
@@ -184,6 +669,7 @@ pub enum Lifespan {
     Single = 0,
     Session = 1,
     Forever = 2,
+    Timespan = 3,
 }
 impl Lifespan {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -195,6 +681,7 @@ impl Lifespan {
             Lifespan::Single => "SINGLE",
             Lifespan::Session => "SESSION",
             Lifespan::Forever => "FOREVER",
+            Lifespan::Timespan => "TIMESPAN",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -203,6 +690,7 @@ impl Lifespan {
             "SINGLE" => Some(Self::Single),
             "SESSION" => Some(Self::Session),
             "FOREVER" => Some(Self::Forever),
+            "TIMESPAN" => Some(Self::Timespan),
             _ => None,
         }
     }
@@ -363,11 +851,11 @@ pub mod app_armor_prompting_client {
                 );
             self.inner.unary(req, path, codec).await
         }
-        pub async fn reply_to_prompt(
+        pub async fn watch_prompts(
             &mut self,
-            request: impl tonic::IntoRequest<super::PromptReply>,
+            request: impl tonic::IntoRequest<()>,
         ) -> std::result::Result<
-            tonic::Response<super::PromptReplyResponse>,
+            tonic::Response<tonic::codec::Streaming<super::WatchPromptsResponse>>,
             tonic::Status,
         > {
             self.inner
@@ -381,21 +869,108 @@ pub mod app_armor_prompting_client {
                 })?;
             let codec = tonic::codec::ProstCodec::default();
             let path = http::uri::PathAndQuery::from_static(
-                "/apparmor_prompting.AppArmorPrompting/ReplyToPrompt",
+                "/apparmor_prompting.AppArmorPrompting/WatchPrompts",
             );
             let mut req = request.into_request();
             req.extensions_mut()
                 .insert(
                     GrpcMethod::new(
                         "apparmor_prompting.AppArmorPrompting",
-                        "ReplyToPrompt",
+                        "WatchPrompts",
                     ),
                 );
-            self.inner.unary(req, path, codec).await
+            self.inner.server_streaming(req, path, codec).await
         }
-        pub async fn resolve_home_pattern_type(
+        pub async fn get_prompt_queue(
             &mut self,
-            request: impl tonic::IntoRequest<::prost::alloc::string::String>,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<tonic::Response<super::PromptQueue>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/GetPromptQueue",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "apparmor_prompting.AppArmorPrompting",
+                        "GetPromptQueue",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn reply_to_prompt(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PromptReply>,
+        ) -> std::result::Result<
+            tonic::Response<super::PromptReplyResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/ReplyToPrompt",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "apparmor_prompting.AppArmorPrompting",
+                        "ReplyToPrompt",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn reply_to_prompts(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PromptReply>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReplyToPromptsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/ReplyToPrompts",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new(
+                        "apparmor_prompting.AppArmorPrompting",
+                        "ReplyToPrompts",
+                    ),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn resolve_home_pattern_type(
+            &mut self,
+            request: impl tonic::IntoRequest<::prost::alloc::string::String>,
         ) -> std::result::Result<
             tonic::Response<super::ResolveHomePatternTypeResponse>,
             tonic::Status,
@@ -453,6 +1028,135 @@ pub mod app_armor_prompting_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn list_rules(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListRulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListRulesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/ListRules",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("apparmor_prompting.AppArmorPrompting", "ListRules"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn remove_rule(
+            &mut self,
+            request: impl tonic::IntoRequest<::prost::alloc::string::String>,
+        ) -> std::result::Result<tonic::Response<super::Rule>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/RemoveRule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("apparmor_prompting.AppArmorPrompting", "RemoveRule"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn update_rule(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateRuleRequest>,
+        ) -> std::result::Result<tonic::Response<super::Rule>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/UpdateRule",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("apparmor_prompting.AppArmorPrompting", "UpdateRule"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn watch_rules(
+            &mut self,
+            request: impl tonic::IntoRequest<()>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::RuleUpdate>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/WatchRules",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("apparmor_prompting.AppArmorPrompting", "WatchRules"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
+        pub async fn get_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetHistoryResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/apparmor_prompting.AppArmorPrompting/GetHistory",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("apparmor_prompting.AppArmorPrompting", "GetHistory"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -469,6 +1173,23 @@ pub mod app_armor_prompting_server {
             tonic::Response<super::GetCurrentPromptResponse>,
             tonic::Status,
         >;
+        /// Server streaming response type for the WatchPrompts method.
+        type WatchPromptsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::WatchPromptsResponse, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        async fn watch_prompts(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<
+            tonic::Response<Self::WatchPromptsStream>,
+            tonic::Status,
+        >;
+        async fn get_prompt_queue(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<tonic::Response<super::PromptQueue>, tonic::Status>;
         async fn reply_to_prompt(
             &self,
             request: tonic::Request<super::PromptReply>,
@@ -476,6 +1197,13 @@ pub mod app_armor_prompting_server {
             tonic::Response<super::PromptReplyResponse>,
             tonic::Status,
         >;
+        async fn reply_to_prompts(
+            &self,
+            request: tonic::Request<super::PromptReply>,
+        ) -> std::result::Result<
+            tonic::Response<super::ReplyToPromptsResponse>,
+            tonic::Status,
+        >;
         async fn resolve_home_pattern_type(
             &self,
             request: tonic::Request<::prost::alloc::string::String>,
@@ -490,6 +1218,38 @@ pub mod app_armor_prompting_server {
             tonic::Response<super::SetLoggingFilterResponse>,
             tonic::Status,
         >;
+        async fn list_rules(
+            &self,
+            request: tonic::Request<super::ListRulesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListRulesResponse>,
+            tonic::Status,
+        >;
+        async fn remove_rule(
+            &self,
+            request: tonic::Request<::prost::alloc::string::String>,
+        ) -> std::result::Result<tonic::Response<super::Rule>, tonic::Status>;
+        async fn update_rule(
+            &self,
+            request: tonic::Request<super::UpdateRuleRequest>,
+        ) -> std::result::Result<tonic::Response<super::Rule>, tonic::Status>;
+        /// Server streaming response type for the WatchRules method.
+        type WatchRulesStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::RuleUpdate, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        async fn watch_rules(
+            &self,
+            request: tonic::Request<()>,
+        ) -> std::result::Result<tonic::Response<Self::WatchRulesStream>, tonic::Status>;
+        async fn get_history(
+            &self,
+            request: tonic::Request<super::GetHistoryRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetHistoryResponse>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct AppArmorPromptingServer<T: AppArmorPrompting> {
@@ -611,6 +1371,89 @@ pub mod app_armor_prompting_server {
                     };
                     Box::pin(fut)
                 }
+                "/apparmor_prompting.AppArmorPrompting/WatchPrompts" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchPromptsSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<T: AppArmorPrompting> tonic::server::ServerStreamingService<()>
+                    for WatchPromptsSvc<T> {
+                        type Response = super::WatchPromptsResponse;
+                        type ResponseStream = T::WatchPromptsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::watch_prompts(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = WatchPromptsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/apparmor_prompting.AppArmorPrompting/GetPromptQueue" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPromptQueueSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<T: AppArmorPrompting> tonic::server::UnaryService<()>
+                    for GetPromptQueueSvc<T> {
+                        type Response = super::PromptQueue;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::get_prompt_queue(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetPromptQueueSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/apparmor_prompting.AppArmorPrompting/ReplyToPrompt" => {
                     #[allow(non_camel_case_types)]
                     struct ReplyToPromptSvc<T: AppArmorPrompting>(pub Arc<T>);
@@ -657,6 +1500,52 @@ pub mod app_armor_prompting_server {
                     };
                     Box::pin(fut)
                 }
+                "/apparmor_prompting.AppArmorPrompting/ReplyToPrompts" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReplyToPromptsSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<
+                        T: AppArmorPrompting,
+                    > tonic::server::UnaryService<super::PromptReply>
+                    for ReplyToPromptsSvc<T> {
+                        type Response = super::ReplyToPromptsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PromptReply>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::reply_to_prompts(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ReplyToPromptsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/apparmor_prompting.AppArmorPrompting/ResolveHomePatternType" => {
                     #[allow(non_camel_case_types)]
                     struct ResolveHomePatternTypeSvc<T: AppArmorPrompting>(pub Arc<T>);
@@ -755,6 +1644,227 @@ pub mod app_armor_prompting_server {
                     };
                     Box::pin(fut)
                 }
+                "/apparmor_prompting.AppArmorPrompting/ListRules" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListRulesSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<
+                        T: AppArmorPrompting,
+                    > tonic::server::UnaryService<super::ListRulesRequest>
+                    for ListRulesSvc<T> {
+                        type Response = super::ListRulesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListRulesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::list_rules(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ListRulesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/apparmor_prompting.AppArmorPrompting/RemoveRule" => {
+                    #[allow(non_camel_case_types)]
+                    struct RemoveRuleSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<
+                        T: AppArmorPrompting,
+                    > tonic::server::UnaryService<::prost::alloc::string::String>
+                    for RemoveRuleSvc<T> {
+                        type Response = super::Rule;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<::prost::alloc::string::String>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::remove_rule(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RemoveRuleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/apparmor_prompting.AppArmorPrompting/UpdateRule" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateRuleSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<
+                        T: AppArmorPrompting,
+                    > tonic::server::UnaryService<super::UpdateRuleRequest>
+                    for UpdateRuleSvc<T> {
+                        type Response = super::Rule;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateRuleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::update_rule(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UpdateRuleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/apparmor_prompting.AppArmorPrompting/WatchRules" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchRulesSvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<T: AppArmorPrompting> tonic::server::ServerStreamingService<()>
+                    for WatchRulesSvc<T> {
+                        type Response = super::RuleUpdate;
+                        type ResponseStream = T::WatchRulesStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(&mut self, request: tonic::Request<()>) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::watch_rules(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = WatchRulesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/apparmor_prompting.AppArmorPrompting/GetHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetHistorySvc<T: AppArmorPrompting>(pub Arc<T>);
+                    impl<
+                        T: AppArmorPrompting,
+                    > tonic::server::UnaryService<super::GetHistoryRequest>
+                    for GetHistorySvc<T> {
+                        type Response = super::GetHistoryResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetHistoryRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as AppArmorPrompting>::get_history(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetHistorySvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         Ok(
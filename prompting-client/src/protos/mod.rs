@@ -4,6 +4,9 @@ pub mod apparmor_prompting;
 pub use apparmor_prompting::{
     app_armor_prompting_server::{AppArmorPrompting, AppArmorPromptingServer},
     get_current_prompt_response::Prompt,
-    GetCurrentPromptResponse, HomePatternType, HomePrompt, PromptReply, PromptReplyResponse,
-    ResolveHomePatternTypeResponse,
+    AudioRecordPrompt, CameraPrompt, GenericPrompt, GetCurrentPromptResponse, GetHistoryRequest,
+    GetHistoryResponse, HistoryEntry, HomePatternType, HomePrompt, ListRulesRequest,
+    ListRulesResponse, PromptQueue, PromptReply, PromptReplyResponse, QueuedPrompt,
+    ReplyToPromptsResponse, ResolveHomePatternTypeResponse, Rule, RuleUpdate, UpdateRuleRequest,
+    WatchPromptsResponse,
 };
@@ -3,18 +3,24 @@ use crate::{
     prompt_sequence::PromptFilter,
     snapd_client::{
         interfaces::{
-            home::{HomeConstraintsFilter, HomeInterface},
-            SnapInterface,
+            home::{HomeConstraintsFilter, HomeInterface, HomePermission, PermissionSet},
+            with_interfaces, SnapInterface,
         },
-        Action, Prompt, SnapdSocketClient, TypedPrompt, TypedPromptReply,
+        Action, Prompt, PromptReply, SnapdSocketClient, TypedPrompt, TypedPromptReply,
     },
     Error, Result, SNAP_NAME,
 };
 use serde::{Deserialize, Serialize};
-use std::{fs, process::exit};
+use std::{fs, process::exit, time::Duration};
 use tokio::{select, signal::ctrl_c, sync::mpsc::UnboundedReceiver};
 use tracing::info;
 
+/// The version of the recording format that we write.
+///
+/// v1 recordings did not include their version and serialized replies without their interface.
+/// From v2 onwards replies are tagged with the interface they are for.
+const RECORDING_VERSION: u8 = 2;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(tag = "kind", rename_all = "camelCase")]
 pub enum Event {
@@ -24,10 +30,96 @@ pub enum Event {
     Error { data: String },
 }
 
+/// The events of a v1 recording, which differ from [Event] only in how replies are serialized.
+#[derive(Debug, Deserialize)]
+#[serde(tag = "kind", rename_all = "camelCase")]
+enum EventV1 {
+    Prompt { data: TypedPrompt },
+    UiInput { data: serde_json::Value },
+    Reply { data: UntaggedPromptReply },
+    Error { data: String },
+}
+
+impl From<EventV1> for Event {
+    fn from(event: EventV1) -> Self {
+        match event {
+            EventV1::Prompt { data } => Event::Prompt { data },
+            EventV1::UiInput { data } => Event::UiInput { data },
+            EventV1::Reply { data } => Event::Reply { data: data.into() },
+            EventV1::Error { data } => Event::Error { data },
+        }
+    }
+}
+
+macro_rules! untagged_reply {
+    (
+        known: [$($variant:ident($iface:ty, $proto:ident)),+ $(,)?],
+        fallback: $fallback:ident($fallback_iface:ty, $fallback_proto:ident) $(,)?
+    ) => {
+        /// A reply as serialized in v1 recordings. Interfaces sharing the same reply shape are
+        /// resolved to the first matching variant.
+        #[derive(Debug, Deserialize)]
+        #[serde(untagged)]
+        enum UntaggedPromptReply {
+            $($variant(PromptReply<$iface>),)+
+            $fallback(PromptReply<$fallback_iface>),
+        }
+
+        impl From<UntaggedPromptReply> for TypedPromptReply {
+            fn from(reply: UntaggedPromptReply) -> Self {
+                match reply {
+                    $(UntaggedPromptReply::$variant(r) => r.into(),)+
+                    UntaggedPromptReply::$fallback(r) => r.into(),
+                }
+            }
+        }
+    };
+}
+
+with_interfaces!(untagged_reply);
+
+#[derive(Debug, Deserialize)]
+struct RawRecording {
+    #[serde(default = "v1")]
+    version: u8,
+    events: Vec<serde_json::Value>,
+}
+
+fn v1() -> u8 {
+    1
+}
+
+impl TryFrom<RawRecording> for PromptRecording {
+    type Error = Error;
+
+    fn try_from(raw: RawRecording) -> Result<Self> {
+        let events = match raw.version {
+            1 => raw
+                .events
+                .into_iter()
+                .map(|e| serde_json::from_value::<EventV1>(e).map(Event::from))
+                .collect::<serde_json::Result<_>>()?,
+            RECORDING_VERSION => raw
+                .events
+                .into_iter()
+                .map(serde_json::from_value)
+                .collect::<serde_json::Result<_>>()?,
+            version => return Err(Error::InvalidRecordingVersion { version }),
+        };
+
+        Ok(Self {
+            version: RECORDING_VERSION,
+            path: None,
+            filter: None,
+            events,
+        })
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(try_from = "RawRecording")]
 pub struct PromptRecording {
-    #[serde(skip)]
-    version: u8, // currently only v1
+    version: u8,
     #[serde(skip)]
     path: Option<String>,
     #[serde(skip)]
@@ -53,7 +145,7 @@ impl PromptRecording {
         });
 
         Self {
-            version: 1,
+            version: RECORDING_VERSION,
             events: Vec::new(),
             filter,
             path,
@@ -62,7 +154,7 @@ impl PromptRecording {
 
     #[allow(dead_code)]
     pub fn validate(&self) -> Result<()> {
-        if self.version != 1 {
+        if self.version != RECORDING_VERSION {
             return Err(Error::InvalidRecordingVersion {
                 version: self.version,
             });
@@ -122,8 +214,11 @@ impl PromptRecording {
     pub async fn allow_write(&self, p: Prompt<HomeInterface>, c: &SnapdSocketClient) -> Result<()> {
         let id = p.id.clone();
         let reply = HomeInterface::prompt_to_reply(p, Action::Allow)
-            .for_timespan("10s") // Using a timespan so our rule auto-removes
-            .try_with_custom_permissions(vec!["read".to_string(), "write".to_string()])?
+            .for_timespan(Duration::from_secs(10)) // Using a timespan so our rule auto-removes
+            .try_with_custom_permissions(PermissionSet::from([
+                HomePermission::Read,
+                HomePermission::Write,
+            ]))?
             .into();
 
         info!("auto-replying to our own prompt for creating output file");
@@ -136,6 +231,8 @@ impl PromptRecording {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::snapd_client::interfaces::audio_record::AudioRecordInterface;
+    use serde_json::json;
 
     const SELF_WRITE_PROMPT: &str = r#"{
         "id": "C7PLOQW54HGFM===",
@@ -162,4 +259,70 @@ mod tests {
 
         assert!(rec.is_prompt_for_writing_output(&prompt));
     }
+
+    #[test]
+    fn v1_recordings_with_untagged_replies_can_be_read() {
+        let raw = json!({
+            "events": [{
+                "kind": "reply",
+                "data": {
+                    "action": "allow",
+                    "lifespan": "single",
+                    "constraints": {
+                        "path-pattern": "/home/ubuntu/test.json",
+                        "permissions": ["write"]
+                    }
+                }
+            }]
+        });
+
+        let rec: PromptRecording = serde_json::from_value(raw).unwrap();
+
+        assert!(rec.validate().is_ok());
+        assert!(
+            matches!(
+                &rec.events[..],
+                [Event::Reply {
+                    data: TypedPromptReply::Home(_)
+                }]
+            ),
+            "{:?}",
+            rec.events
+        );
+    }
+
+    #[test]
+    fn recordings_round_trip_replies_to_the_correct_interface() {
+        let mut rec = PromptRecording::new(None);
+        rec.events.push(Event::Reply {
+            data: PromptReply::<AudioRecordInterface> {
+                action: Action::Allow,
+                lifespan: crate::snapd_client::Lifespan::Single,
+                duration: None,
+                constraints: Default::default(),
+            }
+            .into(),
+        });
+
+        let json = serde_json::to_string(&rec).unwrap();
+        let parsed: PromptRecording = serde_json::from_str(&json).unwrap();
+
+        assert!(
+            matches!(
+                &parsed.events[..],
+                [Event::Reply {
+                    data: TypedPromptReply::AudioRecord(_)
+                }]
+            ),
+            "{:?}",
+            parsed.events
+        );
+    }
+
+    #[test]
+    fn unsupported_recording_versions_are_rejected() {
+        let res = serde_json::from_value::<PromptRecording>(json!({ "version": 3, "events": [] }));
+
+        assert!(res.is_err());
+    }
 }
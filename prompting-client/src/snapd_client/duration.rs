@@ -0,0 +1,306 @@
+//! Parsing of the durations accepted by snapd for timespan lifespans.
+//!
+//! Durations use the format accepted by Go's [ParseDuration](https://pkg.go.dev/time#ParseDuration):
+//! a possibly signed sequence of decimal numbers, each with an optional fraction and a unit
+//! suffix, such as `300ms`, `1.5h` or `2h45m`. Valid units are `ns`, `us` (or `µs`), `ms`, `s`,
+//! `m` and `h`.
+use crate::{Error, Result};
+use serde::{Deserialize, Serialize};
+use std::{fmt, str::FromStr, time::Duration};
+
+const UNITS: [(&str, u128); 8] = [
+    ("ns", 1),
+    ("us", 1_000),
+    ("µs", 1_000),
+    ("μs", 1_000),
+    ("ms", 1_000_000),
+    ("s", 1_000_000_000),
+    ("m", 60 * 1_000_000_000),
+    ("h", 60 * 60 * 1_000_000_000),
+];
+
+/// Go durations are stored as an int64 number of nanoseconds.
+const MAX_NANOS: u128 = i64::MAX as u128;
+
+/// Only this many digits of a fraction are significant at nanosecond precision for any unit.
+const MAX_FRACTION_DIGITS: usize = 20;
+
+const NANOS_PER_MICRO: u128 = 1_000;
+const NANOS_PER_MILLI: u128 = 1_000_000;
+const NANOS_PER_SEC: u128 = 1_000_000_000;
+
+/// A validated duration in the format accepted by Go's `time.ParseDuration`.
+///
+/// The duration is sent to snapd exactly as it was provided so that it is displayed back to
+/// the user in the same form. Durations created from a [Duration] are formatted in the same
+/// way as Go's `Duration.String` (e.g. `1h30m0s`).
+#[derive(Debug, Clone, Eq, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct GoDuration {
+    raw: String,
+    duration: Duration,
+}
+
+impl GoDuration {
+    pub fn parse(raw: impl Into<String>) -> Result<Self> {
+        let raw = raw.into();
+        let duration = parse_duration(&raw)?;
+
+        Ok(Self { raw, duration })
+    }
+
+    pub fn as_duration(&self) -> Duration {
+        self.duration
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+}
+
+impl PartialEq for GoDuration {
+    fn eq(&self, other: &Self) -> bool {
+        self.duration == other.duration
+    }
+}
+
+impl fmt::Display for GoDuration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.raw)
+    }
+}
+
+impl FromStr for GoDuration {
+    type Err = Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        Self::parse(s)
+    }
+}
+
+impl TryFrom<String> for GoDuration {
+    type Error = Error;
+
+    fn try_from(raw: String) -> Result<Self> {
+        Self::parse(raw)
+    }
+}
+
+impl From<GoDuration> for String {
+    fn from(d: GoDuration) -> Self {
+        d.raw
+    }
+}
+
+impl From<GoDuration> for Duration {
+    fn from(d: GoDuration) -> Self {
+        d.duration
+    }
+}
+
+impl From<Duration> for GoDuration {
+    /// Durations longer than a Go duration can represent (roughly 292 years) are truncated.
+    fn from(duration: Duration) -> Self {
+        let duration = duration.min(Duration::from_nanos(MAX_NANOS as u64));
+
+        Self {
+            raw: format_duration(duration),
+            duration,
+        }
+    }
+}
+
+/// Format a duration as Go's `Duration.String` does: using the largest unit less than a second
+/// for short durations and hours, minutes and seconds otherwise.
+fn format_duration(d: Duration) -> String {
+    let n = d.as_nanos();
+    let with_fraction = |n: u128, scale: u128| {
+        let (int, frac) = (n / scale, n % scale);
+        if frac == 0 {
+            return int.to_string();
+        }
+        let width = scale.ilog10() as usize;
+        let frac = format!("{frac:0width$}");
+
+        format!("{int}.{}", frac.trim_end_matches('0'))
+    };
+
+    match n {
+        0 => "0s".to_string(),
+        n if n < NANOS_PER_MICRO => format!("{n}ns"),
+        n if n < NANOS_PER_MILLI => format!("{}µs", with_fraction(n, NANOS_PER_MICRO)),
+        n if n < NANOS_PER_SEC => format!("{}ms", with_fraction(n, NANOS_PER_MILLI)),
+        n => {
+            let (h, m) = (n / (3600 * NANOS_PER_SEC), n / (60 * NANOS_PER_SEC) % 60);
+            let s = with_fraction(n % (60 * NANOS_PER_SEC), NANOS_PER_SEC);
+            match (h, m) {
+                (0, 0) => format!("{s}s"),
+                (0, m) => format!("{m}m{s}s"),
+                (h, m) => format!("{h}h{m}m{s}s"),
+            }
+        }
+    }
+}
+
+/// Parse a duration in the format accepted by Go's `time.ParseDuration`.
+///
+/// Negative durations are valid Go syntax but are never meaningful for snapd lifespans so they
+/// are rejected.
+pub fn parse_duration(raw: &str) -> Result<Duration> {
+    let invalid = |reason| Error::InvalidDuration {
+        duration: raw.to_string(),
+        reason,
+    };
+
+    let (negative, mut s) = match raw.strip_prefix('-') {
+        Some(s) => (true, s),
+        None => (false, raw.strip_prefix('+').unwrap_or(raw)),
+    };
+
+    if s == "0" {
+        return Ok(Duration::ZERO);
+    } else if s.is_empty() {
+        return Err(invalid("empty duration"));
+    }
+
+    let mut total: u128 = 0;
+    while !s.is_empty() {
+        let (int, rest) = split_digits(s);
+        let (frac, rest) = match rest.strip_prefix('.') {
+            Some(rest) => split_digits(rest),
+            None => ("", rest),
+        };
+        if int.is_empty() && frac.is_empty() {
+            return Err(invalid("expected a number"));
+        }
+
+        let unit_len = rest
+            .find(|c: char| c == '.' || c.is_ascii_digit())
+            .unwrap_or(rest.len());
+        let (unit, rest) = rest.split_at(unit_len);
+        let scale = match UNITS.iter().find(|(u, _)| *u == unit) {
+            Some((_, scale)) => *scale,
+            None if unit.is_empty() => return Err(invalid("missing unit")),
+            None => return Err(invalid("unknown unit")),
+        };
+
+        let int: u128 = match int {
+            "" => 0,
+            digits => digits
+                .parse()
+                .map_err(|_| invalid("duration out of range"))?,
+        };
+        let (frac, div) = frac
+            .bytes()
+            .take(MAX_FRACTION_DIGITS)
+            .fold((0u128, 1u128), |(n, div), d| {
+                (n * 10 + u128::from(d - b'0'), div * 10)
+            });
+
+        total = int
+            .checked_mul(scale)
+            .and_then(|n| n.checked_add(frac * scale / div))
+            .and_then(|n| n.checked_add(total))
+            .filter(|&n| n <= MAX_NANOS)
+            .ok_or_else(|| invalid("duration out of range"))?;
+        s = rest;
+    }
+
+    if negative && total > 0 {
+        return Err(invalid("negative durations are not supported"));
+    }
+
+    Ok(Duration::from_nanos(total as u64))
+}
+
+fn split_digits(s: &str) -> (&str, &str) {
+    let n = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+
+    s.split_at(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple_test_case::test_case;
+
+    #[test_case("0", Duration::ZERO; "zero without unit")]
+    #[test_case("-0", Duration::ZERO; "negative zero")]
+    #[test_case("0s", Duration::ZERO; "zero with unit")]
+    #[test_case("300ms", Duration::from_millis(300); "millis")]
+    #[test_case("+5s", Duration::from_secs(5); "explicit sign")]
+    #[test_case("1h", Duration::from_secs(3600); "hours")]
+    #[test_case("2h45m", Duration::from_secs(2 * 3600 + 45 * 60); "multiple components")]
+    #[test_case("1.5h", Duration::from_secs(5400); "fraction")]
+    #[test_case(".5s", Duration::from_millis(500); "fraction without integer")]
+    #[test_case("1.s", Duration::from_secs(1); "integer with trailing point")]
+    #[test_case("1.0000000001s", Duration::from_secs(1); "fraction beyond nanosecond precision")]
+    #[test_case("10us", Duration::from_micros(10); "micros")]
+    #[test_case("10µs", Duration::from_micros(10); "micros with micro sign")]
+    #[test_case("10μs", Duration::from_micros(10); "micros with greek mu")]
+    #[test_case("7ns", Duration::from_nanos(7); "nanos")]
+    #[test_case("2562047h47m16.854775807s", Duration::from_nanos(i64::MAX as u64); "max duration")]
+    #[test]
+    fn parse_duration_works(raw: &str, expected: Duration) {
+        assert_eq!(parse_duration(raw).unwrap(), expected);
+    }
+
+    #[test_case("1h30m", "1h30m", 5400; "kept as provided")]
+    #[test_case("90m", "90m", 5400; "not normalised")]
+    #[test]
+    fn go_duration_parse_works(raw: &str, expected_display: &str, expected_secs: u64) {
+        let d: GoDuration = raw.parse().unwrap();
+
+        assert_eq!(d.to_string(), expected_display);
+        assert_eq!(d.as_duration(), Duration::from_secs(expected_secs));
+        assert_eq!(d, GoDuration::from(Duration::from_secs(expected_secs)));
+    }
+
+    #[test_case(Duration::ZERO, "0s"; "zero")]
+    #[test_case(Duration::from_nanos(7), "7ns"; "nanos")]
+    #[test_case(Duration::from_nanos(1500), "1.5µs"; "micros")]
+    #[test_case(Duration::from_micros(250), "250µs"; "whole micros")]
+    #[test_case(Duration::from_millis(250), "250ms"; "millis")]
+    #[test_case(Duration::from_micros(1_250), "1.25ms"; "fractional millis")]
+    #[test_case(Duration::from_secs(10), "10s"; "seconds")]
+    #[test_case(Duration::from_millis(1_500), "1.5s"; "fractional seconds")]
+    #[test_case(Duration::from_secs(90), "1m30s"; "minutes")]
+    #[test_case(Duration::from_secs(5400), "1h30m0s"; "hours")]
+    #[test_case(Duration::from_nanos(3_600_000_000_001), "1h0m0.000000001s"; "hours with nanos")]
+    #[test_case(Duration::MAX, "2562047h47m16.854775807s"; "truncated to max")]
+    #[test]
+    fn go_duration_from_duration_works(d: Duration, expected: &str) {
+        let go = GoDuration::from(d);
+
+        assert_eq!(go.to_string(), expected);
+        assert_eq!(parse_duration(expected).unwrap(), go.as_duration());
+    }
+
+    #[test]
+    fn go_duration_serde_round_trip_works() {
+        let d: GoDuration = serde_json::from_str(r#""2h45m""#).unwrap();
+
+        assert_eq!(d.as_duration(), Duration::from_secs(2 * 3600 + 45 * 60));
+        assert_eq!(serde_json::to_string(&d).unwrap(), r#""2h45m""#);
+        assert!(serde_json::from_str::<GoDuration>(r#""2 hours""#).is_err());
+    }
+
+    #[test_case(""; "empty")]
+    #[test_case("-"; "sign only")]
+    #[test_case("10"; "missing unit")]
+    #[test_case("1d"; "unknown unit")]
+    #[test_case("1H"; "units are case sensitive")]
+    #[test_case("h"; "missing number")]
+    #[test_case(".s"; "point without digits")]
+    #[test_case("1h 30m"; "whitespace")]
+    #[test_case("-1s"; "negative")]
+    #[test_case("2562047h47m16.854775808s"; "overflow")]
+    #[test_case("99999999999999999999999999999999999999999h"; "overflowing integer")]
+    #[test]
+    fn parse_duration_errors_for_invalid_input(raw: &str) {
+        match parse_duration(raw) {
+            Err(Error::InvalidDuration { .. }) => (),
+            res => panic!("expected InvalidDuration, got {res:?}"),
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::snapd_client::interfaces::device::{Device, DeviceInterface};
+use serde::{Deserialize, Serialize};
+
+/// The interface for allowing access to the microphone (recording audio).
+pub type AudioRecordInterface = DeviceInterface<AudioRecord>;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct AudioRecord;
+
+impl Device for AudioRecord {
+    const NAME: &'static str = "audio-record";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapd_client::{interfaces::SnapInterface, Action, Prompt, RawPrompt, TypedPrompt};
+
+    const AUDIO_RECORD_PROMPT: &str = r#"{
+      "id": "0000000000000004",
+      "timestamp": "2024-08-14T07:28:22.694800024Z",
+      "snap": "audacity",
+      "interface": "audio-record",
+      "constraints": {
+        "requested-permissions": [
+          "access"
+        ],
+        "available-permissions": [
+          "access"
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn deserializing_an_audio_record_prompt_works() {
+        let raw: RawPrompt = serde_json::from_str(AUDIO_RECORD_PROMPT).unwrap();
+        assert_eq!(raw.interface, "audio-record");
+
+        let p: TypedPrompt = raw.try_into().unwrap();
+        assert!(matches!(p, TypedPrompt::AudioRecord(_)));
+    }
+
+    #[test]
+    fn prompt_to_reply_serializes_correctly() {
+        let p: Prompt<AudioRecordInterface> = serde_json::from_str(AUDIO_RECORD_PROMPT).unwrap();
+        let reply = AudioRecordInterface::prompt_to_reply(p, Action::Allow).for_forever();
+
+        assert_eq!(
+            serde_json::to_value(reply).unwrap(),
+            serde_json::json!({
+                "action": "allow",
+                "lifespan": "forever",
+                "constraints": {
+                    "permissions": ["access"]
+                }
+            })
+        );
+    }
+}
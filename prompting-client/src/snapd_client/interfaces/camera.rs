@@ -0,0 +1,59 @@
+use crate::snapd_client::interfaces::device::{Device, DeviceInterface};
+use serde::{Deserialize, Serialize};
+
+/// The interface for allowing access to the camera device(s).
+pub type CameraInterface = DeviceInterface<Camera>;
+
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct Camera;
+
+impl Device for Camera {
+    const NAME: &'static str = "camera";
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapd_client::{interfaces::SnapInterface, Action, Prompt, RawPrompt, TypedPrompt};
+
+    const CAMERA_PROMPT: &str = r#"{
+      "id": "0000000000000003",
+      "timestamp": "2024-08-14T07:28:22.694800024Z",
+      "snap": "cheese",
+      "interface": "camera",
+      "constraints": {
+        "requested-permissions": [
+          "access"
+        ],
+        "available-permissions": [
+          "access"
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn deserializing_a_camera_prompt_works() {
+        let raw: RawPrompt = serde_json::from_str(CAMERA_PROMPT).unwrap();
+        assert_eq!(raw.interface, "camera");
+
+        let p: TypedPrompt = raw.try_into().unwrap();
+        assert!(matches!(p, TypedPrompt::Camera(_)));
+    }
+
+    #[test]
+    fn prompt_to_reply_serializes_correctly() {
+        let p: Prompt<CameraInterface> = serde_json::from_str(CAMERA_PROMPT).unwrap();
+        let reply = CameraInterface::prompt_to_reply(p, Action::Allow).for_forever();
+
+        assert_eq!(
+            serde_json::to_value(reply).unwrap(),
+            serde_json::json!({
+                "action": "allow",
+                "lifespan": "forever",
+                "constraints": {
+                    "permissions": ["access"]
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,164 @@
+//! Interfaces that only expose a single permission granting access to a class of device, such as
+//! the camera or the microphone.
+use crate::{
+    field_matches,
+    prompt_sequence::{MatchAttempt, MatchFailure},
+    snapd_client::{
+        interfaces::{
+            ConstraintsFilter, Prompt, PromptReply, ReplyConstraintsOverrides, SnapInterface,
+        },
+        prompt::UiInput,
+        Action, Lifespan, Result, SnapMeta,
+    },
+};
+use serde::{de::DeserializeOwned, Deserialize, Serialize};
+use std::{fmt, marker::PhantomData};
+
+/// Device interfaces only expose a single permission: access to the device(s).
+const ACCESS: &str = "access";
+
+/// A class of device that is accessed through a single permission snapd interface.
+pub trait Device:
+    fmt::Debug + Default + Clone + PartialEq + Eq + Serialize + DeserializeOwned
+{
+    /// The name of the snapd interface used to access the device.
+    const NAME: &'static str;
+}
+
+impl<D> Prompt<DeviceInterface<D>>
+where
+    D: Device,
+{
+    pub fn requested_permissions(&self) -> &[String] {
+        &self.constraints.requested_permissions
+    }
+}
+
+/// The interface for allowing access to a [Device].
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct DeviceInterface<D>(PhantomData<D>);
+
+impl<D> SnapInterface for DeviceInterface<D>
+where
+    D: Device,
+{
+    const NAME: &'static str = D::NAME;
+
+    type Constraints = DeviceConstraints;
+    type ReplyConstraints = DeviceReplyConstraints;
+
+    type ConstraintsFilter = DeviceConstraintsFilter;
+    type ReplyConstraintsOverrides = DeviceReplyConstraintsOverrides;
+
+    type UiInputData = DeviceUiInputData;
+    type UiReply = DeviceUiReply;
+
+    fn prompt_to_reply(prompt: Prompt<Self>, action: Action) -> PromptReply<Self> {
+        PromptReply {
+            action,
+            lifespan: Lifespan::Single,
+            duration: None,
+            constraints: DeviceReplyConstraints {
+                permissions: prompt.constraints.requested_permissions,
+            },
+        }
+    }
+
+    fn map_ui_input(&self, prompt: Prompt<Self>, meta: Option<SnapMeta>) -> Result<UiInput<Self>> {
+        let meta = meta.unwrap_or_else(|| SnapMeta {
+            name: prompt.snap,
+            ..Default::default()
+        });
+
+        Ok(UiInput {
+            id: prompt.id,
+            timestamp: prompt.timestamp,
+            meta,
+            data: DeviceUiInputData {},
+        })
+    }
+
+    fn requested_permissions(constraints: &Self::Constraints) -> Vec<String> {
+        constraints.requested_permissions.clone()
+    }
+
+    fn permissions(constraints: &Self::ReplyConstraints) -> Vec<String> {
+        constraints.permissions.clone()
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeviceConstraints {
+    pub(crate) requested_permissions: Vec<String>,
+    pub(crate) available_permissions: Vec<String>,
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceUiInputData {}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeviceUiReply {
+    action: Action,
+    lifespan: Lifespan,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeviceReplyConstraints {
+    pub(crate) permissions: Vec<String>,
+}
+
+impl Default for DeviceReplyConstraints {
+    /// Replies from the UI do not carry a permission set for device interfaces so we default to
+    /// the only permission they have.
+    fn default() -> Self {
+        Self {
+            permissions: vec![ACCESS.to_string()],
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeviceConstraintsFilter {
+    pub requested_permissions: Option<Vec<String>>,
+    pub available_permissions: Option<Vec<String>>,
+}
+
+impl ConstraintsFilter for DeviceConstraintsFilter {
+    type Constraints = DeviceConstraints;
+
+    fn matches(&self, constraints: &Self::Constraints) -> MatchAttempt {
+        let mut failures = Vec::new();
+
+        field_matches!(self, constraints, failures, requested_permissions);
+        field_matches!(self, constraints, failures, available_permissions);
+
+        if failures.is_empty() {
+            MatchAttempt::Success
+        } else {
+            MatchAttempt::Failure(failures)
+        }
+    }
+}
+
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "kebab-case")]
+pub struct DeviceReplyConstraintsOverrides {
+    pub permissions: Option<Vec<String>>,
+}
+
+impl ReplyConstraintsOverrides for DeviceReplyConstraintsOverrides {
+    type ReplyConstraints = DeviceReplyConstraints;
+
+    fn apply(self, mut constraints: Self::ReplyConstraints) -> Self::ReplyConstraints {
+        if let Some(permissions) = self.permissions {
+            constraints.permissions = permissions;
+        }
+
+        constraints
+    }
+}
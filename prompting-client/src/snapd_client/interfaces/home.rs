@@ -5,6 +5,7 @@ use crate::{
         interfaces::{
             ConstraintsFilter, Prompt, PromptReply, ReplyConstraintsOverrides, SnapInterface,
         },
+        path_pattern::{validate_path_pattern, PathPattern},
         prompt::UiInput,
         Action, Error, Lifespan, Result, SnapMeta,
     },
@@ -12,44 +13,111 @@ use crate::{
 };
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::{env, path::PathBuf};
+use std::{
+    env,
+    path::{Path, PathBuf},
+};
+
+mod permissions;
+
+pub use permissions::{HomePermission, PermissionSet};
 
 impl Prompt<HomeInterface> {
     pub fn path(&self) -> &str {
         &self.constraints.path
     }
 
-    pub fn requested_permissions(&self) -> &[String] {
+    pub fn requested_permissions(&self) -> &PermissionSet {
         &self.constraints.requested_permissions
     }
+
+    /// Whether this prompt can be presented to the user as a single prompt along with `other`:
+    /// both need to be from the same snap, requesting the same permissions for paths within the
+    /// same directory.
+    pub(crate) fn can_coalesce_with(&self, other: &Self) -> bool {
+        fn containing_dir(path: &str) -> Option<&Path> {
+            Path::new(path)
+                .parent()
+                .filter(|dir| !dir.as_os_str().is_empty())
+        }
+
+        self.snap == other.snap
+            && self.requested_permissions() == other.requested_permissions()
+            && containing_dir(self.path()).is_some()
+            && containing_dir(self.path()) == containing_dir(other.path())
+    }
+}
+
+impl UiInput<HomeInterface> {
+    /// Restrict the pattern options offered to the user to those that also match each of the
+    /// provided paths, preferring the containing directory as the initial option if it remains.
+    /// The options are left unchanged if none of them match every path.
+    pub(crate) fn retain_pattern_options_matching(&mut self, paths: &[&str]) {
+        let data = &mut self.data;
+        let initial = data
+            .pattern_options
+            .get(data.initial_pattern_option)
+            .cloned();
+
+        let retained: Vec<TypedPathPattern> = data
+            .pattern_options
+            .iter()
+            .filter(|opt| match PathPattern::parse(opt.path_pattern.as_str()) {
+                Ok(patt) => paths.iter().all(|path| patt.matches(path)),
+                Err(_) => false,
+            })
+            .cloned()
+            .collect();
+        if retained.is_empty() {
+            return;
+        }
+        data.pattern_options = retained;
+
+        let options = &data.pattern_options;
+        data.initial_pattern_option = options
+            .iter()
+            .position(|opt| opt.pattern_type == PatternType::ContainingDirectory)
+            .or_else(|| options.iter().position(|opt| Some(opt) == initial.as_ref()))
+            .unwrap_or_default();
+    }
 }
 
 impl PromptReply<HomeInterface> {
-    /// Specify a custom path pattern to replace the one originally requested in the parent [Prompt].
+    /// Attempt to specify a custom path pattern to replace the one originally requested in the
+    /// parent [Prompt].
     ///
-    /// If the path pattern provided is invalid or does not apply to the path originally requested
-    /// in the parent prompt then submitting this reply will result in an error being returned by
-    /// snapd.
-    pub fn with_custom_path_pattern(mut self, path_pattern: impl Into<String>) -> Self {
+    /// This method will error if the path pattern provided is invalid or does not match the path
+    /// originally requested in the parent prompt.
+    pub fn try_with_custom_path_pattern(mut self, path_pattern: impl Into<String>) -> Result<Self> {
         self.constraints.path_pattern = path_pattern.into();
-        self
+        self.validate_path_pattern()?;
+
+        Ok(self)
+    }
+
+    /// Check that the path pattern for this reply is valid and matches the path originally
+    /// requested in the parent [Prompt].
+    pub fn validate_path_pattern(&self) -> Result<()> {
+        validate_path_pattern(
+            &self.constraints.path_pattern,
+            &self.constraints.requested_path,
+        )?;
+
+        Ok(())
     }
 
     /// Attempt to set a custom permission set for this reply.
     ///
     /// This method will error if the requested permissions are not available on the parent
     /// [Prompt].
-    pub fn try_with_custom_permissions(mut self, permissions: Vec<String>) -> Result<Self> {
-        if permissions
-            .iter()
-            .all(|p| self.constraints.available_permissions.contains(p))
-        {
+    pub fn try_with_custom_permissions(mut self, permissions: PermissionSet) -> Result<Self> {
+        if permissions.is_subset(&self.constraints.available_permissions) {
             self.constraints.permissions = permissions;
             Ok(self)
         } else {
             Err(Error::InvalidCustomPermissions {
-                requested: permissions,
-                available: self.constraints.available_permissions,
+                requested: permissions.to_vec(),
+                available: self.constraints.available_permissions.to_vec(),
             })
         }
     }
@@ -153,9 +221,10 @@ impl SnapInterface for HomeInterface {
             lifespan: Lifespan::Single,
             duration: None,
             constraints: HomeReplyConstraints {
-                path_pattern: prompt.constraints.path,
+                path_pattern: prompt.constraints.path.clone(),
                 permissions: prompt.constraints.requested_permissions,
                 available_permissions: prompt.constraints.available_permissions,
+                requested_path: prompt.constraints.path,
             },
         }
     }
@@ -167,9 +236,7 @@ impl SnapInterface for HomeInterface {
         } = self.ui_options(&prompt)?;
         let meta = meta.unwrap_or_else(|| SnapMeta {
             name: prompt.snap,
-            updated_at: String::default(),
-            store_url: String::default(),
-            publisher: String::default(),
+            ..Default::default()
         });
 
         // We elevate the suggested permissions in the ui from write -> read/write in order to
@@ -177,11 +244,12 @@ impl SnapInterface for HomeInterface {
         // interact with a file after writing it.
         let mut suggested_permissions = prompt.constraints.requested_permissions.clone();
         if prompt.constraints.is_only_write() {
-            suggested_permissions.push("read".to_string());
+            suggested_permissions.insert(HomePermission::Read);
         }
 
         Ok(UiInput {
             id: prompt.id,
+            timestamp: prompt.timestamp,
             meta,
             data: HomeUiInputData {
                 requested_path: prompt.constraints.path,
@@ -194,19 +262,79 @@ impl SnapInterface for HomeInterface {
             },
         })
     }
+
+    /// The path pattern and permissions chosen by the user are kept when they also cover this
+    /// prompt, otherwise the reply falls back to exactly what was requested.
+    fn mirror_reply(prompt: Prompt<Self>, reply: &PromptReply<Self>) -> PromptReply<Self> {
+        let mut mirrored = Self::prompt_to_reply(prompt, reply.action);
+        mirrored.lifespan = reply.lifespan;
+        mirrored.duration = reply.duration.clone();
+
+        let chosen = &reply.constraints;
+        let constraints = &mut mirrored.constraints;
+        if let Ok(patt) = PathPattern::parse(chosen.path_pattern.as_str()) {
+            if patt.matches(&constraints.requested_path) {
+                constraints.path_pattern = chosen.path_pattern.clone();
+            }
+        }
+        if constraints.permissions.is_subset(&chosen.permissions)
+            && chosen
+                .permissions
+                .is_subset(&constraints.available_permissions)
+        {
+            constraints.permissions = chosen.permissions.clone();
+        }
+
+        mirrored
+    }
+
+    fn can_coalesce(prompt: &Prompt<Self>, other: &Prompt<Self>) -> bool {
+        prompt.can_coalesce_with(other)
+    }
+
+    fn restrict_to_grouped(input: &mut UiInput<Self>, grouped: &[&Prompt<Self>]) {
+        let paths: Vec<&str> = grouped.iter().map(|p| p.path()).collect();
+        input.retain_pattern_options_matching(&paths);
+    }
+
+    fn requested_path(constraints: &Self::Constraints) -> Option<&str> {
+        Some(&constraints.path)
+    }
+
+    fn requested_permissions(constraints: &Self::Constraints) -> Vec<String> {
+        constraints.requested_permissions.to_vec()
+    }
+
+    fn path_pattern(constraints: &Self::ReplyConstraints) -> Option<&str> {
+        Some(&constraints.path_pattern)
+    }
+
+    fn permissions(constraints: &Self::ReplyConstraints) -> Vec<String> {
+        constraints.permissions.to_vec()
+    }
+
+    fn edit_overrides(
+        path_pattern: Option<String>,
+        permissions: Option<Vec<String>>,
+    ) -> Result<Self::ReplyConstraintsOverrides> {
+        Ok(HomeReplyConstraintsOverrides {
+            path_pattern,
+            permissions: permissions.map(PermissionSet::from_iter),
+        })
+    }
 }
 
 #[derive(Default, Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct HomeConstraints {
     pub(crate) path: String,
-    pub(crate) requested_permissions: Vec<String>,
-    pub(crate) available_permissions: Vec<String>,
+    pub(crate) requested_permissions: PermissionSet,
+    pub(crate) available_permissions: PermissionSet,
 }
 
 impl HomeConstraints {
     fn is_only_write(&self) -> bool {
-        self.requested_permissions.len() == 1 && self.requested_permissions[0] == "write"
+        self.requested_permissions.is_only(&HomePermission::Write)
     }
 }
 
@@ -215,13 +343,26 @@ impl HomeConstraints {
 pub struct HomeUiInputData {
     pub(crate) requested_path: String,
     pub(crate) home_dir: String,
-    pub(crate) requested_permissions: Vec<String>,
-    pub(crate) available_permissions: Vec<String>,
-    pub(crate) suggested_permissions: Vec<String>,
+    pub(crate) requested_permissions: PermissionSet,
+    pub(crate) available_permissions: PermissionSet,
+    pub(crate) suggested_permissions: PermissionSet,
     pub(crate) initial_pattern_option: usize,
     pub(crate) pattern_options: Vec<TypedPathPattern>,
 }
 
+impl HomeUiInputData {
+    /// Classify a free-form path pattern provided by the user into the [PatternType] that best
+    /// describes it relative to the path that was requested in the prompt.
+    ///
+    /// This method will error if the pattern is not contained within the user's home directory
+    /// or if it does not match the requested path.
+    pub fn resolve_pattern_type(&self, pattern: &str) -> Result<PatternType> {
+        let cpath = CategorisedPath::from_path(&self.requested_path, &self.home_dir)?;
+
+        cpath.classify_pattern(pattern)
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct TypedPathPattern {
@@ -265,9 +406,11 @@ pub struct HomeUiReply {
 #[serde(rename_all = "kebab-case")]
 pub struct HomeReplyConstraints {
     pub(crate) path_pattern: String,
-    pub(crate) permissions: Vec<String>,
+    pub(crate) permissions: PermissionSet,
     #[serde(skip)]
-    pub(crate) available_permissions: Vec<String>,
+    pub(crate) available_permissions: PermissionSet,
+    #[serde(skip)]
+    pub(crate) requested_path: String,
 }
 
 #[derive(Debug, Default, Serialize, Deserialize, Clone)]
@@ -275,8 +418,8 @@ pub struct HomeReplyConstraints {
 pub struct HomeConstraintsFilter {
     #[serde(with = "serde_option_regex", default)]
     pub path: Option<Regex>,
-    pub requested_permissions: Option<Vec<String>>,
-    pub available_permissions: Option<Vec<String>>,
+    pub requested_permissions: Option<PermissionSet>,
+    pub available_permissions: Option<PermissionSet>,
 }
 
 impl HomeConstraintsFilter {
@@ -286,13 +429,19 @@ impl HomeConstraintsFilter {
         Ok(self)
     }
 
-    pub fn with_requested_permissions(&mut self, permissions: Vec<impl Into<String>>) -> &mut Self {
-        self.requested_permissions = Some(permissions.into_iter().map(|p| p.into()).collect());
+    pub fn with_requested_permissions(
+        &mut self,
+        permissions: impl IntoIterator<Item = impl Into<HomePermission>>,
+    ) -> &mut Self {
+        self.requested_permissions = Some(permissions.into_iter().collect());
         self
     }
 
-    pub fn with_available_permissions(&mut self, permissions: Vec<impl Into<String>>) -> &mut Self {
-        self.available_permissions = Some(permissions.into_iter().map(|p| p.into()).collect());
+    pub fn with_available_permissions(
+        &mut self,
+        permissions: impl IntoIterator<Item = impl Into<HomePermission>>,
+    ) -> &mut Self {
+        self.available_permissions = Some(permissions.into_iter().collect());
         self
     }
 }
@@ -328,7 +477,7 @@ impl ConstraintsFilter for HomeConstraintsFilter {
 #[serde(rename_all = "kebab-case")]
 pub struct HomeReplyConstraintsOverrides {
     pub path_pattern: Option<String>,
-    pub permissions: Option<Vec<String>>,
+    pub permissions: Option<PermissionSet>,
 }
 
 impl ReplyConstraintsOverrides for HomeReplyConstraintsOverrides {
@@ -457,6 +606,47 @@ impl<'a> CategorisedPath<'a> {
             _ => None,
         }
     }
+
+    fn classify_pattern(&self, pattern: &str) -> Result<PatternType> {
+        let invalid = |reason| Error::InvalidPathPattern {
+            pattern: pattern.to_string(),
+            reason,
+        };
+
+        let relative = match pattern.strip_prefix(self.home_dir) {
+            Some(rel) if rel.is_empty() || rel.starts_with('/') => rel.trim_start_matches('/'),
+            _ => return Err(invalid("pattern is not within the home directory")),
+        };
+        if relative.split('/').any(|segment| segment == "..") {
+            return Err(invalid("pattern is not within the home directory"));
+        }
+        validate_path_pattern(pattern, self.raw_path)?;
+
+        if pattern == self.raw_path {
+            return Ok(self.requested_path_pattern().pattern_type);
+        }
+
+        if let Some(dir) = relative.strip_suffix("**") {
+            let dir = dir.trim_end_matches('/');
+            let n_segments = dir.split('/').filter(|s| !s.is_empty()).count();
+            let is_requested_dir = self.is_dir && pattern == format!("{}**", self.raw_path);
+
+            return Ok(match n_segments {
+                0 => PatternType::HomeDirectory,
+                1 => PatternType::TopLevelDirectory,
+                _ if is_requested_dir => PatternType::RequestedDirectoryContents,
+                _ => PatternType::ContainingDirectory,
+            });
+        }
+
+        let file_name = relative.rsplit('/').next().unwrap_or_default();
+        if !self.is_dir && file_name.starts_with("*.") {
+            return Ok(PatternType::MatchingFileExtension);
+        }
+
+        // Any other pattern is a customisation of the requested path itself
+        Ok(self.requested_path_pattern().pattern_type)
+    }
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -473,7 +663,7 @@ pub enum PatternType {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::snapd_client::{RawPrompt, TypedPrompt};
+    use crate::snapd_client::{PromptId, RawPrompt, TypedPrompt};
     use simple_test_case::test_case;
     use PathKind::*;
 
@@ -510,13 +700,14 @@ mod tests {
     fn invalid_reply_permissions_error(available: &[&str], requested: &[&str]) {
         let reply = PromptReply {
             constraints: HomeReplyConstraints {
-                available_permissions: available.iter().map(|&s| s.into()).collect(),
+                available_permissions: available.iter().copied().collect::<PermissionSet>(),
                 ..Default::default()
             },
             ..Default::default()
         };
 
-        let res = reply.try_with_custom_permissions(requested.iter().map(|&s| s.into()).collect());
+        let res =
+            reply.try_with_custom_permissions(requested.iter().copied().collect::<PermissionSet>());
         match res {
             Err(Error::InvalidCustomPermissions { .. }) => (),
             Err(e) => panic!("expected InvalidCustomPermissions, got {e}"),
@@ -704,4 +895,193 @@ mod tests {
             );
         }
     }
+
+    fn home_prompt(snap: &str, path: &str, permissions: &[&str]) -> Prompt<HomeInterface> {
+        Prompt {
+            id: PromptId("1".to_string()),
+            timestamp: Default::default(),
+            snap: snap.to_string(),
+            interface: "home".to_string(),
+            constraints: HomeConstraints {
+                path: path.to_string(),
+                requested_permissions: permissions.iter().copied().collect(),
+                available_permissions: PermissionSet::from(["read", "write"]),
+            },
+        }
+    }
+
+    #[test_case("firefox", "/home/user/Downloads/b.txt", &["read"], true; "same dir and permissions")]
+    #[test_case("firefox", "/home/user/Downloads/nested/b.txt", &["read"], false; "different dir")]
+    #[test_case("firefox", "/home/user/Downloads/b.txt", &["write"], false; "different permissions")]
+    #[test_case("thunderbird", "/home/user/Downloads/b.txt", &["read"], false; "different snap")]
+    #[test]
+    fn can_coalesce_with_works(snap: &str, path: &str, permissions: &[&str], expected: bool) {
+        let p = home_prompt("firefox", "/home/user/Downloads/a.txt", &["read"]);
+        let other = home_prompt(snap, path, permissions);
+
+        assert_eq!(p.can_coalesce_with(&other), expected);
+    }
+
+    #[test]
+    fn prompts_without_a_path_are_not_coalesced() {
+        let p = home_prompt("firefox", "", &["read"]);
+
+        assert!(!p.can_coalesce_with(&p.clone()));
+    }
+
+    #[test_case(
+        "/home/user/Pictures/nested/foo.jpeg",
+        &["/home/user/Pictures/nested/bar.jpeg"],
+        2,
+        &[
+            PatternType::HomeDirectory,
+            PatternType::TopLevelDirectory,
+            PatternType::ContainingDirectory,
+            PatternType::MatchingFileExtension,
+        ];
+        "same extension in sub-folder"
+    )]
+    #[test_case(
+        "/home/user/Pictures/nested/foo.jpeg",
+        &["/home/user/Pictures/nested/bar.png"],
+        2,
+        &[
+            PatternType::HomeDirectory,
+            PatternType::TopLevelDirectory,
+            PatternType::ContainingDirectory,
+        ];
+        "different extension in sub-folder"
+    )]
+    #[test_case(
+        "/home/user/Downloads/foo.jpeg",
+        &["/home/user/Downloads/bar.png"],
+        1,
+        &[PatternType::HomeDirectory, PatternType::TopLevelDirectory];
+        "top level folder keeps initial option"
+    )]
+    #[test_case(
+        "/home/user/foo.jpeg",
+        &["/home/user/bar.png"],
+        0,
+        &[PatternType::HomeDirectory];
+        "home folder"
+    )]
+    #[test_case(
+        "/home/user/Downloads/foo.jpeg",
+        &["/home/other/bar.png"],
+        1,
+        &[
+            PatternType::HomeDirectory,
+            PatternType::TopLevelDirectory,
+            PatternType::RequestedFile,
+            PatternType::MatchingFileExtension,
+        ];
+        "nothing matches keeps all options"
+    )]
+    #[test]
+    fn retain_pattern_options_matching_works(
+        path: &str,
+        grouped: &[&str],
+        initial_pattern_option: usize,
+        expected: &[PatternType],
+    ) {
+        let PatternOptions {
+            initial_pattern_option: initial,
+            pattern_options,
+        } = PatternOptions::new(path, "/home/user").unwrap();
+        let mut input = UiInput::<HomeInterface> {
+            id: PromptId("1".to_string()),
+            timestamp: Default::default(),
+            meta: SnapMeta::default(),
+            data: HomeUiInputData {
+                requested_path: path.to_string(),
+                home_dir: "/home/user".to_string(),
+                requested_permissions: PermissionSet::new(),
+                available_permissions: PermissionSet::new(),
+                suggested_permissions: PermissionSet::new(),
+                initial_pattern_option: initial,
+                pattern_options,
+            },
+        };
+
+        input.retain_pattern_options_matching(grouped);
+
+        let options: Vec<PatternType> = input
+            .data
+            .pattern_options
+            .iter()
+            .map(|opt| opt.pattern_type)
+            .collect();
+
+        assert_eq!(options, expected);
+        assert_eq!(input.data.initial_pattern_option, initial_pattern_option);
+    }
+
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/**", PatternType::HomeDirectory; "home dir")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/Documents/**", PatternType::TopLevelDirectory; "top level dir")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/Documents/notes/**", PatternType::ContainingDirectory; "containing dir")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/**/*.md", PatternType::MatchingFileExtension; "matching extension")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/Documents/notes/todo.md", PatternType::RequestedFile; "requested file")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/Documents/notes/todo.{md,txt}", PatternType::RequestedFile; "custom requested file")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/Documents/*/todo.md", PatternType::RequestedFile; "requested file with wildcard")]
+    #[test_case("/home/user/Documents/notes/", "/home/user/Documents/notes/**", PatternType::RequestedDirectoryContents; "dir contents")]
+    #[test_case("/home/user/Documents/notes/", "/home/user/Documents/notes/", PatternType::RequestedDirectory; "requested dir")]
+    #[test_case("/home/user/Documents/", "/home/user/Documents/**", PatternType::TopLevelDirectory; "requested top level dir")]
+    #[test]
+    fn resolve_pattern_type_works(path: &str, pattern: &str, expected: PatternType) {
+        let cpath = CategorisedPath::from_path(path, "/home/user").unwrap();
+        assert_eq!(cpath.classify_pattern(pattern).unwrap(), expected);
+    }
+
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/**"; "parent of home")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user2/**"; "sibling of home")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/../user/**"; "dot dot")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/**"; "root")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/Pictures/**"; "different dir")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/**/*.txt"; "different extension")]
+    #[test_case("/home/user/Documents/notes/todo.md", "/home/user/*/todo.md"; "single star does not cross separators")]
+    #[test]
+    fn resolve_pattern_type_errors_for_invalid_patterns(path: &str, pattern: &str) {
+        let cpath = CategorisedPath::from_path(path, "/home/user").unwrap();
+
+        match cpath.classify_pattern(pattern) {
+            Err(Error::InvalidPathPattern { .. }) => (),
+            res => panic!("expected InvalidPathPattern, got {res:?}"),
+        }
+    }
+
+    #[test_case("/home/user/Documents/**"; "containing dir")]
+    #[test_case("/home/user/**/*.{txt,md}"; "extension group")]
+    #[test]
+    fn valid_custom_path_pattern_works(pattern: &str) {
+        let reply = PromptReply::<HomeInterface> {
+            constraints: HomeReplyConstraints {
+                requested_path: "/home/user/Documents/foo.txt".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let reply = reply.try_with_custom_path_pattern(pattern).unwrap();
+        assert_eq!(reply.constraints.path_pattern, pattern);
+    }
+
+    #[test_case("not a valid custom path!", "pattern must be an absolute path"; "malformed path")]
+    #[test_case("/home/bob/*", "pattern does not match the requested path"; "non matching path")]
+    #[test]
+    fn invalid_custom_path_pattern_errors(pattern: &str, expected: &str) {
+        let reply = PromptReply::<HomeInterface> {
+            constraints: HomeReplyConstraints {
+                requested_path: "/home/user/Documents/foo.txt".to_string(),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        match reply.try_with_custom_path_pattern(pattern) {
+            Err(Error::InvalidPathPattern { reason, .. }) => assert_eq!(reason, expected),
+            Err(e) => panic!("expected InvalidPathPattern, got {e}"),
+            Ok(_) => panic!("should have errored"),
+        }
+    }
 }
@@ -0,0 +1,228 @@
+//! Typed permissions for the home interface.
+//!
+//! snapd represents permissions as a list of strings in its JSON API. Any permissions that we do
+//! not recognise are preserved as [HomePermission::Other] so that they round trip unchanged.
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::{collections::BTreeSet, convert::Infallible, fmt, str::FromStr};
+
+/// A single permission that can be requested through the home interface.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HomePermission {
+    Read,
+    Write,
+    Execute,
+    /// A permission that is not (yet) known to us.
+    Other(String),
+}
+
+impl HomePermission {
+    const KNOWN: [Self; 3] = [Self::Read, Self::Write, Self::Execute];
+
+    fn bit(&self) -> Option<u8> {
+        match self {
+            Self::Read => Some(1),
+            Self::Write => Some(1 << 1),
+            Self::Execute => Some(1 << 2),
+            Self::Other(_) => None,
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        match self {
+            Self::Read => "read",
+            Self::Write => "write",
+            Self::Execute => "execute",
+            Self::Other(s) => s,
+        }
+    }
+}
+
+impl fmt::Display for HomePermission {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for HomePermission {
+    type Err = Infallible;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(s.into())
+    }
+}
+
+impl From<&str> for HomePermission {
+    fn from(s: &str) -> Self {
+        match s {
+            "read" => Self::Read,
+            "write" => Self::Write,
+            "execute" => Self::Execute,
+            other => Self::Other(other.to_string()),
+        }
+    }
+}
+
+impl From<String> for HomePermission {
+    fn from(s: String) -> Self {
+        s.as_str().into()
+    }
+}
+
+/// A set of [HomePermission]s.
+///
+/// Known permissions are stored as a bitset and always listed in the order read, write, execute
+/// followed by any unknown permissions in lexical order.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct PermissionSet {
+    bits: u8,
+    other: BTreeSet<String>,
+}
+
+impl PermissionSet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn insert(&mut self, p: impl Into<HomePermission>) {
+        match p.into() {
+            HomePermission::Other(s) => {
+                self.other.insert(s);
+            }
+            known => self.bits |= known.bit().unwrap_or_default(),
+        }
+    }
+
+    pub fn with(mut self, p: impl Into<HomePermission>) -> Self {
+        self.insert(p);
+        self
+    }
+
+    pub fn contains(&self, p: &HomePermission) -> bool {
+        match (p.bit(), p) {
+            (Some(bit), _) => self.bits & bit != 0,
+            (None, HomePermission::Other(s)) => self.other.contains(s),
+            (None, _) => false,
+        }
+    }
+
+    pub fn is_subset(&self, other: &Self) -> bool {
+        self.bits & !other.bits == 0 && self.other.is_subset(&other.other)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bits == 0 && self.other.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.bits.count_ones() as usize + self.other.len()
+    }
+
+    /// Whether this set contains exactly the one given permission.
+    pub fn is_only(&self, p: &HomePermission) -> bool {
+        self.len() == 1 && self.contains(p)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = HomePermission> + '_ {
+        HomePermission::KNOWN
+            .into_iter()
+            .filter(|p| self.contains(p))
+            .chain(self.other.iter().cloned().map(HomePermission::Other))
+    }
+
+    /// The permissions in this set in the string form used by snapd.
+    pub fn to_vec(&self) -> Vec<String> {
+        self.iter().map(|p| p.as_str().to_string()).collect()
+    }
+}
+
+impl<P: Into<HomePermission>> FromIterator<P> for PermissionSet {
+    fn from_iter<T: IntoIterator<Item = P>>(iter: T) -> Self {
+        let mut set = Self::new();
+        for p in iter {
+            set.insert(p);
+        }
+
+        set
+    }
+}
+
+impl<P: Into<HomePermission>, const N: usize> From<[P; N]> for PermissionSet {
+    fn from(permissions: [P; N]) -> Self {
+        permissions.into_iter().collect()
+    }
+}
+
+impl fmt::Display for PermissionSet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_vec().join(","))
+    }
+}
+
+impl Serialize for PermissionSet {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter().map(|p| p.as_str().to_string()))
+    }
+}
+
+impl<'de> Deserialize<'de> for PermissionSet {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Vec::<String>::deserialize(deserializer)?
+            .into_iter()
+            .collect())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple_test_case::test_case;
+
+    #[test_case("read", HomePermission::Read; "read")]
+    #[test_case("write", HomePermission::Write; "write")]
+    #[test_case("execute", HomePermission::Execute; "execute")]
+    #[test_case("append", HomePermission::Other("append".to_string()); "other")]
+    #[test]
+    fn home_permission_round_trips(raw: &str, expected: HomePermission) {
+        let p = HomePermission::from(raw);
+
+        assert_eq!(p, expected);
+        assert_eq!(p.to_string(), raw);
+    }
+
+    #[test_case(r#"["read","write"]"#, r#"["read","write"]"#; "known")]
+    #[test_case(r#"["execute","read"]"#, r#"["read","execute"]"#; "canonical order")]
+    #[test_case(r#"["write","write"]"#, r#"["write"]"#; "duplicates")]
+    #[test_case(r#"["zz","read","append"]"#, r#"["read","append","zz"]"#; "unknown kept")]
+    #[test_case("[]", "[]"; "empty")]
+    #[test]
+    fn permission_set_serde_works(raw: &str, expected: &str) {
+        let set: PermissionSet = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(serde_json::to_string(&set).unwrap(), expected);
+    }
+
+    #[test_case(&["read"], &["read", "write"], true; "strict subset")]
+    #[test_case(&["read", "write"], &["read", "write"], true; "equal")]
+    #[test_case(&[], &["read"], true; "empty")]
+    #[test_case(&["execute"], &["read", "write"], false; "missing known")]
+    #[test_case(&["append"], &["read", "append"], true; "other present")]
+    #[test_case(&["append"], &["read", "write"], false; "other missing")]
+    #[test]
+    fn is_subset_works(set: &[&str], other: &[&str], expected: bool) {
+        let set: PermissionSet = set.iter().copied().collect();
+        let other: PermissionSet = other.iter().copied().collect();
+
+        assert_eq!(set.is_subset(&other), expected);
+    }
+
+    #[test]
+    fn set_operations_work() {
+        let set = PermissionSet::from(["write"]);
+
+        assert!(set.is_only(&HomePermission::Write));
+        assert!(!set.clone().with("read").is_only(&HomePermission::Write));
+        assert!(!PermissionSet::new().is_only(&HomePermission::Write));
+        assert_eq!(set.len(), 1);
+        assert_eq!(set.with(HomePermission::Read).to_string(), "read,write");
+    }
+}
@@ -3,14 +3,54 @@
 use crate::{
     prompt_sequence::MatchAttempt,
     snapd_client::{Action, Prompt, PromptReply},
-    Result,
+    Error, Result,
 };
 use serde::{de::DeserializeOwned, Serialize};
 use std::fmt;
 
 use super::{prompt::UiInput, SnapMeta};
 
+pub mod audio_record;
+pub mod camera;
+pub mod device;
 pub mod home;
+pub mod unknown;
+
+/// The single registry of the interfaces that we support.
+///
+/// Each entry is of the form `Variant(Interface, ProtoReply)` where `Variant` is the name used
+/// for the interface in each of the typed enums (`TypedPrompt`, `TypedPromptReply`,
+/// `TypedUiInput`, ...), `Interface` is the [SnapInterface] implementation and `ProtoReply` is
+/// the variant of the `PromptReply` oneof in the protobuf definition that the UI uses to reply
+/// to prompts for the interface. The fallback entry is used for any prompts whose interface
+/// does not match one of the known entries. Each interface listed here also needs to implement
+/// the `ProtoInterface` trait in the daemon server so that it can be mapped to and from the
+/// protobuf messages used by the UI.
+///
+/// This macro takes the name of another macro that is called with the registry so that the
+/// enums and dispatch logic that need to cover every interface can be generated from this list.
+/// Any additional tokens provided after the macro name are passed through as a prefix.
+macro_rules! with_interfaces {
+    ($callback:ident $(, $($args:tt)*)?) => {
+        $callback! {
+            $($($args)*)?
+            known: [
+                Home($crate::snapd_client::interfaces::home::HomeInterface, HomePromptReply),
+                Camera($crate::snapd_client::interfaces::camera::CameraInterface, CameraPromptReply),
+                AudioRecord(
+                    $crate::snapd_client::interfaces::audio_record::AudioRecordInterface,
+                    AudioRecordPromptReply
+                ),
+            ],
+            fallback: Unknown(
+                $crate::snapd_client::interfaces::unknown::UnknownInterface,
+                GenericPromptReply
+            ),
+        }
+    };
+}
+
+pub(crate) use with_interfaces;
 
 #[allow(async_fn_in_trait)]
 pub trait SnapInterface: fmt::Debug + Clone {
@@ -30,6 +70,59 @@ pub trait SnapInterface: fmt::Debug + Clone {
     fn prompt_to_reply(prompt: Prompt<Self>, action: Action) -> PromptReply<Self>;
 
     fn map_ui_input(&self, prompt: Prompt<Self>, meta: Option<SnapMeta>) -> Result<UiInput<Self>>;
+
+    /// A reply to `prompt` that mirrors a `reply` given by the user for a related prompt.
+    ///
+    /// By default this covers exactly what was requested by `prompt` with the action, lifespan
+    /// and duration of `reply`.
+    fn mirror_reply(prompt: Prompt<Self>, reply: &PromptReply<Self>) -> PromptReply<Self> {
+        let mut mirrored = Self::prompt_to_reply(prompt, reply.action);
+        mirrored.lifespan = reply.lifespan;
+        mirrored.duration = reply.duration.clone();
+
+        mirrored
+    }
+
+    /// Whether `prompt` can be presented to the user as a single prompt along with `other` so
+    /// that one reply covers both of them. By default prompts are never coalesced.
+    fn can_coalesce(_prompt: &Prompt<Self>, _other: &Prompt<Self>) -> bool {
+        false
+    }
+
+    /// Restrict the options offered to the user in `input` to those that also cover the prompts
+    /// that have been coalesced with it.
+    fn restrict_to_grouped(_input: &mut UiInput<Self>, _grouped: &[&Prompt<Self>]) {}
+
+    /// The path requested by a prompt for this interface if the interface has one.
+    fn requested_path(_constraints: &Self::Constraints) -> Option<&str> {
+        None
+    }
+
+    /// The permissions requested by a prompt for this interface.
+    fn requested_permissions(constraints: &Self::Constraints) -> Vec<String>;
+
+    /// The path pattern covered by a reply or rule for this interface if the interface has one.
+    fn path_pattern(_constraints: &Self::ReplyConstraints) -> Option<&str> {
+        None
+    }
+
+    /// The permissions covered by a reply or rule for this interface.
+    fn permissions(constraints: &Self::ReplyConstraints) -> Vec<String>;
+
+    /// Map the interface agnostic constraint fields of a rule edit to the constraint overrides
+    /// for this interface. By default the constraints of a rule can not be edited.
+    fn edit_overrides(
+        path_pattern: Option<String>,
+        permissions: Option<Vec<String>>,
+    ) -> Result<Self::ReplyConstraintsOverrides> {
+        if path_pattern.is_some() || permissions.is_some() {
+            return Err(Error::InvalidRuleEdit {
+                reason: format!("constraints for {} rules can not be edited", Self::NAME),
+            });
+        }
+
+        Ok(Default::default())
+    }
 }
 
 pub trait ConstraintsFilter: Default + fmt::Debug + Clone + Serialize + DeserializeOwned {
@@ -0,0 +1,304 @@
+use crate::{
+    prompt_sequence::{MatchAttempt, MatchFailure},
+    snapd_client::{
+        interfaces::{
+            ConstraintsFilter, Prompt, PromptReply, ReplyConstraintsOverrides, SnapInterface,
+        },
+        prompt::UiInput,
+        Action, Lifespan, Result, SnapMeta,
+    },
+};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+
+impl Prompt<UnknownInterface> {
+    pub fn constraints(&self) -> &Value {
+        &self.constraints
+    }
+}
+
+/// A catch-all for prompts from interfaces that we do not have a typed model for.
+///
+/// The constraints provided by snapd are kept as raw JSON so that we are still able to show them
+/// to the user and reply to the prompt once (either allowing or denying it) rather than leaving
+/// the requesting application blocked until snapd times out the prompt.
+#[derive(Debug, Default, Clone, Deserialize, Serialize, PartialEq, Eq)]
+pub struct UnknownInterface;
+
+impl SnapInterface for UnknownInterface {
+    const NAME: &'static str = "unknown";
+
+    type Constraints = Value;
+    type ReplyConstraints = Value;
+
+    type ConstraintsFilter = UnknownConstraintsFilter;
+    type ReplyConstraintsOverrides = UnknownReplyConstraintsOverrides;
+
+    type UiInputData = UnknownUiInputData;
+    type UiReply = UnknownUiReply;
+
+    fn prompt_to_reply(prompt: Prompt<Self>, action: Action) -> PromptReply<Self> {
+        PromptReply {
+            action,
+            lifespan: Lifespan::Single,
+            duration: None,
+            constraints: reply_constraints(&prompt.constraints),
+        }
+    }
+
+    fn map_ui_input(&self, prompt: Prompt<Self>, meta: Option<SnapMeta>) -> Result<UiInput<Self>> {
+        let meta = meta.unwrap_or_else(|| SnapMeta {
+            name: prompt.snap,
+            ..Default::default()
+        });
+
+        Ok(UiInput {
+            id: prompt.id,
+            timestamp: prompt.timestamp,
+            meta,
+            data: UnknownUiInputData {
+                interface: prompt.interface,
+                constraints: prompt.constraints,
+            },
+        })
+    }
+
+    fn requested_path(constraints: &Self::Constraints) -> Option<&str> {
+        constraints["path"].as_str()
+    }
+
+    fn requested_permissions(constraints: &Self::Constraints) -> Vec<String> {
+        string_array(&constraints["requested-permissions"])
+    }
+
+    fn path_pattern(constraints: &Self::ReplyConstraints) -> Option<&str> {
+        constraints["path-pattern"].as_str()
+    }
+
+    fn permissions(constraints: &Self::ReplyConstraints) -> Vec<String> {
+        string_array(&constraints["permissions"])
+    }
+
+    fn edit_overrides(
+        path_pattern: Option<String>,
+        permissions: Option<Vec<String>>,
+    ) -> Result<Self::ReplyConstraintsOverrides> {
+        let mut overrides = UnknownReplyConstraintsOverrides::default();
+        if let Some(path_pattern) = path_pattern {
+            overrides
+                .0
+                .insert("path-pattern".to_string(), path_pattern.into());
+        }
+        if let Some(permissions) = permissions {
+            overrides
+                .0
+                .insert("permissions".to_string(), permissions.into());
+        }
+
+        Ok(overrides)
+    }
+}
+
+/// Without knowing the shape of the reply constraints for an interface we follow the conventions
+/// of the interfaces that we do know about: the requested path (if any) becomes the path pattern
+/// and the requested permissions are the ones we reply for.
+fn reply_constraints(constraints: &Value) -> Value {
+    let mut reply = Map::new();
+    if let Some(path) = constraints.get("path") {
+        reply.insert("path-pattern".to_string(), path.clone());
+    }
+    if let Some(permissions) = constraints.get("requested-permissions") {
+        reply.insert("permissions".to_string(), permissions.clone());
+    }
+
+    Value::Object(reply)
+}
+
+/// The string elements of a JSON array, ignoring anything else.
+pub(crate) fn string_array(v: &Value) -> Vec<String> {
+    match v {
+        Value::Array(arr) => arr
+            .iter()
+            .filter_map(|s| s.as_str().map(String::from))
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnknownUiInputData {
+    pub(crate) interface: String,
+    pub(crate) constraints: Value,
+}
+
+impl UnknownUiInputData {
+    /// The reply constraints for the prompt this UI input was generated from.
+    pub fn reply_constraints(&self) -> Value {
+        reply_constraints(&self.constraints)
+    }
+
+    /// Flatten the raw constraints into key value pairs for display in the UI.
+    pub fn constraint_pairs(&self) -> Vec<(String, String)> {
+        let obj = match &self.constraints {
+            Value::Object(obj) => obj,
+            Value::Null => return Vec::new(),
+            v => return vec![(String::new(), display_value(v))],
+        };
+
+        obj.iter()
+            .map(|(k, v)| (k.clone(), display_value(v)))
+            .collect()
+    }
+}
+
+fn display_value(v: &Value) -> String {
+    match v {
+        Value::String(s) => s.clone(),
+        Value::Array(arr) => arr.iter().map(display_value).collect::<Vec<_>>().join(", "),
+        v => v.to_string(),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnknownUiReply {
+    action: Action,
+}
+
+/// Match on the raw constraints of a prompt: each key present in the filter must be present in
+/// the prompt constraints with an identical value.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct UnknownConstraintsFilter(pub Map<String, Value>);
+
+impl ConstraintsFilter for UnknownConstraintsFilter {
+    type Constraints = Value;
+
+    fn matches(&self, constraints: &Self::Constraints) -> MatchAttempt {
+        let failures: Vec<_> = self
+            .0
+            .iter()
+            .filter(|(k, v)| constraints.get(k) != Some(v))
+            .map(|(k, v)| MatchFailure {
+                field: "constraints",
+                expected: format!("{k}={v}"),
+                seen: format!("{k}={}", constraints.get(k).unwrap_or(&Value::Null)),
+            })
+            .collect();
+
+        if failures.is_empty() {
+            MatchAttempt::Success
+        } else {
+            MatchAttempt::Failure(failures)
+        }
+    }
+}
+
+/// Keys to set (or replace) in the raw reply constraints.
+#[derive(Debug, Default, Serialize, Deserialize, Clone)]
+#[serde(transparent)]
+pub struct UnknownReplyConstraintsOverrides(pub Map<String, Value>);
+
+impl ReplyConstraintsOverrides for UnknownReplyConstraintsOverrides {
+    type ReplyConstraints = Value;
+
+    fn apply(self, mut constraints: Self::ReplyConstraints) -> Self::ReplyConstraints {
+        if let Value::Object(obj) = &mut constraints {
+            obj.extend(self.0);
+        }
+
+        constraints
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapd_client::{RawPrompt, TypedPrompt};
+    use serde_json::json;
+
+    const UNKNOWN_PROMPT: &str = r#"{
+      "id": "0000000000000005",
+      "timestamp": "2024-08-14T07:28:22.694800024Z",
+      "snap": "some-snap",
+      "interface": "not-a-real-interface",
+      "constraints": {
+        "path": "/dev/foo",
+        "requested-permissions": [
+          "read",
+          "write"
+        ],
+        "available-permissions": [
+          "read",
+          "write"
+        ],
+        "extra": 42
+      }
+    }"#;
+
+    #[test]
+    fn deserializing_an_unknown_prompt_works() {
+        let raw: RawPrompt = serde_json::from_str(UNKNOWN_PROMPT).unwrap();
+        let p: TypedPrompt = raw.try_into().unwrap();
+
+        match p {
+            TypedPrompt::Unknown(p) => {
+                assert_eq!(p.interface(), "not-a-real-interface");
+                assert_eq!(p.constraints()["extra"], json!(42));
+            }
+            p => panic!("expected an unknown prompt, got {p:?}"),
+        }
+    }
+
+    #[test]
+    fn prompt_to_reply_uses_path_and_requested_permissions() {
+        let p: Prompt<UnknownInterface> = serde_json::from_str(UNKNOWN_PROMPT).unwrap();
+        let reply = UnknownInterface::prompt_to_reply(p, Action::Deny);
+
+        assert_eq!(
+            serde_json::to_value(reply).unwrap(),
+            json!({
+                "action": "deny",
+                "lifespan": "single",
+                "constraints": {
+                    "path-pattern": "/dev/foo",
+                    "permissions": ["read", "write"]
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn constraint_pairs_works() {
+        let data = UnknownUiInputData {
+            interface: "not-a-real-interface".to_string(),
+            constraints: json!({ "a": "b", "c": ["d", "e"], "f": 1 }),
+        };
+
+        assert_eq!(
+            data.constraint_pairs(),
+            vec![
+                ("a".to_string(), "b".to_string()),
+                ("c".to_string(), "d, e".to_string()),
+                ("f".to_string(), "1".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn constraints_filter_works() {
+        let filter: UnknownConstraintsFilter =
+            serde_json::from_value(json!({ "path": "/dev/foo", "extra": 1 })).unwrap();
+        let constraints = json!({ "path": "/dev/foo", "extra": 42 });
+
+        match filter.matches(&constraints) {
+            MatchAttempt::Failure(failures) => {
+                assert_eq!(failures.len(), 1);
+                assert_eq!(failures[0].expected, "extra=1");
+                assert_eq!(failures[0].seen, "extra=42");
+            }
+            MatchAttempt::Success => panic!("filter should not have matched"),
+        }
+    }
+}
@@ -1,25 +1,32 @@
 use crate::{
+    snapd_client::interfaces::{with_interfaces, SnapInterface},
     socket_client::{body_json, UnixSocketClient},
     Error, Result,
 };
 use chrono::{DateTime, SecondsFormat, Utc};
 use hyper::{body::Incoming, Response, Uri};
 use prompt::RawPrompt;
+use rule::RuleRequest;
 use serde::{de::DeserializeOwned, Deserialize, Serialize};
-use std::{collections::HashMap, env, str::FromStr};
+use std::{collections::HashMap, env, str::FromStr, time::Duration};
 use tracing::{debug, error, warn};
 
+pub mod duration;
 pub mod interfaces;
+pub mod path_pattern;
 mod prompt;
+mod rule;
 
 pub use prompt::{
     Action, Lifespan, Prompt, PromptId, PromptReply, TypedPrompt, TypedPromptReply, TypedUiInput,
     UiInput,
 };
+pub use rule::{NewRule, RawRule, Rule, RuleEdit, RuleId, RulePatch, TypedRule};
 
 const FEATURE_NAME: &str = "apparmor-prompting";
-const LONG_POLL_TIMEOUT: &str = "1h";
-const NOTICE_TYPES: &str = "interfaces-requests-prompt";
+const DEFAULT_LONG_POLL_TIMEOUT: Duration = Duration::from_secs(60 * 60);
+const PROMPT_NOTICE_TYPE: &str = "interfaces-requests-prompt";
+const RULE_NOTICE_TYPE: &str = "interfaces-requests-rule-update";
 const SNAPD_BASE_URI: &str = "http://localhost/v2";
 const SNAPD_SOCKET: &str = "/run/snapd.socket";
 const SNAPD_SNAP_SOCKET: &str = "/run/snapd-snap.socket";
@@ -112,7 +119,8 @@ where
     C: Client,
 {
     client: C,
-    notices_after: String,
+    notices_after: DateTime<Utc>,
+    long_poll_timeout: Duration,
 }
 
 pub type SnapdSocketClient = SnapdClient<UnixSocketClient>;
@@ -137,7 +145,8 @@ impl SnapdSocketClient {
 
         Self {
             client: UnixSocketClient::new(socket),
-            notices_after: dt.to_rfc3339_opts(SecondsFormat::Nanos, true),
+            notices_after: dt,
+            long_poll_timeout: DEFAULT_LONG_POLL_TIMEOUT,
         }
     }
 }
@@ -164,37 +173,56 @@ where
         Ok(())
     }
 
+    /// Set the timeout requested from snapd when long polling the notices API.
+    pub fn set_long_poll_timeout(&mut self, timeout: Duration) {
+        self.long_poll_timeout = timeout;
+    }
+
     /// HTTP long poll on the /v2/notices API from snapd to await prompt requests for the user we
     /// are running under.
     ///
     /// Calling this method will update our [Self::notices_after] field when we successfully obtain
     /// new notices from snapd.
     pub async fn pending_prompt_ids(&mut self) -> Result<Vec<PromptId>> {
-        let path = format!(
-            "notices?types={NOTICE_TYPES}&timeout={LONG_POLL_TIMEOUT}&after={}",
+        let notices = self.raw_notices(PROMPT_NOTICE_TYPE).await?;
+
+        Ok(notices.into_iter().map(|n| PromptId(n.key)).collect())
+    }
+
+    /// HTTP long poll on the /v2/notices API from snapd to await both prompt requests and changes
+    /// to prompting rules for the user we are running under.
+    ///
+    /// As with [Self::pending_prompt_ids], this updates our [Self::notices_after] field when we
+    /// successfully obtain new notices from snapd.
+    pub async fn pending_notices(&mut self) -> Result<Vec<SnapdNotice>> {
+        let types = format!("{PROMPT_NOTICE_TYPE},{RULE_NOTICE_TYPE}");
+        let notices = self.raw_notices(&types).await?;
+
+        Ok(notices
+            .into_iter()
+            .filter_map(RawNotice::into_notice)
+            .collect())
+    }
+
+    fn notices_path(&self, types: &str) -> String {
+        format!(
+            "notices?types={types}&timeout={}s&after={}",
+            self.long_poll_timeout.as_secs(),
             self.notices_after
-        );
+                .to_rfc3339_opts(SecondsFormat::Nanos, true)
+        )
+    }
 
-        let notices: Vec<Notice> = self.client.get_json(&path).await?;
+    async fn raw_notices(&mut self, types: &str) -> Result<Vec<RawNotice>> {
+        let path = self.notices_path(types);
+        let notices: Vec<RawNotice> = self.client.get_json(&path).await?;
         if let Some(n) = notices.last() {
-            n.last_occurred.clone_into(&mut self.notices_after);
+            self.notices_after = n.last_occurred;
         }
 
         debug!("received notices: {notices:?}");
 
-        return Ok(notices.into_iter().map(|n| n.key).collect());
-
-        // serde structs
-
-        #[derive(Debug, Deserialize)]
-        #[serde(rename_all = "kebab-case")]
-        struct Notice {
-            key: PromptId,
-            last_occurred: String,
-            #[allow(dead_code)]
-            #[serde(flatten)]
-            extra: HashMap<String, serde_json::Value>,
-        }
+        Ok(notices)
     }
 
     /// Pull details for all pending prompts from snapd
@@ -223,7 +251,10 @@ where
     ) -> Result<Vec<PromptId>> {
         let resp: Option<Vec<PromptId>> = self
             .client
-            .post_json(&format!("interfaces/requests/prompts/{}", id.0), reply)
+            .post_json(
+                &format!("interfaces/requests/prompts/{}", id.0),
+                reply.to_snapd_json()?,
+            )
             .await?;
 
         debug!(prompt = id.0, ?resp, "response from snapd");
@@ -231,42 +262,177 @@ where
         Ok(resp.unwrap_or_default())
     }
 
+    /// Pull details for all prompting rules from snapd, optionally restricted to those for a
+    /// specific snap and/or interface.
+    pub async fn list_rules(
+        &self,
+        snap: Option<&str>,
+        interface: Option<&str>,
+    ) -> Result<Vec<TypedRule>> {
+        let mut params = Vec::new();
+        if let Some(snap) = snap {
+            params.push(rule_filter_param("snap", snap)?);
+        }
+        if let Some(interface) = interface {
+            params.push(rule_filter_param("interface", interface)?);
+        }
+
+        let mut path = "interfaces/requests/rules".to_string();
+        if !params.is_empty() {
+            path.push('?');
+            path.push_str(&params.join("&"));
+        }
+
+        let raw_rules: Vec<RawRule> = self.client.get_json(&path).await?;
+
+        raw_rules.into_iter().map(|r| r.try_into()).collect()
+    }
+
+    /// Pull details for a specific rule from snapd
+    pub async fn rule_details(&self, id: &RuleId) -> Result<TypedRule> {
+        let rule: RawRule = self
+            .client
+            .get_json(&format!("interfaces/requests/rules/{}", id.0))
+            .await?;
+
+        rule.try_into()
+    }
+
+    /// Add a new rule directly, without needing to first receive a prompt
+    pub async fn add_rule<I>(&self, rule: NewRule<I>) -> Result<TypedRule>
+    where
+        I: SnapInterface,
+    {
+        let rule: RawRule = self
+            .client
+            .post_json("interfaces/requests/rules", RuleRequest::Add { rule })
+            .await?;
+
+        debug!(rule = rule.id.0, "added rule");
+
+        rule.try_into()
+    }
+
+    /// Modify an existing rule, returning the updated rule
+    pub async fn patch_rule<I>(&self, id: &RuleId, patch: RulePatch<I>) -> Result<TypedRule>
+    where
+        I: SnapInterface,
+    {
+        let rule: RawRule = self
+            .client
+            .post_json(
+                &format!("interfaces/requests/rules/{}", id.0),
+                RuleRequest::Patch { rule: patch },
+            )
+            .await?;
+
+        debug!(rule = id.0, "patched rule");
+
+        rule.try_into()
+    }
+
+    /// Apply an interface agnostic edit to an existing rule, returning the updated rule
+    pub async fn edit_rule(&self, id: &RuleId, edit: RuleEdit) -> Result<TypedRule> {
+        let rule = self.rule_details(id).await?;
+
+        macro_rules! patch_typed_rule {
+            (
+                known: [$($variant:ident($iface:ty, $proto:ident)),+ $(,)?],
+                fallback: $fallback:ident($fallback_iface:ty, $fallback_proto:ident) $(,)?
+            ) => {
+                match rule {
+                    $(TypedRule::$variant(rule) => self.patch_rule(id, edit.into_patch(rule)?).await,)+
+                    TypedRule::$fallback(rule) => self.patch_rule(id, edit.into_patch(rule)?).await,
+                }
+            };
+        }
+
+        with_interfaces!(patch_typed_rule)
+    }
+
+    /// Remove an existing rule, returning the rule that was removed
+    pub async fn remove_rule(&self, id: &RuleId) -> Result<TypedRule> {
+        let rule: RawRule = self
+            .client
+            .post_json(
+                &format!("interfaces/requests/rules/{}", id.0),
+                RuleRequest::<()>::Remove,
+            )
+            .await?;
+
+        debug!(rule = id.0, "removed rule");
+
+        rule.try_into()
+    }
+
     /// Pull metadata for rendering apparmor prompts using the `snaps` snapd endpoint.
     pub async fn snap_metadata(&self, name: &str) -> Option<SnapMeta> {
-        let res = self.client.get_json(&format!("snaps/{name}")).await;
-        return match res {
-            Ok(SnapDetails {
-                install_date,
-                publisher,
-            }) => Some(SnapMeta {
-                name: name.to_owned(),
-                updated_at: install_date
-                    .split_once('T')
-                    .map(|(s, _)| s.to_owned())
-                    .unwrap_or(install_date),
-                store_url: format!("snap://{name}"),
-                publisher: publisher.display_name,
-            }),
+        let res: Result<SnapDetails> = self.client.get_json(&format!("snaps/{name}")).await;
 
+        match res {
+            Ok(details) => Some(details.into_meta(name)),
             Err(e) => {
                 error!("unable to pull snap metadata for {name}: {e}");
                 None
             }
-        };
+        }
+    }
+}
 
-        // Serde structs
+/// A notice from snapd that requires action on our part.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SnapdNotice {
+    /// A prompt has been created or updated.
+    Prompt(PromptId),
+    /// A rule has been created or changed, or has been removed for the given reason.
+    Rule {
+        id: RuleId,
+        removed: Option<RuleRemoval>,
+    },
+}
 
-        #[derive(Debug, Default, Deserialize)]
-        #[serde(rename_all = "kebab-case")]
-        struct SnapDetails {
-            install_date: String,
-            publisher: Publisher,
-        }
+/// Why a rule notified on by snapd is no longer present.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuleRemoval {
+    Removed,
+    Expired,
+}
 
-        #[derive(Debug, Default, Deserialize)]
-        #[serde(rename_all = "kebab-case")]
-        struct Publisher {
-            display_name: String,
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawNotice {
+    #[serde(rename = "type")]
+    ty: String,
+    key: String,
+    last_occurred: DateTime<Utc>,
+    #[serde(default)]
+    last_data: HashMap<String, String>,
+}
+
+impl RawNotice {
+    fn into_notice(self) -> Option<SnapdNotice> {
+        match self.ty.as_str() {
+            PROMPT_NOTICE_TYPE => Some(SnapdNotice::Prompt(PromptId(self.key))),
+
+            RULE_NOTICE_TYPE => {
+                let removed = self
+                    .last_data
+                    .get("removed")
+                    .map(|reason| match reason.as_str() {
+                        "expired" => RuleRemoval::Expired,
+                        _ => RuleRemoval::Removed,
+                    });
+
+                Some(SnapdNotice::Rule {
+                    id: RuleId(self.key),
+                    removed,
+                })
+            }
+
+            ty => {
+                warn!(%ty, key=%self.key, "unexpected notice type from snapd");
+                None
+            }
         }
     }
 }
@@ -276,8 +442,90 @@ where
 pub struct SnapMeta {
     pub name: String,
     pub updated_at: String,
+    /// Only set for snaps that were installed from the store.
     pub store_url: String,
     pub publisher: String,
+    pub publisher_validation: PublisherValidation,
+    pub version: String,
+    pub revision: String,
+    pub channel: String,
+    pub confinement: String,
+    pub title: String,
+    pub summary: String,
+    /// The snapd REST API path (`/v2/icons/<name>/icon`) for fetching the icon of the snap if
+    /// it has one. This is not a path on the filesystem.
+    pub icon_api_path: String,
+}
+
+/// How the store has validated the identity of a snap publisher.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PublisherValidation {
+    Verified,
+    Starred,
+    Unproven,
+    /// Snaps installed locally have no publisher validation.
+    #[default]
+    #[serde(other)]
+    Unknown,
+}
+
+/// The subset of the `snaps/{name}` response from snapd that we present to the user.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+struct SnapDetails {
+    install_date: String,
+    publisher: Publisher,
+    version: String,
+    revision: String,
+    channel: String,
+    confinement: String,
+    title: String,
+    summary: String,
+    icon: String,
+    store_url: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "kebab-case", default)]
+struct Publisher {
+    display_name: String,
+    validation: PublisherValidation,
+}
+
+impl SnapDetails {
+    fn into_meta(self, name: &str) -> SnapMeta {
+        let Self {
+            install_date,
+            publisher,
+            version,
+            revision,
+            channel,
+            confinement,
+            title,
+            summary,
+            icon,
+            store_url,
+        } = self;
+
+        SnapMeta {
+            name: name.to_owned(),
+            updated_at: install_date
+                .split_once('T')
+                .map(|(s, _)| s.to_owned())
+                .unwrap_or(install_date),
+            store_url,
+            publisher: publisher.display_name,
+            publisher_validation: publisher.validation,
+            version,
+            revision,
+            channel,
+            confinement,
+            title,
+            summary,
+            icon_api_path: icon,
+        }
+    }
 }
 
 #[derive(Debug, Default, Deserialize)]
@@ -307,6 +555,25 @@ struct Feature {
     unsupported_reason: Option<String>,
 }
 
+/// Snap and interface names (including snap instance keys) are restricted to lowercase ASCII
+/// letters, digits, hyphens and underscores so we reject anything else rather than needing to
+/// percent-encode it in the query string.
+fn rule_filter_param(field: &'static str, value: &str) -> Result<String> {
+    let valid = !value.is_empty()
+        && value
+            .chars()
+            .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-' || c == '_');
+
+    if !valid {
+        return Err(Error::InvalidRuleFilter {
+            field,
+            value: value.to_string(),
+        });
+    }
+
+    Ok(format!("{field}={value}"))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -385,7 +652,7 @@ mod tests {
         let raw: SnapdResponse<RawPrompt> = serde_json::from_str(RAW_PROMPT).unwrap();
         let expected = RawPrompt {
             id: PromptId("00000000000000BE".to_string()),
-            timestamp: "2024-08-15T13:28:17.077016791Z".to_string(),
+            timestamp: "2024-08-15T13:28:17.077016791Z".parse().unwrap(),
             snap: "aa-prompting-test".to_string(),
             interface: "home".to_string(),
             constraints: serde_json::json!({
@@ -397,4 +664,200 @@ mod tests {
 
         assert_eq!(raw.result, ResOrErr::Res(expected));
     }
+
+    const RAW_SNAP_DETAILS: &str = r#"{
+  "id": "3wdHCAVyZEmYsCMFDE9qt92UV8rC8Wdk",
+  "title": "firefox",
+  "summary": "Mozilla Firefox web browser",
+  "description": "Firefox is a powerful, extensible web browser with support for modern web application technologies.",
+  "icon": "/v2/icons/firefox/icon",
+  "installed-size": 261066752,
+  "install-date": "2024-08-12T09:12:31.419632427+01:00",
+  "name": "firefox",
+  "publisher": {
+    "id": "OgeoZuqQpVvSr9eGKJzNCrFGSaKXpkey",
+    "username": "mozilla",
+    "display-name": "Mozilla",
+    "validation": "verified"
+  },
+  "developer": "mozilla",
+  "status": "active",
+  "type": "app",
+  "base": "core22",
+  "version": "129.0-2",
+  "channel": "stable",
+  "tracking-channel": "latest/stable",
+  "revision": "4757",
+  "confinement": "strict",
+  "store-url": "https://snapcraft.io/firefox"
+}"#;
+
+    #[test]
+    fn snap_details_parsing_works() {
+        let details: SnapDetails = serde_json::from_str(RAW_SNAP_DETAILS).unwrap();
+        let expected = SnapMeta {
+            name: "firefox".to_string(),
+            updated_at: "2024-08-12".to_string(),
+            store_url: "https://snapcraft.io/firefox".to_string(),
+            publisher: "Mozilla".to_string(),
+            publisher_validation: PublisherValidation::Verified,
+            version: "129.0-2".to_string(),
+            revision: "4757".to_string(),
+            channel: "stable".to_string(),
+            confinement: "strict".to_string(),
+            title: "firefox".to_string(),
+            summary: "Mozilla Firefox web browser".to_string(),
+            icon_api_path: "/v2/icons/firefox/icon".to_string(),
+        };
+
+        assert_eq!(details.into_meta("firefox"), expected);
+    }
+
+    #[test_case(r#"{"validation": "verified"}"#, PublisherValidation::Verified; "verified")]
+    #[test_case(r#"{"validation": "starred"}"#, PublisherValidation::Starred; "starred")]
+    #[test_case(r#"{"validation": "unproven"}"#, PublisherValidation::Unproven; "unproven")]
+    #[test_case(r#"{"validation": "something-new"}"#, PublisherValidation::Unknown; "unrecognised")]
+    #[test_case("{}", PublisherValidation::Unknown; "missing")]
+    #[test]
+    fn publisher_validation_parsing_works(raw: &str, expected: PublisherValidation) {
+        let publisher: Publisher = serde_json::from_str(raw).unwrap();
+
+        assert_eq!(publisher.validation, expected);
+    }
+
+    #[test]
+    fn local_snap_details_parsing_works() {
+        let raw = r#"{"name": "aa-prompting-test", "revision": "x1", "confinement": "strict"}"#;
+        let meta = serde_json::from_str::<SnapDetails>(raw)
+            .unwrap()
+            .into_meta("aa-prompting-test");
+
+        assert_eq!(meta.revision, "x1");
+        assert_eq!(meta.store_url, "");
+        assert_eq!(meta.publisher_validation, PublisherValidation::Unknown);
+    }
+
+    const RAW_NOTICES: &str = r#"[
+  {
+    "id": "1",
+    "user-id": 1000,
+    "type": "interfaces-requests-prompt",
+    "key": "00000000000000BE",
+    "first-occurred": "2024-08-15T13:28:17.077016791Z",
+    "last-occurred": "2024-08-15T13:28:17.077016791Z",
+    "last-repeated": "2024-08-15T13:28:17.077016791Z",
+    "occurrences": 1,
+    "expire-after": "168h0m0s"
+  },
+  {
+    "id": "2",
+    "user-id": 1000,
+    "type": "interfaces-requests-rule-update",
+    "key": "0000000000000002",
+    "first-occurred": "2024-08-15T13:28:18.077016791Z",
+    "last-occurred": "2024-08-15T13:28:18.077016791Z",
+    "last-repeated": "2024-08-15T13:28:18.077016791Z",
+    "occurrences": 1,
+    "expire-after": "168h0m0s"
+  },
+  {
+    "id": "3",
+    "user-id": 1000,
+    "type": "interfaces-requests-rule-update",
+    "key": "0000000000000003",
+    "first-occurred": "2024-08-15T13:28:19.077016791Z",
+    "last-occurred": "2024-08-15T13:28:19.077016791Z",
+    "last-repeated": "2024-08-15T13:28:19.077016791Z",
+    "last-data": { "removed": "removed" },
+    "occurrences": 2,
+    "expire-after": "168h0m0s"
+  },
+  {
+    "id": "4",
+    "user-id": 1000,
+    "type": "interfaces-requests-rule-update",
+    "key": "0000000000000004",
+    "first-occurred": "2024-08-15T13:28:20.077016791Z",
+    "last-occurred": "2024-08-15T13:28:20.077016791Z",
+    "last-repeated": "2024-08-15T13:28:20.077016791Z",
+    "last-data": { "removed": "expired" },
+    "occurrences": 2,
+    "expire-after": "168h0m0s"
+  },
+  {
+    "id": "5",
+    "user-id": 1000,
+    "type": "change-update",
+    "key": "42",
+    "first-occurred": "2024-08-15T13:28:21.077016791Z",
+    "last-occurred": "2024-08-15T13:28:21.077016791Z",
+    "last-repeated": "2024-08-15T13:28:21.077016791Z",
+    "occurrences": 1,
+    "expire-after": "168h0m0s"
+  }
+]"#;
+
+    #[test]
+    fn raw_notice_parsing_works() {
+        let raw: Vec<RawNotice> = serde_json::from_str(RAW_NOTICES).unwrap();
+        let notices: Vec<SnapdNotice> =
+            raw.into_iter().filter_map(RawNotice::into_notice).collect();
+
+        let expected = vec![
+            SnapdNotice::Prompt(PromptId("00000000000000BE".to_string())),
+            SnapdNotice::Rule {
+                id: RuleId("0000000000000002".to_string()),
+                removed: None,
+            },
+            SnapdNotice::Rule {
+                id: RuleId("0000000000000003".to_string()),
+                removed: Some(RuleRemoval::Removed),
+            },
+            SnapdNotice::Rule {
+                id: RuleId("0000000000000004".to_string()),
+                removed: Some(RuleRemoval::Expired),
+            },
+        ];
+
+        assert_eq!(notices, expected);
+    }
+
+    #[test_case("2024-08-15T13:28:17.077016791Z", "2024-08-15T13:28:17.077016791Z"; "nanos")]
+    #[test_case("2024-08-15T13:28:17Z", "2024-08-15T13:28:17.000000000Z"; "whole seconds")]
+    #[test_case("2024-08-15T15:28:17.5+02:00", "2024-08-15T13:28:17.500000000Z"; "offset")]
+    #[test]
+    fn notices_path_uses_last_occurred(last_occurred: &str, expected: &str) {
+        let raw: RawNotice = serde_json::from_value(serde_json::json!({
+            "type": PROMPT_NOTICE_TYPE,
+            "key": "1",
+            "last-occurred": last_occurred,
+        }))
+        .unwrap();
+        let mut client = SnapdSocketClient::new_with_notices_after(raw.last_occurred);
+        client.set_long_poll_timeout(Duration::from_secs(60));
+
+        assert_eq!(
+            client.notices_path(PROMPT_NOTICE_TYPE),
+            format!("notices?types={PROMPT_NOTICE_TYPE}&timeout=60s&after={expected}")
+        );
+    }
+
+    #[test_case("firefox", true; "snap name")]
+    #[test_case("audio-record", true; "interface name")]
+    #[test_case("firefox_beta", true; "snap instance")]
+    #[test_case("", false; "empty")]
+    #[test_case("foo&interface=home", false; "query injection")]
+    #[test_case("Firefox", false; "uppercase")]
+    #[test_case("foo bar", false; "whitespace")]
+    #[test]
+    fn rule_filter_param_validates_names(value: &str, expect_ok: bool) {
+        match rule_filter_param("snap", value) {
+            Ok(param) if expect_ok => assert_eq!(param, format!("snap={value}")),
+            Err(Error::InvalidRuleFilter { field, value: v }) if !expect_ok => {
+                assert_eq!(field, "snap");
+                assert_eq!(v, value);
+            }
+            res => panic!("unexpected result: {res:?}"),
+        }
+    }
 }
@@ -0,0 +1,260 @@
+//! Parsing and matching of the path patterns accepted by snapd in reply constraints.
+//!
+//! Path patterns use doublestar glob syntax:
+//!   - `*` matches any number of characters within a single path segment
+//!   - `**` as a full path segment matches zero or more path segments
+//!   - `?` matches a single character other than `/`
+//!   - `{a,b}` groups match any one of the comma separated alternatives (and may be nested)
+//!   - `\` escapes the following character so that it is matched literally
+//!
+//! Character classes (`[...]`) are reserved by snapd and so are rejected.
+use crate::{Error, Result};
+use regex::Regex;
+
+/// The maximum number of patterns that a single pattern may expand to once all groups have
+/// been expanded.
+const MAX_EXPANSIONS: usize = 1000;
+
+/// The maximum depth of nested groups within a pattern.
+const MAX_GROUP_DEPTH: usize = 50;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Literal(char),
+    AnySegmentChars,
+    AnyChar,
+    AnySegments,
+    Group(Vec<Vec<Token>>),
+}
+
+/// A validated path pattern that can be checked against concrete paths.
+#[derive(Debug, Clone)]
+pub struct PathPattern {
+    raw: String,
+    re: Regex,
+}
+
+impl PathPattern {
+    pub fn parse(pattern: impl Into<String>) -> Result<Self> {
+        let raw = pattern.into();
+        let invalid = |reason| Error::InvalidPathPattern {
+            pattern: raw.clone(),
+            reason,
+        };
+
+        if raw.is_empty() {
+            return Err(invalid("pattern is empty"));
+        } else if !raw.starts_with('/') {
+            return Err(invalid("pattern must be an absolute path"));
+        }
+
+        let chars: Vec<char> = raw.chars().collect();
+        let mut pos = 0;
+        let tokens = parse_alternative(&chars, &mut pos, 0).map_err(invalid)?;
+        if pos != chars.len() {
+            return Err(invalid("unmatched '}' in pattern"));
+        }
+        if n_expansions(&tokens) > MAX_EXPANSIONS {
+            return Err(invalid("pattern expands to too many alternatives"));
+        }
+
+        let mut re = String::from("^");
+        push_regex(&tokens, &mut re);
+        re.push('$');
+
+        Ok(Self {
+            re: Regex::new(&re)?,
+            raw,
+        })
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.raw
+    }
+
+    pub fn matches(&self, path: &str) -> bool {
+        self.re.is_match(path)
+    }
+}
+
+/// Check that `pattern` is a valid path pattern that matches the provided path.
+pub(crate) fn validate_path_pattern(pattern: &str, path: &str) -> Result<PathPattern> {
+    let patt = PathPattern::parse(pattern)?;
+    if !patt.matches(path) {
+        return Err(Error::InvalidPathPattern {
+            pattern: pattern.to_string(),
+            reason: "pattern does not match the requested path",
+        });
+    }
+
+    Ok(patt)
+}
+
+fn parse_alternative(
+    chars: &[char],
+    pos: &mut usize,
+    depth: usize,
+) -> std::result::Result<Vec<Token>, &'static str> {
+    let mut tokens = Vec::new();
+
+    while let Some(&c) = chars.get(*pos) {
+        match c {
+            // The end of this alternative: handled by the enclosing group
+            ',' | '}' if depth > 0 => return Ok(tokens),
+            '}' => return Err("unmatched '}' in pattern"),
+            '[' | ']' => return Err("character classes are not supported"),
+
+            '\\' => {
+                *pos += 1;
+                match chars.get(*pos) {
+                    Some(&c) => tokens.push(Token::Literal(c)),
+                    None => return Err("pattern ends with an escape character"),
+                }
+            }
+
+            '{' => {
+                if depth == MAX_GROUP_DEPTH {
+                    return Err("groups are nested too deeply");
+                }
+                *pos += 1;
+                let mut alternatives = vec![parse_alternative(chars, pos, depth + 1)?];
+                loop {
+                    match chars.get(*pos) {
+                        Some(',') => {
+                            *pos += 1;
+                            alternatives.push(parse_alternative(chars, pos, depth + 1)?);
+                        }
+                        Some('}') => break,
+                        _ => return Err("unmatched '{' in pattern"),
+                    }
+                }
+                tokens.push(Token::Group(alternatives));
+            }
+
+            '*' if chars.get(*pos + 1) == Some(&'*') => {
+                *pos += 1;
+                let starts_segment = chars.get(pos.wrapping_sub(2)) == Some(&'/');
+                let ends_segment = matches!(chars.get(*pos + 1), None | Some('/'));
+                if starts_segment && ends_segment {
+                    tokens.push(Token::AnySegments);
+                } else {
+                    // Outside of a full segment a double star is equivalent to a single star
+                    tokens.push(Token::AnySegmentChars);
+                }
+            }
+
+            '*' => tokens.push(Token::AnySegmentChars),
+            '?' => tokens.push(Token::AnyChar),
+            c => tokens.push(Token::Literal(c)),
+        }
+
+        *pos += 1;
+    }
+
+    Ok(tokens)
+}
+
+fn n_expansions(tokens: &[Token]) -> usize {
+    tokens.iter().fold(1, |acc, t| match t {
+        Token::Group(alternatives) => {
+            let n: usize = alternatives.iter().map(|alt| n_expansions(alt)).sum();
+            acc.saturating_mul(n)
+        }
+        _ => acc,
+    })
+}
+
+fn push_regex(tokens: &[Token], re: &mut String) {
+    let mut tokens = tokens.iter().peekable();
+
+    while let Some(t) = tokens.next() {
+        match t {
+            Token::Literal(c) => re.push_str(&regex::escape(&c.to_string())),
+            Token::AnySegmentChars => re.push_str("[^/]*"),
+            Token::AnyChar => re.push_str("[^/]"),
+            Token::AnySegments => {
+                if tokens.peek() == Some(&&Token::Literal('/')) {
+                    // "/**/" matches "/" as well as any number of intermediate directories
+                    tokens.next();
+                    re.push_str("(?:.*/)?");
+                } else {
+                    re.push_str(".*");
+                }
+            }
+            Token::Group(alternatives) => {
+                re.push_str("(?:");
+                for (i, alt) in alternatives.iter().enumerate() {
+                    if i > 0 {
+                        re.push('|');
+                    }
+                    push_regex(alt, re);
+                }
+                re.push(')');
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use simple_test_case::test_case;
+
+    #[test_case("/home/user/foo.txt", "/home/user/foo.txt"; "literal path")]
+    #[test_case("/home/user/*", "/home/user/foo.txt"; "single star")]
+    #[test_case("/home/user/*.txt", "/home/user/foo.txt"; "single star with extension")]
+    #[test_case("/home/user/foo.???", "/home/user/foo.txt"; "question marks")]
+    #[test_case("/home/user/**", "/home/user/a/b/c/foo.txt"; "trailing double star")]
+    #[test_case("/home/user/**", "/home/user/"; "trailing double star matches the directory itself")]
+    #[test_case("/home/user/**/foo.txt", "/home/user/foo.txt"; "double star matching no segments")]
+    #[test_case("/home/user/**/foo.txt", "/home/user/a/b/foo.txt"; "double star matching many segments")]
+    #[test_case("/home/user/foo.{txt,md}", "/home/user/foo.md"; "group")]
+    #[test_case("/home/user/{a,b{c,d}}/foo", "/home/user/bd/foo"; "nested group")]
+    #[test_case("/home/user/foo\\*", "/home/user/foo*"; "escaped star")]
+    #[test_case("/home/user/foo**", "/home/user/foobar"; "double star within segment")]
+    #[test]
+    fn matching_paths_work(pattern: &str, path: &str) {
+        let patt = PathPattern::parse(pattern).unwrap();
+        assert!(patt.matches(path), "{pattern:?} should match {path:?}");
+    }
+
+    #[test_case("/home/user/*", "/home/user/a/foo.txt"; "single star crossing segments")]
+    #[test_case("/home/user/foo.?", "/home/user/foo.txt"; "question mark matches one char")]
+    #[test_case("/home/user/foo.{txt,md}", "/home/user/foo.rs"; "group")]
+    #[test_case("/home/user/foo\\*", "/home/user/foobar"; "escaped star")]
+    #[test_case("/home/user/foo**", "/home/user/foo/bar"; "double star within segment")]
+    #[test]
+    fn non_matching_paths_do_not_match(pattern: &str, path: &str) {
+        let patt = PathPattern::parse(pattern).unwrap();
+        assert!(!patt.matches(path), "{pattern:?} should not match {path:?}");
+    }
+
+    #[test_case("", "pattern is empty"; "empty")]
+    #[test_case("not a path", "pattern must be an absolute path"; "relative")]
+    #[test_case("/home/user/[ab]", "character classes are not supported"; "character class")]
+    #[test_case("/home/user/{a,b", "unmatched '{' in pattern"; "unclosed group")]
+    #[test_case("/home/user/a}", "unmatched '}' in pattern"; "unopened group")]
+    #[test_case("/home/user/a\\", "pattern ends with an escape character"; "trailing escape")]
+    #[test_case(
+        "/{a,b,c,d,e,f,g,h,i,j}{a,b,c,d,e,f,g,h,i,j}{a,b,c,d,e,f,g,h,i,j}{a,b}",
+        "pattern expands to too many alternatives";
+        "too many expansions"
+    )]
+    #[test]
+    fn invalid_patterns_error(pattern: &str, expected: &str) {
+        match PathPattern::parse(pattern) {
+            Err(Error::InvalidPathPattern { reason, .. }) => assert_eq!(reason, expected),
+            res => panic!("expected InvalidPathPattern, got {res:?}"),
+        }
+    }
+
+    #[test]
+    fn validate_path_pattern_errors_for_non_matching_paths() {
+        match validate_path_pattern("/home/user/Pictures/**", "/home/user/Documents/foo.txt") {
+            Err(Error::InvalidPathPattern { reason, .. }) => {
+                assert_eq!(reason, "pattern does not match the requested path")
+            }
+            res => panic!("expected InvalidPathPattern, got {res:?}"),
+        }
+    }
+}
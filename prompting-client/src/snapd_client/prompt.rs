@@ -1,62 +1,313 @@
 //! Types for working with apparmor prompts
 use crate::{
     snapd_client::{
-        interfaces::{home::HomeInterface, SnapInterface},
+        duration::GoDuration,
+        interfaces::{with_interfaces, SnapInterface},
         SnapMeta,
     },
     Error, Result,
 };
+use chrono::{DateTime, TimeDelta, Utc};
 use serde::{Deserialize, Serialize};
 use strum::{Display, EnumString};
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum TypedPrompt {
-    Home(Prompt<HomeInterface>),
-}
+/// Generate the enums over all supported interfaces along with their dispatch methods.
+///
+/// See [with_interfaces] for how the list of interfaces is defined.
+macro_rules! typed_enums {
+    (
+        known: [$($variant:ident($iface:ty, $proto:ident)),+ $(,)?],
+        fallback: $fallback:ident($fallback_iface:ty, $fallback_proto:ident) $(,)?
+    ) => {
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(untagged, try_from = "RawPrompt")]
+        pub enum TypedPrompt {
+            $($variant(Prompt<$iface>),)+
+            $fallback(Prompt<$fallback_iface>),
+        }
 
-impl TypedPrompt {
-    pub fn into_deny_once(self) -> TypedPromptReply {
-        let Self::Home(p) = self;
-        HomeInterface::prompt_to_reply(p, Action::Deny).into()
-    }
+        impl TypedPrompt {
+            pub fn into_deny_once(self) -> TypedPromptReply {
+                self.into_reply_once(Action::Deny)
+            }
+
+            /// A deny reply for exactly what was requested by this prompt that applies for the
+            /// remainder of the user's current session.
+            pub fn into_deny_for_session(self) -> TypedPromptReply {
+                match self {
+                    $(Self::$variant(p) => {
+                        <$iface>::prompt_to_reply(p, Action::Deny).for_session().into()
+                    })+
+                    Self::$fallback(p) => {
+                        <$fallback_iface>::prompt_to_reply(p, Action::Deny).for_session().into()
+                    }
+                }
+            }
+
+            /// A reply with the given action for exactly what was requested by this prompt.
+            pub fn into_reply_once(self, action: Action) -> TypedPromptReply {
+                match self {
+                    $(Self::$variant(p) => <$iface>::prompt_to_reply(p, action).into(),)+
+                    Self::$fallback(p) => <$fallback_iface>::prompt_to_reply(p, action).into(),
+                }
+            }
+
+            pub fn id(&self) -> &PromptId {
+                match self {
+                    $(Self::$variant(p) => &p.id,)+
+                    Self::$fallback(p) => &p.id,
+                }
+            }
+
+            pub fn snap(&self) -> &str {
+                match self {
+                    $(Self::$variant(p) => &p.snap,)+
+                    Self::$fallback(p) => &p.snap,
+                }
+            }
+
+            pub fn interface(&self) -> &str {
+                match self {
+                    $(Self::$variant(p) => &p.interface,)+
+                    Self::$fallback(p) => &p.interface,
+                }
+            }
+
+            /// Whether this prompt can be coalesced with `other` so that a single reply from the
+            /// user covers both of them.
+            pub fn can_coalesce_with(&self, other: &TypedPrompt) -> bool {
+                match (self, other) {
+                    $((Self::$variant(p), Self::$variant(other)) => {
+                        <$iface>::can_coalesce(p, other)
+                    })+
+                    (Self::$fallback(p), Self::$fallback(other)) => {
+                        <$fallback_iface>::can_coalesce(p, other)
+                    }
+                    _ => false,
+                }
+            }
+
+            /// A reply to this prompt that mirrors a `reply` given by the user for a related
+            /// prompt. Prompts for a different interface to `reply` are replied to once with the
+            /// same action.
+            pub fn into_reply_like(self, reply: &TypedPromptReply) -> TypedPromptReply {
+                match (self, reply) {
+                    $((Self::$variant(p), TypedPromptReply::$variant(r)) => {
+                        <$iface>::mirror_reply(p, r).into()
+                    })+
+                    (Self::$fallback(p), TypedPromptReply::$fallback(r)) => {
+                        <$fallback_iface>::mirror_reply(p, r).into()
+                    }
+                    (p, r) => p.into_reply_once(r.action()),
+                }
+            }
+
+            /// The path being requested by this prompt if its interface has one.
+            pub fn requested_path(&self) -> Option<&str> {
+                match self {
+                    $(Self::$variant(p) => <$iface>::requested_path(&p.constraints),)+
+                    Self::$fallback(p) => <$fallback_iface>::requested_path(&p.constraints),
+                }
+            }
+
+            pub fn requested_permissions(&self) -> Vec<String> {
+                match self {
+                    $(Self::$variant(p) => <$iface>::requested_permissions(&p.constraints),)+
+                    Self::$fallback(p) => {
+                        <$fallback_iface>::requested_permissions(&p.constraints)
+                    }
+                }
+            }
+
+            /// When this prompt was created by snapd.
+            pub fn timestamp(&self) -> DateTime<Utc> {
+                match self {
+                    $(Self::$variant(p) => p.timestamp,)+
+                    Self::$fallback(p) => p.timestamp,
+                }
+            }
+
+            /// The constraints of this prompt as they were provided by snapd.
+            pub fn constraints_json(&self) -> serde_json::Value {
+                let res = match self {
+                    $(Self::$variant(p) => serde_json::to_value(&p.constraints),)+
+                    Self::$fallback(p) => serde_json::to_value(&p.constraints),
+                };
+
+                res.unwrap_or_default()
+            }
+        }
 
-    pub fn id(&self) -> &PromptId {
-        let Self::Home(p) = self;
-        &p.id
-    }
+        impl TryFrom<RawPrompt> for TypedPrompt {
+            type Error = Error;
 
-    pub fn snap(&self) -> &str {
-        let Self::Home(p) = self;
-        &p.snap
-    }
-}
+            fn try_from(raw: RawPrompt) -> Result<Self> {
+                $(
+                    if raw.interface == <$iface>::NAME {
+                        return Ok(Self::$variant(raw.try_into()?));
+                    }
+                )+
 
-impl TryFrom<RawPrompt> for TypedPrompt {
-    type Error = Error;
+                Ok(Self::$fallback(raw.try_into()?))
+            }
+        }
 
-    fn try_from(raw: RawPrompt) -> Result<Self> {
-        match raw.interface.as_str() {
-            "home" => Ok(TypedPrompt::Home(raw.try_into()?)),
+        $(
+            impl From<Prompt<$iface>> for TypedPrompt {
+                fn from(value: Prompt<$iface>) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )+
+
+        impl From<Prompt<$fallback_iface>> for TypedPrompt {
+            fn from(value: Prompt<$fallback_iface>) -> Self {
+                Self::$fallback(value)
+            }
+        }
 
-            interface => Err(Error::UnsupportedInterface {
-                interface: interface.to_string(),
-            }),
+        /// Replies are tagged with their interface so that interfaces sharing the same reply
+        /// shape deserialize to the correct variant. Use [TypedPromptReply::to_snapd_json] for
+        /// the untagged form that is sent to snapd.
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(tag = "interface", rename_all = "kebab-case")]
+        pub enum TypedPromptReply {
+            $($variant(PromptReply<$iface>),)+
+            $fallback(PromptReply<$fallback_iface>),
         }
-    }
-}
 
-impl From<Prompt<HomeInterface>> for TypedPrompt {
-    fn from(value: Prompt<HomeInterface>) -> Self {
-        Self::Home(value)
-    }
+        impl TypedPromptReply {
+            pub fn action(&self) -> Action {
+                match self {
+                    $(Self::$variant(r) => r.action,)+
+                    Self::$fallback(r) => r.action,
+                }
+            }
+
+            pub fn lifespan(&self) -> Lifespan {
+                match self {
+                    $(Self::$variant(r) => r.lifespan,)+
+                    Self::$fallback(r) => r.lifespan,
+                }
+            }
+
+            /// The path pattern being replied to if the interface has one.
+            pub fn path_pattern(&self) -> Option<&str> {
+                match self {
+                    $(Self::$variant(r) => <$iface>::path_pattern(&r.constraints),)+
+                    Self::$fallback(r) => <$fallback_iface>::path_pattern(&r.constraints),
+                }
+            }
+
+            pub fn permissions(&self) -> Vec<String> {
+                match self {
+                    $(Self::$variant(r) => <$iface>::permissions(&r.constraints),)+
+                    Self::$fallback(r) => <$fallback_iface>::permissions(&r.constraints),
+                }
+            }
+
+            /// The body of this reply as expected by snapd, which knows the interface from the
+            /// prompt being replied to.
+            pub(crate) fn to_snapd_json(&self) -> Result<serde_json::Value> {
+                let body = match self {
+                    $(Self::$variant(r) => serde_json::to_value(r)?,)+
+                    Self::$fallback(r) => serde_json::to_value(r)?,
+                };
+
+                Ok(body)
+            }
+        }
+
+        $(
+            impl From<PromptReply<$iface>> for TypedPromptReply {
+                fn from(value: PromptReply<$iface>) -> Self {
+                    Self::$variant(value)
+                }
+            }
+        )+
+
+        impl From<PromptReply<$fallback_iface>> for TypedPromptReply {
+            fn from(value: PromptReply<$fallback_iface>) -> Self {
+                Self::$fallback(value)
+            }
+        }
+
+        #[derive(Debug, Clone, Serialize, Deserialize)]
+        #[serde(tag = "interface", rename_all = "kebab-case")]
+        pub enum TypedUiInput {
+            $($variant(UiInput<$iface>),)+
+            $fallback(UiInput<$fallback_iface>),
+        }
+
+        impl TypedUiInput {
+            pub fn id(&self) -> &PromptId {
+                match self {
+                    $(Self::$variant(input) => &input.id,)+
+                    Self::$fallback(input) => &input.id,
+                }
+            }
+
+            pub fn snap(&self) -> &str {
+                match self {
+                    $(Self::$variant(input) => &input.meta.name,)+
+                    Self::$fallback(input) => &input.meta.name,
+                }
+            }
+
+            /// Restrict the options presented to the user to those that also cover the prompts
+            /// that have been grouped with this one.
+            pub(crate) fn restrict_to_grouped(&mut self, grouped: &[TypedPrompt]) {
+                if grouped.is_empty() {
+                    return;
+                }
+
+                match self {
+                    $(Self::$variant(input) => {
+                        let grouped: Vec<_> = grouped
+                            .iter()
+                            .filter_map(|p| match p {
+                                TypedPrompt::$variant(p) => Some(p),
+                                _ => None,
+                            })
+                            .collect();
+                        <$iface>::restrict_to_grouped(input, &grouped);
+                    })+
+                    Self::$fallback(input) => {
+                        let grouped: Vec<_> = grouped
+                            .iter()
+                            .filter_map(|p| match p {
+                                TypedPrompt::$fallback(p) => Some(p),
+                                _ => None,
+                            })
+                            .collect();
+                        <$fallback_iface>::restrict_to_grouped(input, &grouped);
+                    }
+                }
+            }
+
+            pub fn try_from_prompt(prompt: TypedPrompt, meta: Option<SnapMeta>) -> Result<Self> {
+                match prompt {
+                    $(
+                        TypedPrompt::$variant(p) => {
+                            Ok(Self::$variant(<$iface>::default().map_ui_input(p, meta)?))
+                        }
+                    )+
+                    TypedPrompt::$fallback(p) => Ok(Self::$fallback(
+                        <$fallback_iface>::default().map_ui_input(p, meta)?,
+                    )),
+                }
+            }
+        }
+    };
 }
 
+with_interfaces!(typed_enums);
+
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct RawPrompt {
     pub(crate) id: PromptId,
-    pub(crate) timestamp: String,
+    pub(crate) timestamp: DateTime<Utc>,
     pub(crate) snap: String,
     pub(crate) interface: String,
     pub(crate) constraints: serde_json::Value,
@@ -87,18 +338,6 @@ where
     }
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum TypedPromptReply {
-    Home(PromptReply<HomeInterface>),
-}
-
-impl From<PromptReply<HomeInterface>> for TypedPromptReply {
-    fn from(value: PromptReply<HomeInterface>) -> Self {
-        Self::Home(value)
-    }
-}
-
 #[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub struct Prompt<I>
@@ -106,7 +345,7 @@ where
     I: SnapInterface,
 {
     pub(crate) id: PromptId,
-    pub(crate) timestamp: String,
+    pub(crate) timestamp: DateTime<Utc>,
     pub(crate) snap: String,
     pub(crate) interface: String,
     pub(crate) constraints: I::Constraints,
@@ -124,8 +363,8 @@ where
         &self.snap
     }
 
-    pub fn timestamp(&self) -> &str {
-        &self.timestamp
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
     }
 
     pub fn interface(&self) -> &str {
@@ -142,7 +381,7 @@ where
     pub(crate) action: Action,
     pub(crate) lifespan: Lifespan,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) duration: Option<String>,
+    pub(crate) duration: Option<GoDuration>,
     pub(crate) constraints: I::ReplyConstraints,
 }
 
@@ -163,9 +402,7 @@ where
     }
 
     /// Set this reply to apply for the specified timespan.
-    ///
-    /// Timespans are provided in the format parsable by go's [ParseDuration](https://pkg.go.dev/time#ParseDuration).
-    pub fn for_timespan(mut self, duration: impl Into<String>) -> Self {
+    pub fn for_timespan(mut self, duration: impl Into<GoDuration>) -> Self {
         self.lifespan = Lifespan::Timespan;
         self.duration = Some(duration.into());
         self
@@ -179,30 +416,24 @@ where
     I: SnapInterface,
 {
     pub(crate) id: PromptId,
+    pub(crate) timestamp: DateTime<Utc>,
     pub(crate) meta: SnapMeta,
     pub(crate) data: I::UiInputData,
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-#[serde(untagged)]
-pub enum TypedUiInput {
-    Home(UiInput<HomeInterface>),
-}
-
-impl TypedUiInput {
-    pub fn id(&self) -> &PromptId {
-        let Self::Home(input) = self;
-        &input.id
-    }
-
-    pub fn try_from_prompt(prompt: TypedPrompt, meta: Option<SnapMeta>) -> Result<Self> {
-        let TypedPrompt::Home(p) = prompt;
-
-        Ok(Self::Home(HomeInterface.map_ui_input(p, meta)?))
+impl<I> UiInput<I>
+where
+    I: SnapInterface,
+{
+    /// How long ago the underlying prompt was created by snapd, as of `now`.
+    ///
+    /// Clock skew between us and snapd is clamped to zero rather than reporting a negative age.
+    pub fn age(&self, now: DateTime<Utc>) -> TimeDelta {
+        (now - self.timestamp).max(TimeDelta::zero())
     }
 }
 
-#[derive(Debug, Default, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
 pub struct PromptId(pub String);
 
 #[derive(
@@ -232,6 +463,10 @@ pub enum Lifespan {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::snapd_client::interfaces::{
+        audio_record::AudioRecordInterface, camera::CameraInterface,
+    };
+    use simple_test_case::test_case;
 
     const RAW_PROMPT: &str = r#"{
   "id": "0000000000000002",
@@ -246,12 +481,79 @@ mod tests {
         let raw: RawPrompt = serde_json::from_str(RAW_PROMPT).unwrap();
         let expected = RawPrompt {
             id: PromptId("0000000000000002".to_string()),
-            timestamp: "2024-08-14T07:28:22.694800024Z".to_string(),
+            timestamp: "2024-08-14T07:28:22.694800024Z".parse().unwrap(),
             snap: "firefox".to_string(),
             interface: "home".to_string(),
             constraints: serde_json::json!({}),
         };
 
         assert_eq!(raw, expected);
+        assert_eq!(
+            serde_json::to_value(&raw).unwrap()["timestamp"],
+            "2024-08-14T07:28:22.694800024Z"
+        );
+    }
+
+    #[test_case("2024-08-14T07:28:32Z", TimeDelta::seconds(10); "elapsed")]
+    #[test_case("2024-08-14T07:28:22Z", TimeDelta::zero(); "same instant")]
+    #[test_case("2024-08-14T07:28:12Z", TimeDelta::zero(); "clock skew")]
+    #[test]
+    fn ui_input_age_works(now: &str, expected: TimeDelta) {
+        let input = UiInput::<CameraInterface> {
+            timestamp: "2024-08-14T07:28:22Z".parse().unwrap(),
+            ..Default::default()
+        };
+
+        assert_eq!(input.age(now.parse().unwrap()), expected);
+    }
+
+    #[test]
+    fn typed_replies_round_trip_to_the_correct_interface() {
+        let reply: TypedPromptReply = PromptReply::<AudioRecordInterface> {
+            action: Action::Allow,
+            lifespan: Lifespan::Forever,
+            duration: None,
+            constraints: Default::default(),
+        }
+        .into();
+
+        let json = serde_json::to_string(&reply).unwrap();
+        let parsed: TypedPromptReply = serde_json::from_str(&json).unwrap();
+
+        assert!(
+            matches!(parsed, TypedPromptReply::AudioRecord(_)),
+            "{parsed:?}"
+        );
+        assert_eq!(parsed, reply);
+    }
+
+    #[test]
+    fn typed_ui_input_round_trips_to_the_correct_interface() {
+        let input = TypedUiInput::AudioRecord(UiInput::<AudioRecordInterface>::default());
+
+        let json = serde_json::to_string(&input).unwrap();
+        let parsed: TypedUiInput = serde_json::from_str(&json).unwrap();
+
+        assert!(matches!(parsed, TypedUiInput::AudioRecord(_)), "{parsed:?}");
+    }
+
+    #[test]
+    fn snapd_json_for_a_reply_is_untagged() {
+        let reply: TypedPromptReply = PromptReply::<CameraInterface> {
+            action: Action::Deny,
+            lifespan: Lifespan::Single,
+            duration: None,
+            constraints: Default::default(),
+        }
+        .into();
+
+        assert_eq!(
+            reply.to_snapd_json().unwrap(),
+            serde_json::json!({
+                "action": "deny",
+                "lifespan": "single",
+                "constraints": { "permissions": ["access"] }
+            })
+        );
     }
 }
@@ -0,0 +1,546 @@
+//! Types for working with the prompting rules created by snapd in response to replies that have
+//! a lifespan other than [Lifespan::Single].
+use crate::{
+    snapd_client::{
+        duration::GoDuration,
+        interfaces::{with_interfaces, ReplyConstraintsOverrides, SnapInterface},
+        Action, Lifespan,
+    },
+    Error, Result,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+/// Generate the enum over all supported interfaces for rules.
+///
+/// See [with_interfaces] for how the list of interfaces is defined.
+macro_rules! typed_rule {
+    (
+        known: [$($variant:ident($iface:ty, $proto:ident)),+ $(,)?],
+        fallback: $fallback:ident($fallback_iface:ty, $fallback_proto:ident) $(,)?
+    ) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+        #[serde(untagged, try_from = "RawRule")]
+        pub enum TypedRule {
+            $($variant(Rule<$iface>),)+
+            $fallback(Rule<$fallback_iface>),
+        }
+
+        impl TypedRule {
+            pub fn id(&self) -> &RuleId {
+                match self {
+                    $(Self::$variant(r) => &r.id,)+
+                    Self::$fallback(r) => &r.id,
+                }
+            }
+
+            pub fn snap(&self) -> &str {
+                match self {
+                    $(Self::$variant(r) => &r.snap,)+
+                    Self::$fallback(r) => &r.snap,
+                }
+            }
+
+            pub fn timestamp(&self) -> DateTime<Utc> {
+                match self {
+                    $(Self::$variant(r) => r.timestamp,)+
+                    Self::$fallback(r) => r.timestamp,
+                }
+            }
+
+            pub fn interface(&self) -> &str {
+                match self {
+                    $(Self::$variant(r) => &r.interface,)+
+                    Self::$fallback(r) => &r.interface,
+                }
+            }
+
+            pub fn outcome(&self) -> Action {
+                match self {
+                    $(Self::$variant(r) => r.outcome,)+
+                    Self::$fallback(r) => r.outcome,
+                }
+            }
+
+            pub fn lifespan(&self) -> Lifespan {
+                match self {
+                    $(Self::$variant(r) => r.lifespan,)+
+                    Self::$fallback(r) => r.lifespan,
+                }
+            }
+
+            pub fn expiration(&self) -> Option<DateTime<Utc>> {
+                match self {
+                    $(Self::$variant(r) => r.expiration,)+
+                    Self::$fallback(r) => r.expiration,
+                }
+            }
+
+            /// The path pattern for this rule if its interface has one.
+            pub fn path_pattern(&self) -> Option<&str> {
+                match self {
+                    $(Self::$variant(r) => <$iface>::path_pattern(&r.constraints),)+
+                    Self::$fallback(r) => <$fallback_iface>::path_pattern(&r.constraints),
+                }
+            }
+
+            pub fn permissions(&self) -> Vec<String> {
+                match self {
+                    $(Self::$variant(r) => <$iface>::permissions(&r.constraints),)+
+                    Self::$fallback(r) => <$fallback_iface>::permissions(&r.constraints),
+                }
+            }
+        }
+
+        impl TryFrom<RawRule> for TypedRule {
+            type Error = Error;
+
+            fn try_from(raw: RawRule) -> Result<Self> {
+                $(
+                    if raw.interface == <$iface>::NAME {
+                        return Ok(Self::$variant(raw.try_into()?));
+                    }
+                )+
+
+                Ok(Self::$fallback(raw.try_into()?))
+            }
+        }
+    };
+}
+
+with_interfaces!(typed_rule);
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub struct RuleId(pub String);
+
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RawRule {
+    pub(crate) id: RuleId,
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) snap: String,
+    pub(crate) interface: String,
+    pub(crate) constraints: serde_json::Value,
+    pub(crate) outcome: Action,
+    pub(crate) lifespan: Lifespan,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) expiration: Option<DateTime<Utc>>,
+}
+
+impl<I> TryFrom<RawRule> for Rule<I>
+where
+    I: SnapInterface,
+{
+    type Error = Error;
+
+    fn try_from(
+        RawRule {
+            id,
+            timestamp,
+            snap,
+            interface,
+            constraints,
+            outcome,
+            lifespan,
+            expiration,
+        }: RawRule,
+    ) -> Result<Self> {
+        Ok(Rule {
+            id,
+            timestamp,
+            snap,
+            interface,
+            constraints: serde_json::from_value(constraints)?,
+            outcome,
+            lifespan,
+            expiration,
+        })
+    }
+}
+
+/// A rule stored by snapd. Rule constraints share the same structure as the constraints used
+/// when replying to a prompt for the same interface.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct Rule<I>
+where
+    I: SnapInterface,
+{
+    pub(crate) id: RuleId,
+    pub(crate) timestamp: DateTime<Utc>,
+    pub(crate) snap: String,
+    pub(crate) interface: String,
+    pub(crate) constraints: I::ReplyConstraints,
+    pub(crate) outcome: Action,
+    pub(crate) lifespan: Lifespan,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) expiration: Option<DateTime<Utc>>,
+}
+
+impl<I> Rule<I>
+where
+    I: SnapInterface,
+{
+    pub fn id(&self) -> &RuleId {
+        &self.id
+    }
+
+    pub fn snap(&self) -> &str {
+        &self.snap
+    }
+
+    pub fn timestamp(&self) -> DateTime<Utc> {
+        self.timestamp
+    }
+
+    pub fn interface(&self) -> &str {
+        &self.interface
+    }
+
+    pub fn constraints(&self) -> &I::ReplyConstraints {
+        &self.constraints
+    }
+}
+
+/// A new rule to be added directly via the snapd rules API rather than in response to a prompt.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct NewRule<I>
+where
+    I: SnapInterface,
+{
+    pub(crate) snap: String,
+    pub(crate) interface: String,
+    pub(crate) constraints: I::ReplyConstraints,
+    pub(crate) outcome: Action,
+    pub(crate) lifespan: Lifespan,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) duration: Option<GoDuration>,
+}
+
+impl<I> NewRule<I>
+where
+    I: SnapInterface,
+{
+    /// A new rule for the given snap that will remain in place until it is removed.
+    pub fn new(snap: impl Into<String>, constraints: I::ReplyConstraints, outcome: Action) -> Self {
+        Self {
+            snap: snap.into(),
+            interface: I::NAME.to_string(),
+            constraints,
+            outcome,
+            lifespan: Lifespan::Forever,
+            duration: None,
+        }
+    }
+
+    /// Set this rule to apply for the remainder of the user's current session.
+    pub fn for_session(mut self) -> Self {
+        self.lifespan = Lifespan::Session;
+        self
+    }
+
+    /// Set this rule to apply for the specified timespan.
+    pub fn for_timespan(mut self, duration: impl Into<GoDuration>) -> Self {
+        self.lifespan = Lifespan::Timespan;
+        self.duration = Some(duration.into());
+        self
+    }
+}
+
+/// Changes to make to an existing rule. Any fields that are not set are left unchanged.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub struct RulePatch<I>
+where
+    I: SnapInterface,
+{
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) constraints: Option<I::ReplyConstraints>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) outcome: Option<Action>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) lifespan: Option<Lifespan>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) duration: Option<GoDuration>,
+}
+
+impl<I> Default for RulePatch<I>
+where
+    I: SnapInterface,
+{
+    fn default() -> Self {
+        Self {
+            constraints: None,
+            outcome: None,
+            lifespan: None,
+            duration: None,
+        }
+    }
+}
+
+impl<I> RulePatch<I>
+where
+    I: SnapInterface,
+{
+    pub fn with_constraints(mut self, constraints: I::ReplyConstraints) -> Self {
+        self.constraints = Some(constraints);
+        self
+    }
+
+    pub fn with_outcome(mut self, outcome: Action) -> Self {
+        self.outcome = Some(outcome);
+        self
+    }
+
+    /// Set the lifespan of the rule. A duration must be provided when setting the lifespan to
+    /// [Lifespan::Timespan].
+    pub fn with_lifespan(mut self, lifespan: Lifespan, duration: Option<GoDuration>) -> Self {
+        self.lifespan = Some(lifespan);
+        self.duration = duration;
+        self
+    }
+}
+
+/// Interface agnostic changes to apply to an existing rule.
+///
+/// Constraint fields are applied on top of the current constraints of the rule and are only
+/// supported for interfaces that have them.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct RuleEdit {
+    pub outcome: Option<Action>,
+    pub lifespan: Option<Lifespan>,
+    pub duration: Option<GoDuration>,
+    pub path_pattern: Option<String>,
+    pub permissions: Option<Vec<String>>,
+}
+
+impl RuleEdit {
+    /// Convert this edit into a patch for the given rule.
+    pub(crate) fn into_patch<I>(self, rule: Rule<I>) -> Result<RulePatch<I>>
+    where
+        I: SnapInterface,
+    {
+        let overrides = I::edit_overrides(self.path_pattern, self.permissions)?;
+        let mut patch = RulePatch::default().with_constraints(overrides.apply(rule.constraints));
+        if let Some(outcome) = self.outcome {
+            patch = patch.with_outcome(outcome);
+        }
+        if let Some(lifespan) = self.lifespan {
+            patch = patch.with_lifespan(lifespan, self.duration);
+        }
+
+        Ok(patch)
+    }
+}
+
+/// The body of a POST request to the snapd rules API.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "kebab-case", tag = "action")]
+pub(crate) enum RuleRequest<T> {
+    Add { rule: T },
+    Patch { rule: T },
+    Remove,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::snapd_client::interfaces::home::{
+        HomeInterface, HomeReplyConstraints, PermissionSet,
+    };
+
+    const RULES: &str = r#"[
+      {
+        "id": "0000000000000002",
+        "timestamp": "2024-08-14T07:28:22.694800024Z",
+        "snap": "firefox",
+        "interface": "home",
+        "constraints": {
+          "path-pattern": "/home/ubuntu/Downloads/**",
+          "permissions": ["read", "write"]
+        },
+        "outcome": "allow",
+        "lifespan": "forever"
+      },
+      {
+        "id": "0000000000000003",
+        "timestamp": "2024-08-14T07:30:22.694800024Z",
+        "snap": "cheese",
+        "interface": "camera",
+        "constraints": {
+          "permissions": ["access"]
+        },
+        "outcome": "deny",
+        "lifespan": "timespan",
+        "expiration": "2024-08-14T08:30:22.694800024Z"
+      },
+      {
+        "id": "0000000000000004",
+        "timestamp": "2024-08-14T07:32:22.694800024Z",
+        "snap": "some-snap",
+        "interface": "not-a-real-interface",
+        "constraints": {
+          "foo": "bar"
+        },
+        "outcome": "allow",
+        "lifespan": "session"
+      }
+    ]"#;
+
+    #[test]
+    fn deserializing_rules_works() {
+        let rules: Vec<TypedRule> = serde_json::from_str(RULES).unwrap();
+
+        let summary: Vec<_> = rules
+            .iter()
+            .map(|r| (r.id().0.as_str(), r.interface(), r.outcome(), r.lifespan()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("0000000000000002", "home", Action::Allow, Lifespan::Forever),
+                (
+                    "0000000000000003",
+                    "camera",
+                    Action::Deny,
+                    Lifespan::Timespan
+                ),
+                (
+                    "0000000000000004",
+                    "not-a-real-interface",
+                    Action::Allow,
+                    Lifespan::Session
+                ),
+            ]
+        );
+
+        assert!(matches!(rules[0], TypedRule::Home(_)));
+        assert!(matches!(rules[1], TypedRule::Camera(_)));
+        assert!(matches!(rules[2], TypedRule::Unknown(_)));
+        assert_eq!(
+            rules[1].expiration(),
+            Some("2024-08-14T08:30:22.694800024Z".parse().unwrap())
+        );
+    }
+
+    #[test]
+    fn home_rule_constraints_reuse_reply_constraints() {
+        let rules: Vec<TypedRule> = serde_json::from_str(RULES).unwrap();
+
+        match &rules[0] {
+            TypedRule::Home(r) => {
+                assert_eq!(r.constraints().path_pattern, "/home/ubuntu/Downloads/**");
+                assert_eq!(
+                    r.constraints().permissions,
+                    PermissionSet::from(["read", "write"])
+                );
+            }
+            r => panic!("expected a home rule, got {r:?}"),
+        }
+    }
+
+    #[test]
+    fn add_request_serializes_correctly() {
+        let rule = NewRule::<HomeInterface>::new(
+            "firefox",
+            HomeReplyConstraints {
+                path_pattern: "/home/ubuntu/Downloads/**".to_string(),
+                permissions: PermissionSet::from(["read"]),
+                ..Default::default()
+            },
+            Action::Allow,
+        )
+        .for_timespan(GoDuration::parse("10m").unwrap());
+
+        assert_eq!(
+            serde_json::to_value(RuleRequest::Add { rule }).unwrap(),
+            serde_json::json!({
+                "action": "add",
+                "rule": {
+                    "snap": "firefox",
+                    "interface": "home",
+                    "constraints": {
+                        "path-pattern": "/home/ubuntu/Downloads/**",
+                        "permissions": ["read"]
+                    },
+                    "outcome": "allow",
+                    "lifespan": "timespan",
+                    "duration": "10m"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn patch_request_only_includes_set_fields() {
+        let rule = RulePatch::<HomeInterface>::default().with_outcome(Action::Deny);
+
+        assert_eq!(
+            serde_json::to_value(RuleRequest::Patch { rule }).unwrap(),
+            serde_json::json!({
+                "action": "patch",
+                "rule": { "outcome": "deny" }
+            })
+        );
+    }
+
+    #[test]
+    fn remove_request_serializes_correctly() {
+        assert_eq!(
+            serde_json::to_value(RuleRequest::<()>::Remove).unwrap(),
+            serde_json::json!({ "action": "remove" })
+        );
+    }
+
+    #[test]
+    fn rule_edit_into_patch_overrides_home_constraints() {
+        let rules: Vec<TypedRule> = serde_json::from_str(RULES).unwrap();
+        let rule = match rules.into_iter().next() {
+            Some(TypedRule::Home(r)) => r,
+            r => panic!("expected a home rule, got {r:?}"),
+        };
+
+        let edit = RuleEdit {
+            outcome: Some(Action::Deny),
+            path_pattern: Some("/home/ubuntu/**".to_string()),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            serde_json::to_value(RuleRequest::Patch {
+                rule: edit.into_patch(rule).unwrap()
+            })
+            .unwrap(),
+            serde_json::json!({
+                "action": "patch",
+                "rule": {
+                    "constraints": {
+                        "path-pattern": "/home/ubuntu/**",
+                        "permissions": ["read", "write"]
+                    },
+                    "outcome": "deny"
+                }
+            })
+        );
+    }
+
+    #[test]
+    fn rule_edit_into_patch_rejects_camera_constraints() {
+        let rules: Vec<TypedRule> = serde_json::from_str(RULES).unwrap();
+        let rule = match rules.into_iter().nth(1) {
+            Some(TypedRule::Camera(r)) => r,
+            r => panic!("expected a camera rule, got {r:?}"),
+        };
+
+        let edit = RuleEdit {
+            path_pattern: Some("/home/ubuntu/**".to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            edit.into_patch(rule),
+            Err(Error::InvalidRuleEdit { .. })
+        ));
+    }
+}
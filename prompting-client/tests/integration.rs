@@ -10,6 +10,7 @@ use prompting_client::{
     cli_actions::ScriptedClient,
     prompt_sequence::MatchError,
     snapd_client::{
+        duration::GoDuration,
         interfaces::{home::HomeInterface, SnapInterface},
         Action, Lifespan, PromptId, SnapdSocketClient, TypedPrompt,
     },
@@ -86,12 +87,13 @@ macro_rules! expect_single_prompt {
             let id = pending.remove(0);
             let p = match $c.prompt_details(&id).await {
                 Ok(TypedPrompt::Home(p)) => p,
+                Ok(p) => panic!("expected a home prompt, got: {p:?}"),
                 Err(e) => panic!("error pulling prompt details: {e}"),
             };
 
             assert_eq!(p.snap(), TEST_SNAP);
             assert_eq!(p.path(), $expected_path);
-            assert_eq!(p.requested_permissions(), $expected_permissions);
+            assert_eq!(p.requested_permissions().to_vec(), $expected_permissions);
 
             (id, p)
         }
@@ -153,11 +155,11 @@ async fn happy_path_create_multiple(action: Action, lifespan: Lifespan) -> Resul
     let _rx = spawn_for_output("aa-prompting-test.create", vec![prefix]);
     let path = format!("{dir_path}/test-1.txt");
     let (id, p) = expect_single_prompt!(&mut c, &path, &["write"]).await;
-    let mut reply =
-        HomeInterface::prompt_to_reply(p, action).with_custom_path_pattern(format!("{dir_path}/*"));
+    let mut reply = HomeInterface::prompt_to_reply(p, action)
+        .try_with_custom_path_pattern(format!("{dir_path}/*"))?;
 
     reply = match lifespan {
-        Lifespan::Timespan => reply.for_timespan("1s"),
+        Lifespan::Timespan => reply.for_timespan(Duration::from_secs(1)),
         Lifespan::Session => reply.for_session(),
         Lifespan::Forever => reply.for_forever(),
         Lifespan::Single => {
@@ -210,11 +212,11 @@ async fn create_multiple_actioned_by_other_pid(action: Action, lifespan: Lifespa
 
     let path = format!("{dir_path}/test.txt");
     let (id, p) = expect_single_prompt!(&mut c, &path, &["write"]).await;
-    let mut reply =
-        HomeInterface::prompt_to_reply(p, action).with_custom_path_pattern(format!("{dir_path}/*"));
+    let mut reply = HomeInterface::prompt_to_reply(p, action)
+        .try_with_custom_path_pattern(format!("{dir_path}/*"))?;
 
     reply = match lifespan {
-        Lifespan::Timespan => reply.for_timespan("1s"),
+        Lifespan::Timespan => reply.for_timespan(Duration::from_secs(1)),
         Lifespan::Session => reply.for_session(),
         Lifespan::Forever => reply.for_forever(),
         Lifespan::Single => {
@@ -267,54 +269,62 @@ async fn requesting_an_unknown_prompt_id_is_an_error() -> Result<()> {
     Ok(())
 }
 
-#[test_case("not a valid custom path!", "cannot decode request body into prompt reply: invalid path pattern: pattern must start with"; "malformed path")]
-#[test_case("/home/bob/*", "path pattern in reply constraints does not match originally requested path"; "invalid path")]
+#[test_case("not a valid custom path!", "pattern must be an absolute path"; "malformed path")]
+#[test_case("/home/bob/*", "pattern does not match the requested path"; "invalid path")]
 #[tokio::test]
 #[serial]
-async fn incorrect_custom_paths_error(reply_path: &str, expected_prefix: &str) -> Result<()> {
+async fn incorrect_custom_paths_error(reply_path: &str, expected_reason: &str) -> Result<()> {
     let mut c = SnapdSocketClient::default();
     let (prefix, dir_path) = setup_test_dir(None, &[("test.txt", "test")])?;
 
     let _rx = spawn_for_output("aa-prompting-test.read", vec![prefix]);
     let (id, p) = expect_single_prompt!(&mut c, &format!("{dir_path}/test.txt"), &["read"]).await;
-    let reply = HomeInterface::prompt_to_reply(p, Action::Allow)
-        .with_custom_path_pattern(reply_path)
-        .into();
+    let res = HomeInterface::prompt_to_reply(p.clone(), Action::Allow)
+        .try_with_custom_path_pattern(reply_path);
 
-    match c.reply_to_prompt(&id, reply).await {
-        Err(Error::SnapdError { message, .. }) => assert!(
-            message.starts_with(expected_prefix),
-            "message format not as expected: {message:?}"
-        ),
-        Err(e) => panic!("expected a snapd error, got: {e:?}"),
+    match res {
+        Err(Error::InvalidPathPattern { reason, .. }) => assert_eq!(reason, expected_reason),
+        Err(e) => panic!("expected an invalid path pattern error, got: {e:?}"),
         Ok(_) => panic!("should have errored but got an OK response"),
     }
 
+    // Clean up the outstanding prompt so that it doesn't interfere with other tests
+    c.reply_to_prompt(&id, HomeInterface::prompt_to_reply(p, Action::Deny).into())
+        .await?;
+
     Ok(())
 }
 
-#[test_case("9d", "cannot parse duration: time: unknown unit"; "unknown unit")]
-#[test_case("foo", "cannot parse duration: time: invalid duration"; "invalid duration")]
-#[test_case("5", "cannot parse duration: time: missing unit in duration"; "missing units")]
-#[test_case("0s", "cannot have zero or negative duration"; "zero value")]
-#[test_case("-12s", "cannot have zero or negative duration"; "negative value")]
+// Durations that snapd would fail to parse are rejected before they are sent
+#[test_case("9d"; "unknown unit")]
+#[test_case("foo"; "invalid duration")]
+#[test_case("5"; "missing units")]
+#[test_case("-12s"; "negative value")]
 // 9223372036854775807 is i64::MAX https://pkg.go.dev/time#Duration
-#[test_case("9223372037s", "cannot parse duration: time: invalid duration"; "overflow")]
+#[test_case("9223372037s"; "overflow")]
+#[test]
+fn invalid_timeperiod_duration_errors_locally(timespan: &str) {
+    match timespan.parse::<GoDuration>() {
+        Err(Error::InvalidDuration { .. }) => (),
+        res => panic!("expected InvalidDuration, got {res:?}"),
+    }
+}
+
 #[tokio::test]
 #[serial]
-async fn invalid_timeperiod_duration_errors(timespan: &str, expected_prefix: &str) -> Result<()> {
+async fn zero_timeperiod_duration_errors() -> Result<()> {
     let mut c = SnapdSocketClient::default();
     let (prefix, dir_path) = setup_test_dir(None, &[("test.txt", "test")])?;
 
     let _rx = spawn_for_output("aa-prompting-test.read", vec![prefix]);
     let (id, p) = expect_single_prompt!(&mut c, &format!("{dir_path}/test.txt"), &["read"]).await;
     let reply = HomeInterface::prompt_to_reply(p, Action::Allow)
-        .for_timespan(timespan)
+        .for_timespan(Duration::ZERO)
         .into();
 
     match c.reply_to_prompt(&id, reply).await {
         Err(Error::SnapdError { message, .. }) => assert!(
-            message.starts_with(&format!("invalid duration: {expected_prefix}")),
+            message.starts_with("invalid duration: cannot have zero or negative duration"),
             "message format not as expected: {message}"
         ),
         Err(e) => panic!("expected a snapd error, got: {e}"),
@@ -462,15 +472,8 @@ async fn invalid_prompt_sequence_reply_errors() -> Result<()> {
     )?;
 
     match scripted_client.run(&mut c, None).await {
-        Err(Error::FailedPromptSequence {
-            error: MatchError::UnexpectedError { error },
-        }) => {
-            assert!(
-                error.starts_with(
-                    "path pattern in reply constraints does not match originally requested path"
-                ),
-                "{error}"
-            );
+        Err(Error::InvalidPathPattern { reason, .. }) => {
+            assert_eq!(reason, "pattern does not match the requested path");
         }
         Err(e) => panic!("unexpected error: {e}"),
         Ok(()) => panic!("expected client to error but it ran to completion"),